@@ -51,18 +51,32 @@ item-pickup = {$chara} picked up {$item}.
 item-pick-up-plant = {$item} is a living plant and cannot be moved.
 item-drop = {$chara} dropped {$item}.
 item-owned-by-others = {$item} is owned by others.
+container-put-in = {$chara} put {$item} into {$container}.
+container-take-out = {$chara} took {$item} out of {$container}.
+container-full = {$container} does not have enough space.
+container-nested = {$item} cannot be put into another container.
+stash-put-in = {$chara} put {$item} in the stash.
+stash-take-out = {$chara} took {$item} from the stash.
 drink-item = {$chara} drank a {$item}.
 eat-item = {$chara} ate a {$item}.
 harvest-plant = {$chara} harvested {$item} x {$n}.
 harvest-plant-not-ready = {$item} cannot be harvested yet.
 use-active-skill-magic = {$chara} used magic "{$active_skill}".
 use-active-skill-special = {$chara} used special skill "{$active_skill}".
+active-skill-lack-of-mp = {$chara} does not have enough MP to use "{$active_skill}".
+active-skill-unavailable = {$chara} cannot use "{$active_skill}" now.
 
 # Messages about using tools
 
 use-tool-without-equip = No tool equiped!
 building-not-adjacent-tile = Need to specify an adjacent tile to build.
 building-shortage-material = Need {$item} x {$n} more to build it.
+building-not-buildable-tile = Cannot build it on this tile.
+building-lack-of-skill = Construction skill level {$skill_level} is needed to build {$item}.
+building-start = {$chara} started to build {$item}.
+building-finish = {$chara} finished building {$item}.
+deconstruct-start = {$chara} started to deconstruct {$item}.
+deconstruct-finish = {$chara} finished deconstructing {$item}.
 chopping-no-tree = There is no tree to chop.
 chopping-not-adjacent-tile = Need to specify an adjacent tile to chop.
 mining-not-adjacent-tile = Need to specify an adjacent tile to mine.
@@ -75,6 +89,7 @@ use_item-deed-succeed = You built new home!
 # Messages when a character is affected
 
 heal-hp = {$chara} was healed ({$value}).
+recover-mp = {$chara} recovered MP ({$value}).
 fall-asleep = {$chara} fell asleep.
 poisoned = {$chara} was poisoned.
 scanned = {$chara} was scanned.
@@ -82,12 +97,27 @@ not-scanned = {$chara} isn't scanned.
 asleep = {$chara} is asleep.
 poison-damage = {$chara} was damaged by poison ({$damage}).
 shop-lack-of-money = {$chara} do not have enough money to buy it.
+shop-not-buy = The shopkeeper is not interested in {$item}.
 
 # Messages about quest
 
 quest-complete-slay_monsters = Quest completed! Slayed {$monster} x {$n}.
+quest-complete = Quest completed! ({$quest})
+quest-failed = Quest failed... ({$quest})
+quest-abandoned = Abandoned the quest. ({$quest})
+quest-receive-item = Received {$item} to deliver.
 quest-reward-receive-money = Recieved {$money} gold as the quest reward.
 
+# Messages about party
+
+party-join = {$chara} joined your party.
+party-leave = {$chara} left your party.
+party-full = Your party is full.
+party-order-follow = You ordered {$chara} to follow you.
+party-order-wait = You ordered {$chara} to wait.
+party-order-attack-target = You ordered {$chara} to attack your target.
+hire-lack-of-money = You do not have enough money to hire {$chara}.
+
 # Messages about creation
 
 creation-start = {$chara} started making {$product}.
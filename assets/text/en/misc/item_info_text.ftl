@@ -2,6 +2,7 @@ item_info_text-item_kind = Kind of this item: {$item_kind}
 item_info_text-material = This is made from {$material}
 item_info_text-medical_effect = {$medical_effect}
 item_info_text-nutrition = Nutrition: {$nutrition}
+item_info_text-container = Contents: {$w}/{$capacity} kg
 item_info_text-melee_weapon = Melee attack: {$eff}
 item_info_text-ranged_weapon = Ranged attack: {$eff}
 item_info_text-defence = Defence: Physical {$physical}, Fire {$fire}, Cold {$cold}, Shock {$shock}, Poison {$poison}, Spirit {$spirit}
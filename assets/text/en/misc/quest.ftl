@@ -1,2 +1,17 @@
 quest-slay_monsters = Slay monsters ({$monster})
+quest-fetch_items = Fetch items ({$item})
+quest-deliver = Delivery ({$chara})
+quest-escort = Escort ({$chara})
+quest-explore_dungeon = Exploration ({$site})
+quest-craft = Crafting ({$item})
+quest-deadline = Deadline: {$date}
+quest-state-active = Active
+quest-state-completed = Completed
+quest-state-reward_received = Reward received
+quest-state-failed = Failed
 desc-quest-slay_monsters = Slay "{$monster}" x {$n}.
+desc-quest-fetch_items = Bring "{$item}" x {$n} to a quest board.
+desc-quest-deliver = Deliver "{$item}" to {$chara} in {$site}.
+desc-quest-escort = Escort {$chara} to {$site}.
+desc-quest-explore_dungeon = Reach floor {$n} of {$site}.
+desc-quest-craft = Create "{$item}" x {$n}.
@@ -50,6 +50,7 @@ skill_kind-throwing = Throwing
 skill_kind-magic_device = Magic Device
 skill_kind-mining = Mining
 skill_kind-plants = Plants
+skill_kind-negotiation = Negotiation
 
 # WeaponKind
 
@@ -9,6 +9,8 @@ dialog-choice-main_menu = Main menu
 dialog-choice-restart = Restart
 item_menu-infomation = Infomation
 item_menu-drop_all = Drop all
+item_menu-put_in = Put in
+item_menu-take_out = Take out
 item_menu-register-as-shortcut = Register as shortcut
 tile-menu-chop = Chop a tree
 tile-menu-down-stairs = Go down the stairs
@@ -17,10 +19,17 @@ tile-menu-enter-wilderness = Enter wilderness
 tile-menu-exit-to-region-map = Exit to region map
 tile-menu-deconstruct = Deconstruct
 tile-menu-harvest = Harvest
+tile-menu-hire = Hire
 tile-menu-infomation = Infomation
+tile-menu-take-out-from-stash = Take items from the stash
 tile-menu-target = Target
 tile-menu-move-to-next-map = Move to next map
+tile-menu-party-attack-target = Attack my target
+tile-menu-party-follow = Follow me
+tile-menu-party-leave = Leave the party
+tile-menu-party-wait = Wait here
 tile-menu-pick-up-items = Pick up items
+tile-menu-put-in-stash = Put items in the stash
 tile-menu-start-centering = Centering
 tile-menu-stop-centering = Stop centering
 tile-menu-up-stairs = Go up the stairs
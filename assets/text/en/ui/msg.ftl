@@ -4,5 +4,9 @@ dialog-gameover = You die...
 dialog-move_floor = Do you want to move from this floor?
 dialog-enter_site = Do you want to enter {$site_name}?
 dialog-undertake_quest = Do you undertake this quest?
+dialog-abandon_quest = Do you abandon this quest?
+dialog-dismiss_quest = Do you remove this failed quest from the list?
+dialog-deconstruct = Do you want to deconstruct {$obj}?
+dialog-hire = Do you want to hire {$chara} for {$cost} gold?
 newgame-chooseclass = Choose your class
 newgame-inputplayername = Please input your name.
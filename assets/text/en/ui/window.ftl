@@ -7,6 +7,7 @@ tab_text-creation_cooking = Cook
 tab_text-creation_craft = Craft
 tab_text-creation_pharmacy = Pharmacy
 tab_text-creation_smith = Smith
+tab_text-creation_other = Other
 tab_text-item_list = List
 tab_text-item_drop = Drop
 tab_text-item_throw = Throw
@@ -26,3 +27,5 @@ label_text-creation-enough-ingredients = Enough ingredients
 label_text-creation-not-enough-ingredients = Not enough ingredients
 label_text-creation-required_skill = Required Skill
 list_item_text-creation-no_ingredient = No available {$group}
+label_text-build-required_skill = Required Skill: {$skill_level} (Construction {$player_skill_level})
+label_text-build-materials = Materials: {$materials}
@@ -45,18 +45,32 @@ item-pickup = {$chara}は{$item}を拾った。
 item-pick-up-plant = {$item}は植物であり、拾うことはできない。
 item-drop = {$chara}は{$item}を床に置いた。
 item-owned-by-others = {$item}は他者の所有物である。
+container-put-in = {$chara}は{$item}を{$container}に入れた。
+container-take-out = {$chara}は{$item}を{$container}から取り出した。
+container-full = {$container}には十分な空きがない。
+container-nested = {$item}を他の入れ物に入れることはできない。
+stash-put-in = {$chara}は{$item}を倉庫に入れた。
+stash-take-out = {$chara}は{$item}を倉庫から取り出した。
 drink-item = {$chara}は{$item}を飲み干した。
 eat-item = {$chara}は{$item}を食べた。
 harvest-plant = {$chara}は{$item} x {$n}を収穫した。
 harvest-plant-not-ready = {$item}はまだ収穫できないようだ。
 use-active-skill-magic = {$chara}は"{$active_skill}"を使用した。
 use-active-skill-special = {$chara}は"{$active_skill}"を使用した。
+active-skill-lack-of-mp = {$chara}は"{$active_skill}"を使用するためのMPが足りない。
+active-skill-unavailable = {$chara}は今"{$active_skill}"を使用できない。
 
 # Messages about using tools
 
 use-tool-without-equip = 道具を持っていない！
 building-not-adjacent-tile = 建築には隣接タイルを指定しなければならない。
 building-shortage-material = 建築には{$item} x {$n}が必要だ。
+building-not-buildable-tile = このタイルには建築できない。
+building-lack-of-skill = {$item}の建築には建築スキルレベル{$skill_level}が必要だ。
+building-start = {$chara}は{$item}の建築を始めた。
+building-finish = {$chara}は{$item}を建築した。
+deconstruct-start = {$chara}は{$item}の解体を始めた。
+deconstruct-finish = {$chara}は{$item}を解体した。
 chopping-no-tree = 切り倒す木が無い。
 chopping-not-adjacent-tile = 木を切り倒すには隣接タイルを指定しなければならない。
 mining-not-adjacent-tile = 掘るには隣接タイルを指定しなければならない。
@@ -69,6 +83,7 @@ use_item-deed-succeed = 新たな家を建設した！
 # Messages when a character is affected
 
 heal-hp = {$chara}は回復した({$value})。
+recover-mp = {$chara}のMPが回復した({$value})。
 fall-asleep = {$chara}は眠りに落ちた。
 poisoned = {$chara}は毒を受けた。
 scanned = {$chara}のスキャンが完了した。
@@ -76,12 +91,27 @@ not-scanned = {$chara}はまだスキャンされていない。
 asleep = {$chara}は眠っている。
 poison-damage = {$chara}は毒のダメージを受けた({$damage})。
 shop-lack-of-money = {$chara}はそれを買うのに十分なお金を持っていない。
+shop-not-buy = 店主は{$item}に興味がないようだ。
 
 # Messages about quest
 
 quest-complete-slay_monsters = {$monster}を{$n}体始末した。クエストを達成した。
+quest-complete = クエストを達成した。({$quest})
+quest-failed = クエストに失敗した…。({$quest})
+quest-abandoned = クエストを放棄した。({$quest})
+quest-receive-item = 配達する{$item}を受け取った。
 quest-reward-receive-money = {$money}ゴールドを報酬として受け取った。
 
+# Messages about party
+
+party-join = {$chara}がパーティに加わった。
+party-leave = {$chara}がパーティから外れた。
+party-full = これ以上パーティに加えることはできない。
+party-order-follow = {$chara}についてくるよう指示した。
+party-order-wait = {$chara}に待機するよう指示した。
+party-order-attack-target = {$chara}にターゲットを攻撃するよう指示した。
+hire-lack-of-money = {$chara}を雇うのに十分なお金を持っていない。
+
 # Messages about creation
 
 creation-start = {$chara}は{$product}の作成を始めた。
@@ -2,6 +2,7 @@ item_info_text-item_kind = アイテムの種別: {$item_kind}
 item_info_text-material = 素材: {$material}
 item_info_text-medical_effect = {$medical_effect}
 item_info_text-nutrition = 栄養価: {$nutrition}
+item_info_text-container = 中身: {$w}/{$capacity} kg
 item_info_text-melee_weapon = 近接攻撃力: {$eff}
 item_info_text-ranged_weapon = 遠隔攻撃力: {$eff}
 item_info_text-defence = 守備力: 物理 {$physical}, 火炎 {$fire}, 冷気 {$cold}, 電撃 {$shock}, 毒 {$poison}, 精神 {$spirit}
@@ -1,2 +1,17 @@
 quest-slay_monsters = モンスター討伐 ({$monster})
+quest-fetch_items = 収集 ({$item})
+quest-deliver = 配達 ({$chara})
+quest-escort = 護衛 ({$chara})
+quest-explore_dungeon = 探索 ({$site})
+quest-craft = 製作 ({$item})
+quest-deadline = 期限: {$date}
+quest-state-active = 進行中
+quest-state-completed = 達成
+quest-state-reward_received = 報酬受取済
+quest-state-failed = 失敗
 desc-quest-slay_monsters = {$monster}を{$n}体倒す。
+desc-quest-fetch_items = {$item}を{$n}個クエストボードに持ってくる。
+desc-quest-deliver = {$item}を{$site}の{$chara}に届ける。
+desc-quest-escort = {$chara}を{$site}まで護衛する。
+desc-quest-explore_dungeon = {$site}の{$n}階に到達する。
+desc-quest-craft = {$item}を{$n}個製作する。
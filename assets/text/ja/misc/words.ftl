@@ -50,6 +50,7 @@ skill_kind-throwing = 投擲
 skill_kind-magic_device = 魔道具
 skill_kind-mining = 採掘
 skill_kind-plants = 栽培
+skill_kind-negotiation = 交渉
 
 # WeaponKind
 
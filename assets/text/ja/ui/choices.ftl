@@ -9,6 +9,8 @@ dialog-choice-main_menu = メインメニュー
 dialog-choice-restart = 再開
 item_menu-infomation = 情報
 item_menu-drop_all = 全部置く
+item_menu-put_in = 入れる
+item_menu-take_out = 取り出す
 item_menu-register-as-shortcut = ショートカット登録
 tile-menu-chop = 木を切る
 tile-menu-down-stairs = 階段を降りる
@@ -17,10 +19,17 @@ tile-menu-enter-wilderness = 野外に入る
 tile-menu-exit-to-region-map = 外に出る
 tile-menu-deconstruct = 解体する
 tile-menu-harvest = 収穫する
+tile-menu-hire = 雇う
 tile-menu-infomation = 情報
+tile-menu-take-out-from-stash = 倉庫から取り出す
 tile-menu-target = ターゲット
 tile-menu-move-to-next-map = 次のマップへ
+tile-menu-party-attack-target = ターゲットを攻撃して
+tile-menu-party-follow = ついて来て
+tile-menu-party-leave = パーティから外す
+tile-menu-party-wait = ここで待機して
 tile-menu-pick-up-items = 拾う
+tile-menu-put-in-stash = 倉庫に入れる
 tile-menu-start-centering = センタリング
 tile-menu-stop-centering = センタリングをやめる
 tile-menu-up-stairs = 階段を上がる
@@ -4,6 +4,7 @@ command-open_equip_win = 装備画面
 command-open_exit_win = 終了画面
 command-open_game_info_win = ゲーム情報
 command-open_help_win = ヘルプ画面
+command-open_quest_win = クエスト画面
 command-open_status_win = ステータス画面
 command-open_item_menu = アイテム画面
 command-pick_up_item = アイテムを拾う
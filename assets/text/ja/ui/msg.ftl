@@ -4,5 +4,9 @@ dialog-gameover = やられてしまった…。
 dialog-move_floor = この階から移動しますか?
 dialog-enter_site = {$site_name}に入りますか?
 dialog-undertake_quest = このクエストを受諾しますか?
+dialog-abandon_quest = このクエストを放棄しますか?
+dialog-dismiss_quest = この失敗したクエストをリストから削除しますか?
+dialog-deconstruct = {$obj}を解体しますか?
+dialog-hire = {$chara}を{$cost}ゴールドで雇いますか?
 newgame-chooseclass = クラスを選択して下さい
 newgame-inputplayername = プレイヤー名を入力して下さい
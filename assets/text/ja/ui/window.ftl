@@ -7,6 +7,7 @@ tab_text-creation_cooking = 料理
 tab_text-creation_craft = 細工
 tab_text-creation_pharmacy = 薬学
 tab_text-creation_smith = 鍛冶
+tab_text-creation_other = その他
 tab_text-item_list = リスト
 tab_text-item_drop = 置く
 tab_text-item_throw = 投げる
@@ -25,3 +26,5 @@ label_text-creation-enough-ingredients = 原材料
 label_text-creation-not-enough-ingredients = 原材料不足
 label_text-creation-required_skill = 必要スキル
 list_item_text-creation-no_ingredient = 利用不能な素材 {$group}
+label_text-build-required_skill = 必要スキル: {$skill_level} (建築 {$player_skill_level})
+label_text-build-materials = 資材: {$materials}
@@ -0,0 +1,52 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Audio backend used by play functions
+pub trait AudioBackend {
+    fn play_sound(&mut self, name: &str);
+    fn play_music(&mut self, name: &str);
+}
+
+/// Audio backend that ignores all requests, for headless and muted runs
+#[derive(Default)]
+pub struct NullBackend;
+
+impl AudioBackend for NullBackend {
+    fn play_sound(&mut self, _name: &str) {}
+    fn play_music(&mut self, _name: &str) {}
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum AudioRecord {
+    Sound(String),
+    Music(String),
+}
+
+pub type AudioRecords = Rc<RefCell<Vec<AudioRecord>>>;
+
+/// Audio backend that records requested sounds and music
+#[derive(Default)]
+pub struct RecordingBackend {
+    records: AudioRecords,
+}
+
+impl RecordingBackend {
+    /// Returns the shared record list. It can be read after the backend is passed to init.
+    pub fn records(&self) -> AudioRecords {
+        self.records.clone()
+    }
+}
+
+impl AudioBackend for RecordingBackend {
+    fn play_sound(&mut self, name: &str) {
+        self.records
+            .borrow_mut()
+            .push(AudioRecord::Sound(name.to_owned()));
+    }
+
+    fn play_music(&mut self, name: &str) {
+        self.records
+            .borrow_mut()
+            .push(AudioRecord::Music(name.to_owned()));
+    }
+}
@@ -6,6 +6,7 @@ extern crate log;
 
 #[macro_use]
 mod tool;
+mod backend;
 mod datwalker;
 mod musictable;
 mod wavtable;
@@ -15,30 +16,51 @@ use crate::wavtable::WavTable;
 use std::cell::RefCell;
 use std::path::Path;
 
-thread_local!(static AUDIO_PLAYER: RefCell<Option<AudioPlayer>> = RefCell::new(None));
+pub use crate::backend::{AudioBackend, AudioRecord, AudioRecords, NullBackend, RecordingBackend};
+
+thread_local!(static AUDIO_BACKEND: RefCell<Option<Box<dyn AudioBackend>>> = RefCell::new(None));
 
 pub struct AudioContext {
-    _mixer_context: sdl2::mixer::Sdl2MixerContext,
+    _mixer_context: Option<sdl2::mixer::Sdl2MixerContext>,
 }
 
-/// Initialize AudioPlayer
+/// Initialize AudioPlayer that uses SDL mixer.
+/// Falls back to NullBackend if the audio device is not available.
 pub fn init<P: AsRef<Path>>(data_dirs: &[P], music_volume: i32) -> AudioContext {
-    let mixer_context = init_device();
+    let mixer_context = match init_device() {
+        Ok(mixer_context) => mixer_context,
+        Err(e) => {
+            warn!("cannot initialize audio device, sound is disabled: {}", e);
+            return init_with_backend(Box::new(NullBackend));
+        }
+    };
 
-    AUDIO_PLAYER.with(|a| {
-        assert!(a.borrow().is_none());
-        *a.borrow_mut() = Some(AudioPlayer::new(data_dirs));
-    });
+    set_backend(Box::new(AudioPlayer::new(data_dirs)));
     sdl2::mixer::Music::set_volume(music_volume);
     AudioContext {
-        _mixer_context: mixer_context,
+        _mixer_context: Some(mixer_context),
+    }
+}
+
+/// Initialize with the given backend without audio devices
+pub fn init_with_backend(backend: Box<dyn AudioBackend>) -> AudioContext {
+    set_backend(backend);
+    AudioContext {
+        _mixer_context: None,
     }
 }
 
-pub fn with_audio_player<F: FnOnce(&AudioPlayer)>(f: F) {
-    AUDIO_PLAYER.with(|a| {
+fn set_backend(backend: Box<dyn AudioBackend>) {
+    AUDIO_BACKEND.with(|a| {
+        assert!(a.borrow().is_none());
+        *a.borrow_mut() = Some(backend);
+    });
+}
+
+pub fn with_audio_backend<F: FnOnce(&mut dyn AudioBackend)>(f: F) {
+    AUDIO_BACKEND.with(|a| {
         assert!(a.borrow().is_some());
-        f(a.borrow().as_ref().unwrap());
+        f(a.borrow_mut().as_mut().unwrap().as_mut());
     });
 }
 
@@ -47,7 +69,7 @@ pub fn play_sound(name: &str) {
     if name.is_empty() {
         return;
     }
-    with_audio_player(|a| {
+    with_audio_backend(|a| {
         a.play_sound(name);
     });
 }
@@ -58,13 +80,13 @@ pub fn play_music(name: &str) {
         return;
     }
 
-    with_audio_player(|a| {
+    with_audio_backend(|a| {
         a.play_music(name);
     });
 }
 
 fn finalize() {
-    AUDIO_PLAYER.with(|a| {
+    AUDIO_BACKEND.with(|a| {
         assert!(a.borrow().is_some());
         *a.borrow_mut() = None;
     });
@@ -76,6 +98,7 @@ impl Drop for AudioContext {
     }
 }
 
+/// Audio backend that plays sounds and music by SDL mixer
 pub struct AudioPlayer {
     wavtable: WavTable,
     musictable: MusicTable,
@@ -90,21 +113,23 @@ impl AudioPlayer {
             musictable,
         }
     }
+}
 
-    pub fn play_sound(&self, name: &str) {
+impl AudioBackend for AudioPlayer {
+    fn play_sound(&mut self, name: &str) {
         if let Err(e) = self.wavtable.play(name) {
             warn!("{}", e);
         }
     }
 
-    pub fn play_music(&self, name: &str) {
+    fn play_music(&mut self, name: &str) {
         if let Err(e) = self.musictable.play(name) {
             warn!("{}", e);
         }
     }
 }
 
-fn init_device() -> sdl2::mixer::Sdl2MixerContext {
+fn init_device() -> Result<sdl2::mixer::Sdl2MixerContext, String> {
     use sdl2::mixer::{InitFlag, AUDIO_S16LSB, DEFAULT_CHANNELS};
 
     // Initialization for sound
@@ -112,12 +137,12 @@ fn init_device() -> sdl2::mixer::Sdl2MixerContext {
     let format = AUDIO_S16LSB; // signed 16 bit samples, in little-endian byte order
     let channels = DEFAULT_CHANNELS; // Stereo
     let chunk_size = 1024;
-    sdl2::mixer::open_audio(frequency, format, channels, chunk_size).unwrap();
-    let mixer_context = sdl2::mixer::init(InitFlag::OPUS).unwrap();
+    sdl2::mixer::open_audio(frequency, format, channels, chunk_size)?;
+    let mixer_context = sdl2::mixer::init(InitFlag::OPUS)?;
 
     sdl2::mixer::allocate_channels(1);
 
-    mixer_context
+    Ok(mixer_context)
 }
 
 #[cfg(test)]
@@ -145,4 +170,23 @@ mod tests {
         play_sound("anim.club");
         std::thread::sleep(std::time::Duration::from_millis(3000));
     }
+
+    #[test]
+    fn recording_backend() {
+        let backend = RecordingBackend::default();
+        let records = backend.records();
+        let _audio_context = init_with_backend(Box::new(backend));
+
+        play_music("test");
+        play_sound("anim.club");
+        play_sound("");
+
+        assert_eq!(
+            *records.borrow(),
+            vec![
+                AudioRecord::Music("test".into()),
+                AudioRecord::Sound("anim.club".into())
+            ]
+        );
+    }
 }
@@ -7,7 +7,7 @@ use super::skill::{CreationKind, SkillKind, SkillList};
 use super::traits::*;
 use super::unknown_id_err;
 use crate::basic::{BonusLevel, ARRAY_STR_ID_LEN};
-use crate::objholder::{CharaTemplateIdx, ItemIdx};
+use crate::objholder::{CharaTemplateIdx, DecoIdx, ItemIdx, TileIdx, WallIdx};
 use geom::Vec2d;
 use std::collections::HashMap;
 
@@ -76,6 +76,8 @@ pub struct Chara {
     pub active_skills: Vec<(ActiveSkillOrigin, ActiveSkillId)>,
     /// When talked, execute this script
     pub trigger_talk: Option<String>,
+    #[serde(default)]
+    pub mp: i32,
 }
 
 /// Character attributes
@@ -101,6 +103,35 @@ pub struct CharaAttributes {
     pub spd: u16,
     /// Range of view in tile
     pub view_range: i32,
+    /// Max MP
+    #[serde(default)]
+    pub max_mp: i32,
+}
+
+/// Kinds of character attributes except max HP, max MP and view range
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum CharaAttrKind {
+    Str,
+    Vit,
+    Dex,
+    Int,
+    Wil,
+    Cha,
+    Spd,
+}
+
+impl CharaAttributes {
+    pub fn get(&self, kind: CharaAttrKind) -> u16 {
+        match kind {
+            CharaAttrKind::Str => self.str,
+            CharaAttrKind::Vit => self.vit,
+            CharaAttrKind::Dex => self.dex,
+            CharaAttrKind::Int => self.int,
+            CharaAttrKind::Wil => self.wil,
+            CharaAttrKind::Cha => self.cha,
+            CharaAttrKind::Spd => self.spd,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize)]
@@ -204,6 +235,22 @@ pub enum Work {
         item_idx: ItemIdx,
         il: ItemLocation,
     },
+    Build {
+        pos: Vec2d,
+        obj: BuildObj,
+    },
+    Deconstruct {
+        pos: Vec2d,
+        obj: BuildObj,
+    },
+}
+
+/// Objects that can be built on maps
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum BuildObj {
+    Tile(TileIdx),
+    Wall(WallIdx),
+    Deco(DecoIdx),
 }
 
 impl Default for Chara {
@@ -227,6 +274,7 @@ impl Default for Chara {
             skills: SkillList::default(),
             active_skills: Vec::new(),
             trigger_talk: None,
+            mp: 0,
         }
     }
 }
@@ -253,6 +301,9 @@ pub enum CharaId {
     /// Indexed for a map. This character don't appear on other maps.
     /// Randomly generated characters use this id.
     OnMap { mid: MapId, n: u32 },
+    /// Party member. This character is not associated any site or map,
+    /// and moves to other maps with the player.
+    Party { n: u32 },
 }
 
 /// Data to determine NPC character's actions
@@ -273,6 +324,12 @@ pub struct CharaAi {
 #[serde(transparent)]
 pub struct NpcAiKind(arrayvec::ArrayString<ARRAY_STR_ID_LEN>);
 
+impl NpcAiKind {
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
 impl Default for NpcAiKind {
     fn default() -> NpcAiKind {
         NpcAiKind(arrayvec::ArrayString::from("default").unwrap())
@@ -351,12 +408,31 @@ impl CharaHolder {
         .unwrap_or_else(|| unknown_id_err(cid))
     }
 
+    /// Characters associated with the given site
+    pub fn on_site_charas(&self, sid: SiteId) -> Vec<CharaId> {
+        let mut ns: Vec<u32> = self
+            .c
+            .keys()
+            .filter_map(|cid| match *cid {
+                CharaId::OnSite { sid: s, n } if s == sid => Some(n),
+                _ => None,
+            })
+            .collect();
+        ns.sort_unstable();
+        ns.into_iter().map(|n| CharaId::OnSite { sid, n }).collect()
+    }
+
     pub(crate) fn remove_chara(&mut self, cid: CharaId) {
+        self.take(cid);
+    }
+
+    /// Remove the character data and returns it
+    pub(crate) fn take(&mut self, cid: CharaId) -> Option<Chara> {
         match cid {
             CharaId::OnMap { .. } => &mut self.on_map,
             _ => &mut self.c,
         }
-        .remove(&cid);
+        .remove(&cid)
     }
 
     pub(crate) fn replace_on_map_chara(
@@ -19,6 +19,13 @@ pub struct Effect {
     pub anim_img_shot: String,
     #[serde(default)]
     pub sound: String,
+    /// Central angle of sector shaped effects in degrees
+    #[serde(default = "default_sector_angle")]
+    pub sector_angle: u32,
+}
+
+fn default_sector_angle() -> u32 {
+    90
 }
 
 impl Default for Effect {
@@ -34,6 +41,7 @@ impl Default for Effect {
             anim_img: "".into(),
             anim_img_shot: "".into(),
             sound: "".into(),
+            sector_angle: default_sector_angle(),
         }
     }
 }
@@ -64,6 +64,16 @@ pub struct FactionRelation(i16);
 const FACTION_RELATION_MAX: i16 = 10000;
 const FACTION_RELATION_MIN: i16 = -10000;
 
+impl FactionRelation {
+    pub fn new(value: i16) -> Self {
+        FactionRelation(value.clamp(FACTION_RELATION_MIN, FACTION_RELATION_MAX))
+    }
+
+    pub fn value(self) -> i16 {
+        self.0
+    }
+}
+
 impl std::ops::Add<i16> for FactionRelation {
     type Output = Self;
     fn add(self, other: i16) -> Self {
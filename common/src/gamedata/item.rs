@@ -120,6 +120,49 @@ impl Item {
     pub fn obj(&self) -> &'static ItemObject {
         crate::gobj::get_obj(self.idx)
     }
+
+    /// Containers are not stacked because each one has its own contents
+    pub fn is_stackable(&self) -> bool {
+        self.kind != ItemKind::Container
+    }
+
+    /// Clone this item without the contents of the container.
+    /// Used when items are split, so the contents are not duplicated.
+    fn clone_for_split(&self) -> Item {
+        let mut item = self.clone();
+        item.attrs
+            .retain(|attr| !matches!(attr, ItemAttr::Container(_)));
+        item
+    }
+
+    /// Get the contents if this item is a container
+    pub fn container(&self) -> Option<&ItemList> {
+        self.attrs.iter().find_map(|attr| match attr {
+            ItemAttr::Container(list) => Some(list),
+            _ => None,
+        })
+    }
+
+    /// Get the mutable contents if this item is a container
+    pub fn container_mut(&mut self) -> Option<&mut ItemList> {
+        if self.kind != ItemKind::Container {
+            return None;
+        }
+        let i = if let Some(i) = self
+            .attrs
+            .iter()
+            .position(|attr| matches!(attr, ItemAttr::Container(_)))
+        {
+            i
+        } else {
+            self.attrs.push(ItemAttr::Container(ItemList::default()));
+            self.attrs.len() - 1
+        };
+        match &mut self.attrs[i] {
+            ItemAttr::Container(list) => Some(list),
+            _ => unreachable!(),
+        }
+    }
 }
 
 /// This is mainly used for item list sorting
@@ -311,22 +354,81 @@ pub struct ItemGen {
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum ItemListLocation {
+    OnMap {
+        mid: super::map::MapId,
+        pos: Vec2d,
+    },
+    Chara {
+        cid: super::chara::CharaId,
+    },
+    Equip {
+        cid: super::chara::CharaId,
+    },
+    Shop {
+        cid: super::CharaId,
+    },
+    /// Contents of the container item at i on ill
+    Container {
+        ill: ItemListLocationExceptContainer,
+        i: u32,
+    },
+    /// Stash at the player's base
+    Stash {
+        sid: super::site::SiteId,
+    },
+}
+
+/// Item list locations that can have containers.
+/// Containers cannot be nested.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum ItemListLocationExceptContainer {
     OnMap { mid: super::map::MapId, pos: Vec2d },
     Chara { cid: super::chara::CharaId },
     Equip { cid: super::chara::CharaId },
     Shop { cid: super::CharaId },
+    Stash { sid: super::site::SiteId },
 }
 
 impl ItemListLocation {
     pub const PLAYER: ItemListLocation = ItemListLocation::Chara {
         cid: super::chara::CharaId::Player,
     };
+
+    /// Get the location of the contents of the container item at il.
+    /// Returns None if il is in a container.
+    pub fn container(il: ItemLocation) -> Option<ItemListLocation> {
+        let ill = match il.0 {
+            ItemListLocation::OnMap { mid, pos } => {
+                ItemListLocationExceptContainer::OnMap { mid, pos }
+            }
+            ItemListLocation::Chara { cid } => ItemListLocationExceptContainer::Chara { cid },
+            ItemListLocation::Equip { cid } => ItemListLocationExceptContainer::Equip { cid },
+            ItemListLocation::Shop { cid } => ItemListLocationExceptContainer::Shop { cid },
+            ItemListLocation::Stash { sid } => ItemListLocationExceptContainer::Stash { sid },
+            ItemListLocation::Container { .. } => return None,
+        };
+        Some(ItemListLocation::Container { ill, i: il.1 })
+    }
+}
+
+impl From<ItemListLocationExceptContainer> for ItemListLocation {
+    fn from(ill: ItemListLocationExceptContainer) -> ItemListLocation {
+        match ill {
+            ItemListLocationExceptContainer::OnMap { mid, pos } => {
+                ItemListLocation::OnMap { mid, pos }
+            }
+            ItemListLocationExceptContainer::Chara { cid } => ItemListLocation::Chara { cid },
+            ItemListLocationExceptContainer::Equip { cid } => ItemListLocation::Equip { cid },
+            ItemListLocationExceptContainer::Shop { cid } => ItemListLocation::Shop { cid },
+            ItemListLocationExceptContainer::Stash { sid } => ItemListLocation::Stash { sid },
+        }
+    }
 }
 
 pub type ItemLocation = (ItemListLocation, u32);
 
 /// Item list that records all items owned by one character or one tile
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
 pub struct ItemList {
     pub items: Vec<(Item, u32)>,
 }
@@ -348,30 +450,40 @@ impl ItemList {
         self.items.is_empty()
     }
 
-    /// Append item
-    pub fn append(&mut self, item: Item, n: u32) {
+    /// Append item, and returns the index of the appended item
+    pub fn append(&mut self, item: Item, n: u32) -> usize {
+        let stackable = item.is_stackable();
+        if !stackable && n > 1 {
+            // Only the returned one keeps the contents
+            for _ in 1..n {
+                self.append(item.clone_for_split(), 1);
+            }
+            return self.append(item, 1);
+        }
+
         if self.items.is_empty() {
             self.items.push((item, n));
-            return;
+            return 0;
         }
 
         for i in 0..self.items.len() {
             match item.cmp(&self.items[i].0) {
-                Ordering::Equal => {
+                Ordering::Equal if stackable => {
                     // If this list has the same item, increases the number
                     self.items[i].1 += n;
-                    return;
+                    return i;
                 }
                 Ordering::Less => {
                     self.items.insert(i, (item, n));
-                    return;
+                    return i;
                 }
-                Ordering::Greater => {
+                _ => {
                     continue;
                 }
             }
         }
         self.items.push((item, n));
+        self.items.len() - 1
     }
 
     /// Remove an item from list
@@ -399,7 +511,7 @@ impl ItemList {
         if self.items[i].1 == 0 {
             self.items.remove(i).0
         } else {
-            self.items[i].0.clone()
+            self.items[i].0.clone_for_split()
         }
     }
 
@@ -413,7 +525,7 @@ impl ItemList {
         let item = if self.items[i].1 == 0 {
             self.items.remove(i).0
         } else {
-            self.items[i].0.clone()
+            self.items[i].0.clone_for_split()
         };
 
         dest.append(item, n);
@@ -551,10 +663,10 @@ impl ItemKind {
 
 impl WeaponKind {
     pub fn equip_slot_kind(self) -> EquipSlotKind {
-        use self::WeaponKind::*;
-        match self {
-            Axe | Spear | Sword => EquipSlotKind::MeleeWeapon,
-            _ => EquipSlotKind::RangedWeapon,
+        if self.is_melee() && self != WeaponKind::Whip {
+            EquipSlotKind::MeleeWeapon
+        } else {
+            EquipSlotKind::RangedWeapon
         }
     }
 }
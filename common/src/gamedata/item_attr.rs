@@ -18,6 +18,13 @@ pub enum ItemObjAttr {
         #[serde(default)]
         required_fertility: u8,
     },
+    /// Container data
+    Container {
+        /// The maximum weight of contents (gram)
+        capacity: u32,
+        /// Percentage of the contents weight added to the container weight
+        weight_rate: u32,
+    },
 }
 
 /// Items can have zero or more attributes.
@@ -36,4 +43,6 @@ pub enum ItemAttr {
     SkillLearning(super::skill::SkillKind),
     /// Title for readable item.
     Title(String),
+    /// Contents of container item.
+    Container(ItemList),
 }
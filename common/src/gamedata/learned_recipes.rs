@@ -1,25 +1,60 @@
 use crate::gamedata::CreationKind;
+use fnv::FnvHashMap;
+use serde::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use std::fmt;
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct LearnedRecipes(Vec<Vec<String>>);
-
-impl Default for LearnedRecipes {
-    fn default() -> Self {
-        LearnedRecipes(vec![vec![]; CreationKind::Smith as usize + 1])
-    }
-}
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(transparent)]
+pub struct LearnedRecipes(FnvHashMap<CreationKind, Vec<String>>);
 
 impl LearnedRecipes {
     pub fn learned(&self, kind: CreationKind, recipe_name: &str) -> bool {
-        let recipes = &self.0[kind as usize];
-        recipes.iter().any(|r| r == recipe_name)
+        self.0
+            .get(&kind)
+            .is_some_and(|recipes| recipes.iter().any(|r| r == recipe_name))
     }
 
     pub fn add(&mut self, kind: CreationKind, recipe_name: &str) {
-        let recipes = &mut self.0[kind as usize];
+        let recipes = self.0.entry(kind).or_default();
         if recipes.iter().any(|s| s == recipe_name) {
             return;
         }
         recipes.push(recipe_name.to_owned());
     }
 }
+
+impl<'de> Deserialize<'de> for LearnedRecipes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<LearnedRecipes, D::Error> {
+        deserializer.deserialize_any(LearnedRecipesVisitor)
+    }
+}
+
+struct LearnedRecipesVisitor;
+
+impl<'de> Visitor<'de> for LearnedRecipesVisitor {
+    type Value = LearnedRecipes;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("map of learned recipes")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<LearnedRecipes, A::Error> {
+        let mut learned_recipes = FnvHashMap::default();
+        while let Some((kind, recipes)) = map.next_entry()? {
+            learned_recipes.insert(kind, recipes);
+        }
+        Ok(LearnedRecipes(learned_recipes))
+    }
+
+    /// Old savefiles have a list of recipes in the order of built-in creation kinds
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<LearnedRecipes, A::Error> {
+        let mut learned_recipes = FnvHashMap::default();
+        let mut kinds = CreationKind::all().into_iter();
+        while let Some(recipes) = seq.next_element()? {
+            if let Some(kind) = kinds.next() {
+                learned_recipes.insert(kind, recipes);
+            }
+        }
+        Ok(LearnedRecipes(learned_recipes))
+    }
+}
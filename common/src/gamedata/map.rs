@@ -32,6 +32,9 @@ pub struct Map {
     pub outside_tile: Option<OutsideTileInfo>,
     pub boundary: MapBoundary,
     pub music: String,
+    /// Positions of walls and decos built by the player
+    #[serde(default)]
+    pub player_built: Vec<Vec2d>,
 }
 
 /// Represents tile image layers
@@ -238,6 +241,7 @@ impl Map {
             outside_tile: None,
             boundary: MapBoundary::default(),
             music: String::default(),
+            player_built: Vec::new(),
         }
     }
 
@@ -7,6 +7,7 @@ pub mod item_attr;
 pub mod learned_recipes;
 pub mod map;
 pub mod meta;
+pub mod party;
 pub mod player;
 pub mod quest;
 pub mod region;
@@ -31,6 +32,7 @@ pub use self::item_attr::*;
 pub use self::learned_recipes::*;
 pub use self::map::*;
 pub use self::meta::*;
+pub use self::party::*;
 pub use self::player::*;
 pub use self::quest::*;
 pub use self::region::*;
@@ -61,6 +63,9 @@ pub struct GameData {
     pub script_exec: ScriptExec,
     pub settings: Settings,
     current_mapid: MapId,
+    // New fields are appended with defaults, because packed savefiles identify fields by order
+    #[serde(default)]
+    pub party: Party,
 }
 
 impl Default for GameData {
@@ -84,6 +89,7 @@ impl GameData {
             settings: Settings::new(),
             learned_recipes: LearnedRecipes::default(),
             current_mapid: MapId::default(),
+            party: Party::default(),
         }
     }
 
@@ -153,6 +159,7 @@ impl GameData {
 
     /// Remove specified character from the current map.
     /// If the chara is not OnMap, removes from the current map, but remains the data.
+    /// Party members are removed from the party and the game.
     pub fn remove_chara_from_map(&mut self, cid: CharaId) {
         match cid {
             CharaId::Player => {
@@ -162,6 +169,9 @@ impl GameData {
                 self.get_current_map_mut().remove_chara(cid);
                 self.chara.remove_chara(cid);
             }
+            CharaId::Party { .. } => {
+                self.remove_party_member(cid);
+            }
             _ => {
                 self.get_current_map_mut().remove_chara(cid);
             }
@@ -179,6 +189,10 @@ impl GameData {
                 let map = self.region.get_map_mut(mid);
                 map.remove_chara(cid);
             }
+            CharaId::Party { .. } => {
+                self.remove_party_member(cid);
+                return;
+            }
             _ => {
                 self.get_current_map_mut().remove_chara(cid);
             }
@@ -233,28 +247,65 @@ impl GameData {
 
     /// Get item list by ItemListLocation
     pub fn get_item_list(&self, list_location: ItemListLocation) -> &ItemList {
+        static EMPTY_ITEM_LIST: ItemList = ItemList { items: Vec::new() };
         match list_location {
             ItemListLocation::Chara { cid } => &self.chara.get(cid).item_list,
             ItemListLocation::Equip { cid } => self.chara.get(cid).equip.list(),
             ItemListLocation::OnMap { mid, pos } => &self.region.get_map(mid).tile[pos].item_list,
             ItemListLocation::Shop { cid } => &self.get_shop(cid).items,
+            ItemListLocation::Container { ill, i } => self
+                .get_item_list(ill.into())
+                .items
+                .get(i as usize)
+                .and_then(|(item, _)| item.container())
+                .unwrap_or(&EMPTY_ITEM_LIST),
+            ItemListLocation::Stash { sid } => self
+                .region
+                .get_site(sid)
+                .stash()
+                .unwrap_or(&EMPTY_ITEM_LIST),
         }
     }
 
     /// Mutable version for get_item_list
     pub fn get_item_list_mut(&mut self, list_location: ItemListLocation) -> &mut ItemList {
+        self.get_item_list_mut_checked(list_location)
+            .unwrap_or_else(|| panic!("item list {:?} does not exist", list_location))
+    }
+
+    /// Mutable version for get_item_list.
+    /// Returns None if the container item or the stash does not exist.
+    pub fn get_item_list_mut_checked(
+        &mut self,
+        list_location: ItemListLocation,
+    ) -> Option<&mut ItemList> {
         match list_location {
-            ItemListLocation::Chara { cid } => &mut self.chara.get_mut(cid).item_list,
+            ItemListLocation::Chara { cid } => Some(&mut self.chara.get_mut(cid).item_list),
             ItemListLocation::Equip { .. } => {
                 panic!("Mutable borrow is prohibited for equipment list");
             }
             ItemListLocation::OnMap { mid, pos } => {
-                &mut self.region.get_map_mut(mid).tile[pos].item_list
+                Some(&mut self.region.get_map_mut(mid).tile[pos].item_list)
             }
-            ItemListLocation::Shop { cid } => &mut self.get_shop_mut(cid).items,
+            ItemListLocation::Shop { cid } => Some(&mut self.get_shop_mut(cid).items),
+            ItemListLocation::Container { ill, i } => self
+                .get_item_list_mut_checked(ill.into())?
+                .items
+                .get_mut(i as usize)?
+                .0
+                .container_mut(),
+            ItemListLocation::Stash { sid } => self.region.get_site_mut(sid).stash_mut(),
         }
     }
 
+    /// Returns true if a container item is at the given location
+    pub fn container_exists(&self, container: ItemLocation) -> bool {
+        self.get_item_list(container.0)
+            .items
+            .get(container.1 as usize)
+            .is_some_and(|(item, _)| item.kind == ItemKind::Container)
+    }
+
     /// Get item list on the current map
     pub fn get_item_list_on_current_map(&self, pos: Vec2d) -> &ItemList {
         let mid = self.get_current_mapid();
@@ -307,6 +358,33 @@ impl GameData {
         dest_list.append(item, n);
     }
 
+    /// Move item into the container item at container.
+    /// Returns the location of the container after moving, because removing the item may shift it.
+    pub fn move_item_to_container<T: Into<ItemMoveNum>>(
+        &mut self,
+        item_location: ItemLocation,
+        container: ItemLocation,
+        n: T,
+    ) -> ItemLocation {
+        let src_n = self
+            .get_item_list(item_location.0)
+            .get_number(item_location.1);
+        let n = match n.into() {
+            ItemMoveNum::Partial(n) => n,
+            ItemMoveNum::All => src_n,
+        };
+        // The source row is removed before the destination is looked up in move_item
+        let container =
+            if n == src_n && item_location.0 == container.0 && item_location.1 < container.1 {
+                (container.0, container.1 - 1)
+            } else {
+                container
+            };
+        let dest = ItemListLocation::container(container).expect("nested container");
+        self.move_item(item_location, dest, n);
+        container
+    }
+
     /// Add item on specified tile of the current map
     pub fn add_item_on_tile(&mut self, pos: Vec2d, item: Item, n: u32) {
         let map = self.get_current_map_mut();
@@ -355,3 +433,83 @@ impl GameData {
 fn unknown_id_err<T: std::fmt::Debug>(id: T) -> ! {
     panic!("Internal error: Unknown id - {:?}", id)
 }
+
+#[test]
+fn move_item_to_container_test() {
+    use crate::objholder::ItemIdx;
+
+    let new_item = |kind| Item {
+        idx: ItemIdx::default(),
+        kind,
+        flags: ItemFlags::empty(),
+        quality: ItemQuality::default(),
+        attrs: Vec::new(),
+        time: None,
+    };
+    let mut gd = GameData::empty();
+    gd.add_chara(Chara::default(), CharaKind::Player);
+    let ill = ItemListLocation::PLAYER;
+    let list = gd.get_item_list_mut(ill);
+    list.append(new_item(ItemKind::Container), 1);
+    list.append(new_item(ItemKind::Potion), 2);
+    assert_eq!(list.items[0].0.kind, ItemKind::Potion);
+
+    // Item index < container index
+    let container = gd.move_item_to_container((ill, 0), (ill, 1), ItemMoveNum::All);
+    assert_eq!(container, (ill, 0));
+    assert_eq!(gd.get_item_list(ill).len(), 1);
+    let contents = gd.get_item(container).0.container().unwrap();
+    assert_eq!(contents.items, vec![(new_item(ItemKind::Potion), 2)]);
+}
+
+#[test]
+fn container_on_map_test() {
+    use crate::objholder::ItemIdx;
+
+    let new_item = |kind| Item {
+        idx: ItemIdx::default(),
+        kind,
+        flags: ItemFlags::empty(),
+        quality: ItemQuality::default(),
+        attrs: Vec::new(),
+        time: None,
+    };
+    let mut gd = GameData::empty();
+    let rid = gd
+        .region
+        .add_region(Region::new("region", Map::new(4, 4), 0));
+    let mid = MapId::from(rid);
+    gd.set_initial_mapid(mid);
+    gd.add_chara(Chara::default(), CharaKind::Player);
+
+    let ill = ItemListLocation::OnMap {
+        mid,
+        pos: Vec2d(1, 1),
+    };
+    let list = gd.get_item_list_mut(ill);
+    list.append(new_item(ItemKind::Container), 1);
+    list.append(new_item(ItemKind::Potion), 2);
+
+    // Put in
+    let container = gd.move_item_to_container((ill, 0), (ill, 1), ItemMoveNum::All);
+    assert_eq!(container, (ill, 0));
+    assert!(gd.container_exists(container));
+    let contents = ItemListLocation::container(container).unwrap();
+    assert_eq!(gd.get_item_list(contents).len(), 1);
+
+    // Take out
+    gd.move_item((contents, 0), ItemListLocation::PLAYER, ItemMoveNum::All);
+    assert!(gd.get_item_list(contents).is_empty());
+    assert_eq!(gd.get_item_list(ItemListLocation::PLAYER).get_number(0), 2);
+
+    // Stale locations of the container
+    gd.remove_item(container, ItemMoveNum::All);
+    assert!(!gd.container_exists(container));
+    assert!(gd.get_item_list(contents).is_empty());
+    assert!(gd.get_item_list_mut_checked(contents).is_none());
+
+    let list = gd.get_item_list_mut(ill);
+    list.append(new_item(ItemKind::Potion), 1);
+    assert!(!gd.container_exists(container));
+    assert!(gd.get_item_list_mut_checked(contents).is_none());
+}
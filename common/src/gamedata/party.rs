@@ -0,0 +1,197 @@
+use crate::gamedata::{AiState, CharaAi, CharaId, FactionId, GameData, MapId, SiteId};
+use geom::Vec2d;
+
+/// Orders from the player to a party member
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum PartyOrder {
+    /// Follow the player, and move to other maps with the player
+    Follow,
+    /// Stay at the current position. Remains on the map when the player leaves.
+    Wait,
+    /// Attack the player's target, and follow the player if there is no target
+    AttackTarget,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PartyMember {
+    pub cid: CharaId,
+    pub order: PartyOrder,
+    /// Faction before joining, restored when leaving the party
+    pub faction: FactionId,
+}
+
+/// Characters who join the player's party
+#[derive(Clone, Default, Debug, Serialize, Deserialize)]
+pub struct Party {
+    members: Vec<PartyMember>,
+}
+
+impl Party {
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, PartyMember> {
+        self.members.iter()
+    }
+
+    pub fn contains(&self, cid: CharaId) -> bool {
+        self.get(cid).is_some()
+    }
+
+    pub fn get(&self, cid: CharaId) -> Option<&PartyMember> {
+        self.members.iter().find(|m| m.cid == cid)
+    }
+
+    pub fn order(&self, cid: CharaId) -> Option<PartyOrder> {
+        self.get(cid).map(|m| m.order)
+    }
+
+    pub fn set_order(&mut self, cid: CharaId, order: PartyOrder) {
+        if let Some(member) = self.members.iter_mut().find(|m| m.cid == cid) {
+            member.order = order;
+        }
+    }
+
+    fn remove(&mut self, cid: CharaId) -> Option<PartyMember> {
+        let i = self.members.iter().position(|m| m.cid == cid)?;
+        Some(self.members.remove(i))
+    }
+
+    fn empty_n(&self) -> u32 {
+        (0..)
+            .find(|&i| {
+                !self
+                    .members
+                    .iter()
+                    .any(|m| matches!(m.cid, CharaId::Party { n } if n == i))
+            })
+            .unwrap()
+    }
+}
+
+impl GameData {
+    /// Make a character on the current map a party member.
+    /// The character gets a new id, and it is returned.
+    pub fn join_party(&mut self, cid: CharaId) -> Option<CharaId> {
+        if matches!(cid, CharaId::Player | CharaId::Party { .. }) {
+            return None;
+        }
+        let pos = self.get_current_map().chara_pos(cid)?;
+        let mut chara = self.chara.take(cid)?;
+        self.get_current_map_mut().remove_chara(cid);
+
+        let new_cid = CharaId::Party {
+            n: self.party.empty_n(),
+        };
+        self.party.members.push(PartyMember {
+            cid: new_cid,
+            order: PartyOrder::Follow,
+            faction: chara.faction,
+        });
+        chara.faction = FactionId::player();
+        chara.ai.state = AiState::Normal;
+        // Party members gain skill exp as the player
+        if chara.skills.exp.is_none() {
+            chara.skills.exp = Some(Default::default());
+        }
+        self.chara.add(new_cid, chara);
+        self.get_current_map_mut().locate_chara(new_cid, pos);
+        Some(new_cid)
+    }
+
+    /// Make a party member on the current map leave the party.
+    /// The character stays on the current map with a new id, and it is returned.
+    pub fn leave_party(&mut self, cid: CharaId) -> Option<CharaId> {
+        let pos = self.get_current_map().chara_pos(cid)?;
+        let member = self.party.remove(cid)?;
+        let mut chara = self.chara.take(cid).unwrap();
+        self.get_current_map_mut().remove_chara(cid);
+
+        chara.faction = member.faction;
+        chara.ai = CharaAi {
+            kind: chara.ai.kind,
+            initial_pos: pos,
+            ..CharaAi::default()
+        };
+        let mid = self.get_current_mapid();
+        let new_cid = self.add_chara_to_map(chara, mid);
+        self.get_current_map_mut().locate_chara(new_cid, pos);
+        Some(new_cid)
+    }
+
+    /// Party members on any floor of the given site, and the maps where they are.
+    /// All maps of the site must be loaded.
+    pub fn party_members_on_site(&self, sid: SiteId) -> Vec<(CharaId, MapId)> {
+        let n_floor = self.region.get_site(sid).floor_num();
+        self.party
+            .iter()
+            .filter_map(|m| {
+                (0..n_floor)
+                    .map(|floor| MapId::SiteMap { sid, floor })
+                    .find(|&mid| self.region.get_map(mid).chara_pos(m.cid).is_some())
+                    .map(|mid| (m.cid, mid))
+            })
+            .collect()
+    }
+
+    /// Move a party member on the given map to the position on the current map
+    pub fn move_party_member(&mut self, cid: CharaId, from: MapId, pos: Vec2d) {
+        debug_assert!(self.party.contains(cid));
+        let map = self.region.get_map_mut(from);
+        if map.chara_pos(cid).is_some() {
+            map.remove_chara(cid);
+        }
+        self.get_current_map_mut().locate_chara(cid, pos);
+    }
+
+    /// Remove a party member from the party and the game
+    pub(crate) fn remove_party_member(&mut self, cid: CharaId) {
+        let map = self.get_current_map_mut();
+        if map.chara_pos(cid).is_some() {
+            map.remove_chara(cid);
+        }
+        self.chara.remove_chara(cid);
+        self.party.remove(cid);
+    }
+}
+
+#[test]
+fn party_members_on_removed_site_test() {
+    use crate::gamedata::*;
+
+    let mut gd = GameData::empty();
+    let rid = gd
+        .region
+        .add_region(Region::new("region", Map::new(4, 4), 0));
+    let region_mid = MapId::from(rid);
+    gd.set_initial_mapid(region_mid);
+    gd.add_chara(Chara::default(), CharaKind::Player);
+
+    let sid = gd
+        .add_site(Site::new(2, None), SiteKind::Temp, rid, None)
+        .unwrap();
+    let floor0 = gd.add_map(Map::new(4, 4), sid, 1);
+    let floor1 = gd.add_map(Map::new(4, 4), sid, 2);
+
+    // A party member waits on the first floor
+    gd.set_current_mapid(floor0);
+    let cid = gd.add_chara_to_map(Chara::default(), floor0);
+    gd.get_current_map_mut().locate_chara(cid, Vec2d(1, 1));
+    let cid = gd.join_party(cid).unwrap();
+    gd.party.set_order(cid, PartyOrder::Wait);
+
+    // The player leaves the site from the second floor
+    gd.set_current_mapid(floor1);
+    gd.set_current_mapid(region_mid);
+    assert_eq!(gd.party_members_on_site(sid), vec![(cid, floor0)]);
+
+    gd.move_party_member(cid, floor0, Vec2d(2, 2));
+    gd.region.remove_site(sid);
+    assert_eq!(gd.get_current_map().chara_pos(cid), Some(Vec2d(2, 2)));
+    assert_eq!(gd.party.len(), 1);
+}
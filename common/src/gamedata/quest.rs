@@ -1,5 +1,8 @@
+use super::chara::CharaId;
 use super::defs::Reward;
-use crate::objholder::CharaTemplateIdx;
+use super::site::SiteId;
+use super::time::Time;
+use crate::objholder::{CharaTemplateIdx, ItemIdx};
 use std::slice::{Iter, IterMut};
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
@@ -7,6 +10,8 @@ pub enum QuestState {
     Active,
     Completed,
     RewardReceived,
+    /// Failed by the deadline or abandoned
+    Failed,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -33,6 +38,18 @@ impl QuestHolder {
         self.quests.push((QuestState::Active, quest));
     }
 
+    pub fn get(&self, i: usize) -> Option<&(QuestState, Quest)> {
+        self.quests.get(i)
+    }
+
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut (QuestState, Quest)> {
+        self.quests.get_mut(i)
+    }
+
+    pub fn remove(&mut self, i: usize) -> (QuestState, Quest) {
+        self.quests.remove(i)
+    }
+
     pub fn remove_reward_received(&mut self) {
         self.quests
             .retain(|&(state, _)| state != QuestState::RewardReceived);
@@ -40,7 +57,33 @@ impl QuestHolder {
 }
 
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
-pub enum Quest {
+#[serde(from = "QuestRepr")]
+pub struct Quest {
+    /// Id of the quest definition in rules. Empty if started by scripts.
+    pub id: String,
+    pub reward: Reward,
+    pub objective: QuestObjective,
+    /// The quest fails if it is not completed until this time
+    #[serde(default)]
+    pub deadline: Option<Time>,
+}
+
+/// Quest was an enum that has the reward in each variant in old savefiles
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum QuestRepr {
+    Current {
+        id: String,
+        reward: Reward,
+        objective: QuestObjective,
+        #[serde(default)]
+        deadline: Option<Time>,
+    },
+    Legacy(LegacyQuest),
+}
+
+#[derive(Deserialize)]
+enum LegacyQuest {
     SlayMonsters {
         reward: Reward,
         idx: CharaTemplateIdx,
@@ -49,10 +92,131 @@ pub enum Quest {
     },
 }
 
-impl Quest {
-    pub fn reward(&self) -> &Reward {
+impl From<QuestRepr> for Quest {
+    fn from(repr: QuestRepr) -> Quest {
+        match repr {
+            QuestRepr::Current {
+                id,
+                reward,
+                objective,
+                deadline,
+            } => Quest {
+                id,
+                reward,
+                objective,
+                deadline,
+            },
+            QuestRepr::Legacy(LegacyQuest::SlayMonsters {
+                reward,
+                idx,
+                goal,
+                killed,
+            }) => Quest {
+                id: String::new(),
+                reward,
+                objective: QuestObjective::SlayMonsters { idx, goal, killed },
+                deadline: None,
+            },
+        }
+    }
+}
+
+/// The objective that the player needs to achieve to complete a quest.
+/// Names in objectives are texts for displaying.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum QuestObjective {
+    SlayMonsters {
+        idx: CharaTemplateIdx,
+        goal: u32,
+        killed: u32,
+    },
+    /// Bring items to a quest board
+    FetchItems { idx: ItemIdx, n: u32 },
+    /// Deliver an item given at the start to a character
+    Deliver {
+        idx: ItemIdx,
+        cid: CharaId,
+        name: String,
+        town: String,
+    },
+    /// Escort a traveller to a town. The traveller joins the party when the quest is undertaken.
+    Escort {
+        name: String,
+        dest: SiteId,
+        dest_name: String,
+        idx: CharaTemplateIdx,
+        cid: Option<CharaId>,
+    },
+    /// Reach the given floor of a dungeon
+    ExploreDungeon {
+        sid: SiteId,
+        name: String,
+        floor: u32,
+    },
+    /// Create items by creation
+    Craft {
+        idx: ItemIdx,
+        goal: u32,
+        crafted: u32,
+    },
+}
+
+impl QuestObjective {
+    /// Id used for text and quest kind
+    pub fn kind_id(&self) -> &'static str {
         match self {
-            Quest::SlayMonsters { reward, .. } => reward,
+            QuestObjective::SlayMonsters { .. } => "slay_monsters",
+            QuestObjective::FetchItems { .. } => "fetch_items",
+            QuestObjective::Deliver { .. } => "deliver",
+            QuestObjective::Escort { .. } => "escort",
+            QuestObjective::ExploreDungeon { .. } => "explore_dungeon",
+            QuestObjective::Craft { .. } => "craft",
         }
     }
 }
+
+#[cfg(feature = "global_state_obj")]
+#[test]
+fn legacy_quest_test() {
+    use crate::objholder::ObjectIndex;
+    let _lock = crate::idx_conv::IDX_CONV_TABLE_TEST_LOCK.lock().unwrap();
+
+    // Quest was an enum before objectives were separated
+    #[derive(Serialize)]
+    enum OldQuest {
+        SlayMonsters {
+            reward: Reward,
+            idx: CharaTemplateIdx,
+            goal: u32,
+            killed: u32,
+        },
+    }
+
+    let reward = Reward {
+        money: 100,
+        item: Vec::new(),
+    };
+    let idx = CharaTemplateIdx::from_raw_int(1).unwrap();
+    let old = OldQuest::SlayMonsters {
+        reward: reward.clone(),
+        idx,
+        goal: 3,
+        killed: 1,
+    };
+    let quest = Quest {
+        id: String::new(),
+        reward,
+        objective: QuestObjective::SlayMonsters {
+            idx,
+            goal: 3,
+            killed: 1,
+        },
+        deadline: None,
+    };
+
+    let v = serde_cbor::ser::to_vec_packed(&old).unwrap();
+    assert_eq!(serde_cbor::from_slice::<Quest>(&v).unwrap(), quest);
+
+    let v = serde_cbor::ser::to_vec_packed(&quest).unwrap();
+    assert_eq!(serde_cbor::from_slice::<Quest>(&v).unwrap(), quest);
+}
@@ -194,6 +194,11 @@ impl RegionHolder {
         unreachable!()
     }
 
+    /// Iterate over sites on all regions
+    pub fn iter_sites(&self) -> impl Iterator<Item = (SiteId, &Site)> {
+        self.0.values().flat_map(|region| region.iter_sites())
+    }
+
     pub fn visit_all_maps<F: FnMut(MapId, &BoxedMap)>(&self, mut f: F) {
         for (&rid, region) in &self.0 {
             let mid = MapId::RegionMap { rid };
@@ -305,6 +310,11 @@ impl Region {
         self.sites.keys().filter(|&sid| sid.kind == kind).count() as u32
     }
 
+    /// Iterate over sites on the region
+    pub fn iter_sites(&self) -> impl Iterator<Item = (SiteId, &Site)> {
+        self.sites.iter().map(|(sid, info)| (*sid, &info.site))
+    }
+
     /// Get site by position on the region
     pub fn get_id_by_pos(&self, pos: Vec2d) -> Option<SiteId> {
         for (sid, sinfo) in self.sites.iter() {
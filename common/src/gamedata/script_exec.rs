@@ -1,4 +1,6 @@
-use crate::gamedata::{CharaId, Value};
+use crate::gamedata::{CharaId, MapId, Quest, QuestState, SiteId, Value};
+use std::collections::VecDeque;
+use std::str::FromStr;
 
 /// Stores data for script execution
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -7,6 +9,12 @@ pub struct ScriptExec {
     pub target_chara: Option<CharaId>,
     pub yield_result: Option<Value>,
     pub talking: bool,
+    /// The event that started the current script
+    #[serde(default)]
+    pub event: Option<ScriptEvent>,
+    /// Hooked scripts waiting for execution with their events
+    #[serde(default)]
+    pub hook_queue: VecDeque<(String, ScriptEvent)>,
 }
 
 impl ScriptExec {
@@ -16,5 +24,92 @@ impl ScriptExec {
         self.target_chara = None;
         self.yield_result = None;
         self.talking = false;
+        self.event = None;
+    }
+}
+
+/// Kinds of events that scripts can hook
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScriptHookKind {
+    EnterMap,
+    EnterSite,
+    PlayerTurn,
+    CharaDead,
+    PickUpItem,
+    NewDay,
+    NewHour,
+    QuestStateChanged,
+}
+
+impl FromStr for ScriptHookKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "enter_map" => ScriptHookKind::EnterMap,
+            "enter_site" => ScriptHookKind::EnterSite,
+            "player_turn" => ScriptHookKind::PlayerTurn,
+            "chara_dead" => ScriptHookKind::CharaDead,
+            "pick_up_item" => ScriptHookKind::PickUpItem,
+            "new_day" => ScriptHookKind::NewDay,
+            "new_hour" => ScriptHookKind::NewHour,
+            "quest_state_changed" => ScriptHookKind::QuestStateChanged,
+            _ => return Err(format!("unknown script hook \"{}\"", s)),
+        })
+    }
+}
+
+/// Event details given to hooked scripts
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ScriptEvent {
+    EnterMap {
+        mid: MapId,
+    },
+    EnterSite {
+        sid: SiteId,
+    },
+    PlayerTurn,
+    CharaDead {
+        cid: CharaId,
+        /// Id of the chara template
+        template: String,
+    },
+    PickUpItem {
+        cid: CharaId,
+        /// Id of the item object
+        item: String,
+        n: u32,
+    },
+    NewDay {
+        year: u32,
+        month: u16,
+        day: u16,
+    },
+    NewHour {
+        year: u32,
+        month: u16,
+        day: u16,
+        hour: u16,
+    },
+    QuestStateChanged {
+        quest: Quest,
+        state: QuestState,
+    },
+}
+
+impl ScriptEvent {
+    pub fn kind(&self) -> ScriptHookKind {
+        match self {
+            ScriptEvent::EnterMap { .. } => ScriptHookKind::EnterMap,
+            ScriptEvent::EnterSite { .. } => ScriptHookKind::EnterSite,
+            ScriptEvent::PlayerTurn => ScriptHookKind::PlayerTurn,
+            ScriptEvent::CharaDead { .. } => ScriptHookKind::CharaDead,
+            ScriptEvent::PickUpItem { .. } => ScriptHookKind::PickUpItem,
+            ScriptEvent::NewDay { .. } => ScriptHookKind::NewDay,
+            ScriptEvent::NewHour { .. } => ScriptHookKind::NewHour,
+            ScriptEvent::QuestStateChanged { .. } => ScriptHookKind::QuestStateChanged,
+        }
     }
 }
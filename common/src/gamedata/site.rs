@@ -1,3 +1,4 @@
+use super::item::ItemList;
 use super::map::{Map, MapId};
 use super::region::RegionId;
 use super::town::Town;
@@ -36,7 +37,12 @@ pub enum SiteContent {
         town: Box<Town>,
     },
     /// Player's base
-    Player { kind: PlayerBaseKind },
+    Player {
+        kind: PlayerBaseKind,
+        /// Items stored at this base
+        #[serde(default)]
+        stash: ItemList,
+    },
     /// Temporary map
     Temp {
         return_map: MapId,
@@ -103,6 +109,21 @@ impl Site {
         self.floor_num() > floor
     }
 
+    /// Get the stash if this site is the player's base
+    pub fn stash(&self) -> Option<&ItemList> {
+        match &self.content {
+            SiteContent::Player { stash, .. } => Some(stash),
+            _ => None,
+        }
+    }
+
+    pub fn stash_mut(&mut self) -> Option<&mut ItemList> {
+        match &mut self.content {
+            SiteContent::Player { stash, .. } => Some(stash),
+            _ => None,
+        }
+    }
+
     pub fn visit_maps<F: FnMut(u32, &BoxedMap)>(&self, mut f: F) {
         for (i, map) in self.map.iter().enumerate() {
             f(i as u32, map)
@@ -1,6 +1,9 @@
-#![allow(clippy::manual_non_exhaustive)]
-
+use super::chara::CharaAttrKind;
 use fnv::FnvHashMap;
+use once_cell::sync::{Lazy, OnceCell};
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+use std::fmt;
 use std::str::FromStr;
 use thiserror::Error;
 
@@ -33,223 +36,441 @@ impl SkillList {
     }
 }
 
-macro_rules! define_skill_kind {
-    {
-        basic_skills = $basic_skill_start_value:expr;
-        {
-            $($basic_skill:ident, $basic_skill_as_str:expr,)*
-        }
-        melee_weapon_kind = $melee_weapon_start_value:expr;
-        {
-            $($melee_weapon:ident, $melee_weapon_as_str:expr,)*
-        }
-        ranged_weapon_kind = $ranged_weapon_start_value:expr;
-        {
-            $($ranged_weapon:ident, $ranged_weapon_as_str:expr,)*
-        }
-        creation_kind = $creation_start_value:expr;
-        {
-            $($creation:ident, $creation_as_str:expr,)*
-        }
-    } => {
-        #[repr(u16)]
-        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
-        pub enum SkillKind {
-            #[doc(hidden)]
-            _DummyBasicSkill = $basic_skill_start_value,
-            $(
-                $basic_skill,
-            )*
-            #[doc(hidden)]
-            _DummyMeleeWeaponSkill = $melee_weapon_start_value,
-            $(
-                $melee_weapon,
-            )*
-            #[doc(hidden)]
-            _DummyRangedWeaponSkill = $ranged_weapon_start_value,
-            $(
-                $ranged_weapon,
-            )*
-            #[doc(hidden)]
-            _DummyCreationSkill = $creation_start_value,
-            $(
-                $creation,
-            )*
-        }
+/// Skill kind, represented as an index of the skill table.
+/// Built-in skills have fixed indices, and other skills defined by rules follow them.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct SkillKind(u16);
 
-        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
-        pub enum WeaponKind {
-            $(
-                $melee_weapon,
-            )*
-            #[doc(hidden)]
-            _DummyWeapon = 0x7F,
-            $(
-                $ranged_weapon,
-            )*
-        }
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum SkillCategory {
+    Basic,
+    MeleeWeapon,
+    RangedWeapon,
+    Creation,
+    Magic,
+}
+
+/// Skill definition
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SkillDef {
+    pub id: String,
+    pub category: SkillCategory,
+    /// The related attribute adds attr_bonus per its value to the skill level
+    #[serde(default)]
+    pub related_attr: Option<CharaAttrKind>,
+    #[serde(default)]
+    pub attr_bonus: f32,
+    /// Gained exp is multiplied by exp_factor / (1 + exp_level_factor * skill level)
+    #[serde(default = "default_exp_factor")]
+    pub exp_factor: f32,
+    #[serde(default)]
+    pub exp_level_factor: f32,
+    /// Text id of the skill name. Generated from id and category if empty
+    #[serde(default)]
+    pub text_id: String,
+}
 
-        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
-        pub enum CreationKind {
-            $(
-                $creation,
-            )*
+fn default_exp_factor() -> f32 {
+    1.0
+}
+
+impl SkillDef {
+    fn builtin(id: &str, category: SkillCategory) -> SkillDef {
+        SkillDef {
+            id: id.to_owned(),
+            category,
+            related_attr: None,
+            attr_bonus: 0.0,
+            exp_factor: default_exp_factor(),
+            exp_level_factor: 0.0,
+            text_id: String::new(),
         }
+    }
 
-        impl SkillKind {
-            pub fn weapon(self) -> Option<WeaponKind> {
-                match self {
-                    $(
-                        SkillKind::$melee_weapon => Some(WeaponKind::$melee_weapon),
-                    )*
-                    $(
-                        SkillKind::$ranged_weapon => Some(WeaponKind::$ranged_weapon),
-                    )*
-                    _ => None,
-                }
-            }
+    fn default_text_id(&self) -> String {
+        let prefix = match self.category {
+            SkillCategory::MeleeWeapon | SkillCategory::RangedWeapon => "weapon_kind",
+            SkillCategory::Creation => "creation_kind",
+            SkillCategory::Basic | SkillCategory::Magic => "skill_kind",
+        };
+        format!("{}-{}", prefix, self.id)
+    }
+}
 
-            pub fn creation(self) -> Option<CreationKind> {
-                match self {
-                    $(
-                        SkillKind::$creation => Some(CreationKind::$creation),
-                    )*
-                    _ => None,
-                }
-            }
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum SkillDefError {
+    #[error("skill \"{0}\" is a built-in skill of another category")]
+    CategoryMismatch(String),
+    #[error("too many skills are defined")]
+    TooManySkills,
+    #[error("skill table is already initialized")]
+    AlreadyInitialized,
+}
 
-            pub fn textid(self) -> &'static str {
-                if let Some(weapon_kind) = self.weapon() {
-                    return weapon_kind.textid();
-                }
-                if let Some(creation_kind) = self.creation() {
-                    return creation_kind.textid();
+struct SkillTable {
+    defs: Vec<SkillDef>,
+    id_map: FnvHashMap<String, SkillKind>,
+}
+
+impl SkillTable {
+    fn new(additional_defs: Vec<SkillDef>) -> Result<SkillTable, SkillDefError> {
+        let mut table = SkillTable {
+            defs: Vec::new(),
+            id_map: FnvHashMap::default(),
+        };
+
+        let builtin_defs = BUILTIN_SKILLS
+            .iter()
+            .map(|&(id, category)| SkillDef::builtin(id, category));
+
+        for mut def in builtin_defs.chain(additional_defs) {
+            if def.text_id.is_empty() {
+                def.text_id = def.default_text_id();
+            }
+            if let Some(&kind) = table.id_map.get(&def.id) {
+                // Replace the definition that has the same id
+                let old_def = &mut table.defs[kind.0 as usize];
+                if kind.is_builtin() && old_def.category != def.category {
+                    return Err(SkillDefError::CategoryMismatch(def.id));
                 }
-                match self {
-                    $(
-                        SkillKind::$basic_skill => concat!("skill_kind-", $basic_skill_as_str),
-                    )*
-                    _ => unreachable!(),
+                *old_def = def;
+            } else {
+                if table.defs.len() > u16::MAX as usize {
+                    return Err(SkillDefError::TooManySkills);
                 }
+                let kind = SkillKind(table.defs.len() as u16);
+                table.id_map.insert(def.id.clone(), kind);
+                table.defs.push(def);
             }
         }
 
-        impl FromStr for SkillKind {
-            type Err = KindParseError;
-            fn from_str(s: &str) -> Result<Self, Self::Err> {
-                Ok(match s {
-                    $(
-                        $basic_skill_as_str => SkillKind::$basic_skill,
-                    )*
-                    $(
-                        $melee_weapon_as_str => SkillKind::$melee_weapon,
-                    )*
-                    $(
-                        $ranged_weapon_as_str => SkillKind::$ranged_weapon,
-                    )*
-                    $(
-                        $creation_as_str => SkillKind::$creation,
-                    )*
-                    _ => {
-                        return Err(KindParseError(s.to_owned()));
-                    }
-                })
-            }
+        Ok(table)
+    }
+}
+
+static BUILTIN_SKILL_TABLE: Lazy<SkillTable> =
+    Lazy::new(|| SkillTable::new(Vec::new()).expect("invalid built-in skills"));
+static SKILL_TABLE: OnceCell<SkillTable> = OnceCell::new();
+
+/// Built-in skills are available before initialization
+fn skill_table() -> &'static SkillTable {
+    SKILL_TABLE.get().unwrap_or(&*BUILTIN_SKILL_TABLE)
+}
+
+/// Initialize the skill table with additional skill definitions.
+/// A definition that has the same id as an existing skill replaces it.
+pub fn set_skill_defs(defs: Vec<SkillDef>) -> Result<(), SkillDefError> {
+    let table = SkillTable::new(defs)?;
+    SKILL_TABLE
+        .set(table)
+        .map_err(|_| SkillDefError::AlreadyInitialized)
+}
+
+/// Write skill ids for the id table of savefiles
+pub(crate) fn write_skill_table<W: std::io::Write>(mut w: W) -> Result<(), std::io::Error> {
+    writeln!(w, "{}Skill", crate::basic::ID_TABLE_SECTION_TAG)?;
+    for def in &skill_table().defs {
+        writeln!(w, "{}", def.id)?;
+    }
+    Ok(())
+}
+
+pub(crate) fn hash_skill_table<H: std::hash::Hasher>(state: &mut H) {
+    use std::hash::Hash;
+    for def in &skill_table().defs {
+        def.id.hash(state);
+    }
+}
+
+impl SkillKind {
+    pub fn from_id(id: &str) -> Option<SkillKind> {
+        skill_table().id_map.get(id).copied()
+    }
+
+    /// Iterate over all defined skills
+    pub fn all() -> impl Iterator<Item = SkillKind> {
+        (0..skill_table().defs.len() as u16).map(SkillKind)
+    }
+
+    pub fn def(self) -> &'static SkillDef {
+        &skill_table().defs[self.0 as usize]
+    }
+
+    pub fn id(self) -> &'static str {
+        &self.def().id
+    }
+
+    pub fn category(self) -> SkillCategory {
+        self.def().category
+    }
+
+    pub fn is_builtin(self) -> bool {
+        (self.0 as usize) < BUILTIN_SKILLS.len()
+    }
+
+    pub fn weapon(self) -> Option<WeaponKind> {
+        match self.category() {
+            SkillCategory::MeleeWeapon | SkillCategory::RangedWeapon => Some(WeaponKind(self)),
+            _ => None,
         }
+    }
 
-        impl FromStr for WeaponKind {
-            type Err = KindParseError;
-            fn from_str(s: &str) -> Result<Self, Self::Err> {
-                Ok(match s {
-                    $(
-                        $melee_weapon_as_str => WeaponKind::$melee_weapon,
-                    )*
-                    $(
-                        $ranged_weapon_as_str => WeaponKind::$ranged_weapon,
-                    )*
-                    _ => {
-                        return Err(KindParseError(s.to_owned()));
-                    }
-                })
-            }
+    pub fn creation(self) -> Option<CreationKind> {
+        match self.category() {
+            SkillCategory::Creation => Some(CreationKind(self)),
+            _ => None,
         }
+    }
+
+    pub fn textid(self) -> &'static str {
+        &self.def().text_id
+    }
+
+    pub(crate) fn as_raw(self) -> u16 {
+        self.0
+    }
 
-        impl From<WeaponKind> for SkillKind {
-            fn from(weapon_kind: WeaponKind) -> Self {
-                match weapon_kind {
-                    $(
-                        WeaponKind::$melee_weapon => SkillKind::$melee_weapon,
-                    )*
-                    $(
-                        WeaponKind::$ranged_weapon => SkillKind::$ranged_weapon,
-                    )*
-                    _ => unreachable!(),
+    pub(crate) fn from_raw(i: u16) -> SkillKind {
+        SkillKind(i)
+    }
+}
+
+impl FromStr for SkillKind {
+    type Err = KindParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(kind) = SkillKind::from_id(s) {
+            return Ok(kind);
+        }
+        // Accept names of the old SkillKind enum variants, e.g. "BareHands"
+        let mut snake_case = String::new();
+        for (i, c) in s.char_indices() {
+            if c.is_ascii_uppercase() {
+                if i > 0 {
+                    snake_case.push('_');
                 }
+                snake_case.push(c.to_ascii_lowercase());
+            } else {
+                snake_case.push(c);
             }
         }
+        SkillKind::from_id(&snake_case).ok_or_else(|| KindParseError(s.to_owned()))
+    }
+}
 
-        impl From<CreationKind> for SkillKind {
-            fn from(creation_kind: CreationKind) -> Self {
-                match creation_kind {
-                    $(
-                        CreationKind::$creation => SkillKind::$creation,
-                    )*
-                }
-            }
+/// Skill ids are used for human readable formats such as rule files.
+/// Savefiles and pak files store indices, which are converted by the id table of savefiles.
+impl Serialize for SkillKind {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(self.id())
+        } else {
+            serializer.serialize_u16(self.0)
         }
+    }
+}
 
-        impl WeaponKind {
-            pub fn is_melee(self) -> bool {
-                self < WeaponKind::_DummyWeapon
-            }
+impl<'de> Deserialize<'de> for SkillKind {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<SkillKind, D::Error> {
+        deserialize_skill_kind(deserializer, LEGACY_SKILL_KIND_ORDER)
+    }
+}
 
-            pub fn is_ranged(self) -> bool {
-                !self.is_melee()
-            }
+fn deserialize_skill_kind<'de, D: Deserializer<'de>>(
+    deserializer: D,
+    legacy_order: &'static [Option<SkillKind>],
+) -> Result<SkillKind, D::Error> {
+    let visitor = SkillKindVisitor { legacy_order };
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_identifier(visitor)
+    } else {
+        deserializer.deserialize_any(visitor)
+    }
+}
 
-            pub fn textid(self) -> &'static str {
-                match self {
-                    $(
-                        WeaponKind::$melee_weapon => concat!("weapon_kind-", $melee_weapon_as_str),
-                    )*
-                    $(
-                        WeaponKind::$ranged_weapon => concat!("weapon_kind-", $ranged_weapon_as_str),
-                    )*
-                    WeaponKind::_DummyWeapon => unreachable!(),
-                }
-            }
+struct SkillKindVisitor {
+    /// Variant order of the old enum, used to convert indices in old savefiles
+    legacy_order: &'static [Option<SkillKind>],
+}
 
-            pub const ALL: &'static [WeaponKind] = &[
-                $(
-                    WeaponKind::$melee_weapon,
-                )*
-                $(
-                    WeaponKind::$ranged_weapon,
-                )*
-            ];
-        }
+impl<'de> Visitor<'de> for SkillKindVisitor {
+    type Value = SkillKind;
 
-        impl CreationKind {
-            pub fn textid(self) -> &'static str {
-                match self {
-                    $(
-                        CreationKind::$creation => concat!("creation_kind-", $creation_as_str),
-                    )*
-                }
-            }
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("skill id or index")
+    }
 
-            pub const ALL: &'static [CreationKind] = &[
-                $(
-                    CreationKind::$creation,
-                )*
-            ];
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<SkillKind, E> {
+        v.parse()
+            .map_err(|_| E::custom(format!("unknown skill \"{}\"", v)))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<SkillKind, E> {
+        let kind = if v <= u16::MAX as u64 {
+            crate::idx_conv::conv_skill_kind(v as u16, self.legacy_order).map_err(|id| {
+                E::custom(format!("skill \"{}\" in the savefile is not defined", id))
+            })?
+        } else {
+            return Err(E::custom(format!("invalid skill index {}", v)));
+        };
+        if (kind.0 as usize) < skill_table().defs.len() {
+            Ok(kind)
+        } else {
+            Err(E::custom(format!("invalid skill index {}", v)))
         }
     }
 }
 
-define_skill_kind! {
-    basic_skills = 0;
+/// Skill kind of melee or ranged weapon category
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize)]
+#[serde(transparent)]
+pub struct WeaponKind(SkillKind);
+
+/// Skill kind of creation category
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize)]
+#[serde(transparent)]
+pub struct CreationKind(SkillKind);
+
+impl<'de> Deserialize<'de> for WeaponKind {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<WeaponKind, D::Error> {
+        let kind = deserialize_skill_kind(deserializer, LEGACY_WEAPON_KIND_ORDER)?;
+        kind.weapon()
+            .ok_or_else(|| de::Error::custom(format!("skill \"{}\" is not a weapon", kind.id())))
+    }
+}
+
+impl<'de> Deserialize<'de> for CreationKind {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<CreationKind, D::Error> {
+        let kind = deserialize_skill_kind(deserializer, LEGACY_CREATION_KIND_ORDER)?;
+        kind.creation().ok_or_else(|| {
+            de::Error::custom(format!("skill \"{}\" is not a creation kind", kind.id()))
+        })
+    }
+}
+
+impl WeaponKind {
+    pub fn is_melee(self) -> bool {
+        self.0.category() == SkillCategory::MeleeWeapon
+    }
+
+    pub fn is_ranged(self) -> bool {
+        !self.is_melee()
+    }
+
+    pub fn textid(self) -> &'static str {
+        self.0.textid()
+    }
+
+    pub fn all() -> Vec<WeaponKind> {
+        SkillKind::all().filter_map(SkillKind::weapon).collect()
+    }
+}
+
+impl CreationKind {
+    pub fn textid(self) -> &'static str {
+        self.0.textid()
+    }
+
+    pub fn is_builtin(self) -> bool {
+        self.0.is_builtin()
+    }
+
+    pub fn all() -> Vec<CreationKind> {
+        SkillKind::all().filter_map(SkillKind::creation).collect()
+    }
+}
+
+impl FromStr for WeaponKind {
+    type Err = KindParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<SkillKind>()
+            .ok()
+            .and_then(SkillKind::weapon)
+            .ok_or_else(|| KindParseError(s.to_owned()))
+    }
+}
+
+impl FromStr for CreationKind {
+    type Err = KindParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<SkillKind>()
+            .ok()
+            .and_then(SkillKind::creation)
+            .ok_or_else(|| KindParseError(s.to_owned()))
+    }
+}
+
+impl From<WeaponKind> for SkillKind {
+    fn from(weapon_kind: WeaponKind) -> Self {
+        weapon_kind.0
+    }
+}
+
+impl From<CreationKind> for SkillKind {
+    fn from(creation_kind: CreationKind) -> Self {
+        creation_kind.0
+    }
+}
+
+macro_rules! define_builtin_skills {
     {
+        basic_skills {
+            $($basic_skill:ident, $basic_skill_as_str:expr,)*
+        }
+        melee_weapon_kind {
+            $($melee_weapon:ident, $melee_weapon_as_str:expr,)*
+        }
+        ranged_weapon_kind {
+            $($ranged_weapon:ident, $ranged_weapon_as_str:expr,)*
+        }
+        creation_kind {
+            $($creation:ident, $creation_as_str:expr,)*
+        }
+        added_basic_skills {
+            $($added_basic_skill:ident, $added_basic_skill_as_str:expr,)*
+        }
+    } => {
+        #[repr(u16)]
+        enum BuiltinSkill {
+            $($basic_skill,)*
+            $($melee_weapon,)*
+            $($ranged_weapon,)*
+            $($creation,)*
+            $($added_basic_skill,)*
+        }
+
+        const BUILTIN_SKILLS: &[(&str, SkillCategory)] = &[
+            $(($basic_skill_as_str, SkillCategory::Basic),)*
+            $(($melee_weapon_as_str, SkillCategory::MeleeWeapon),)*
+            $(($ranged_weapon_as_str, SkillCategory::RangedWeapon),)*
+            $(($creation_as_str, SkillCategory::Creation),)*
+            $(($added_basic_skill_as_str, SkillCategory::Basic),)*
+        ];
+
+        #[allow(non_upper_case_globals)]
+        impl SkillKind {
+            $(pub const $basic_skill: SkillKind = SkillKind(BuiltinSkill::$basic_skill as u16);)*
+            $(pub const $melee_weapon: SkillKind = SkillKind(BuiltinSkill::$melee_weapon as u16);)*
+            $(pub const $ranged_weapon: SkillKind = SkillKind(BuiltinSkill::$ranged_weapon as u16);)*
+            $(pub const $creation: SkillKind = SkillKind(BuiltinSkill::$creation as u16);)*
+            $(pub const $added_basic_skill: SkillKind =
+                SkillKind(BuiltinSkill::$added_basic_skill as u16);)*
+        }
+
+        #[allow(non_upper_case_globals)]
+        impl WeaponKind {
+            $(pub const $melee_weapon: WeaponKind = WeaponKind(SkillKind::$melee_weapon);)*
+            $(pub const $ranged_weapon: WeaponKind = WeaponKind(SkillKind::$ranged_weapon);)*
+        }
+
+        #[allow(non_upper_case_globals)]
+        impl CreationKind {
+            $(pub const $creation: CreationKind = CreationKind(SkillKind::$creation);)*
+        }
+    }
+}
+
+define_builtin_skills! {
+    basic_skills {
         BareHands, "bare_hands",
         Carrying, "carrying",
         Defence, "defence",
@@ -264,21 +485,18 @@ define_skill_kind! {
         Plants, "plants",
         Animal, "animal",
     }
-    melee_weapon_kind = 0x0400;
-    {
+    melee_weapon_kind {
         Sword, "sword",
         Spear, "spear",
         Axe, "axe",
         Whip, "whip",
     }
-    ranged_weapon_kind = 0x0800;
-    {
+    ranged_weapon_kind {
         Bow, "bow",
         Crossbow, "crossbow",
         Firearm, "firearm",
     }
-    creation_kind = 0x1000;
-    {
+    creation_kind {
         Art, "art",
         Construction, "construction",
         Cooking, "cooking",
@@ -286,4 +504,184 @@ define_skill_kind! {
         Pharmacy, "pharmacy",
         Smith, "smith",
     }
+    // Basic skills added later are appended to keep the indices of existing skills
+    added_basic_skills {
+        Negotiation, "negotiation",
+    }
+}
+
+/// Variant order of the old SkillKind enum, including hidden dummy variants
+const LEGACY_SKILL_KIND_ORDER: &[Option<SkillKind>] = &[
+    None,
+    Some(SkillKind::BareHands),
+    Some(SkillKind::Carrying),
+    Some(SkillKind::Defence),
+    Some(SkillKind::Endurance),
+    Some(SkillKind::Evasion),
+    Some(SkillKind::Healing),
+    Some(SkillKind::Conceal),
+    Some(SkillKind::Detection),
+    Some(SkillKind::Throwing),
+    Some(SkillKind::MagicDevice),
+    Some(SkillKind::Mining),
+    Some(SkillKind::Plants),
+    Some(SkillKind::Animal),
+    None,
+    Some(SkillKind::Sword),
+    Some(SkillKind::Spear),
+    Some(SkillKind::Axe),
+    Some(SkillKind::Whip),
+    None,
+    Some(SkillKind::Bow),
+    Some(SkillKind::Crossbow),
+    Some(SkillKind::Firearm),
+    None,
+    Some(SkillKind::Art),
+    Some(SkillKind::Construction),
+    Some(SkillKind::Cooking),
+    Some(SkillKind::Craft),
+    Some(SkillKind::Pharmacy),
+    Some(SkillKind::Smith),
+];
+
+/// Variant order of the old WeaponKind enum
+const LEGACY_WEAPON_KIND_ORDER: &[Option<SkillKind>] = &[
+    Some(SkillKind::Sword),
+    Some(SkillKind::Spear),
+    Some(SkillKind::Axe),
+    Some(SkillKind::Whip),
+    None,
+    Some(SkillKind::Bow),
+    Some(SkillKind::Crossbow),
+    Some(SkillKind::Firearm),
+];
+
+/// Variant order of the old CreationKind enum
+const LEGACY_CREATION_KIND_ORDER: &[Option<SkillKind>] = &[
+    Some(SkillKind::Art),
+    Some(SkillKind::Construction),
+    Some(SkillKind::Cooking),
+    Some(SkillKind::Craft),
+    Some(SkillKind::Pharmacy),
+    Some(SkillKind::Smith),
+];
+
+#[test]
+fn skill_kind_serde_test() {
+    let kinds = vec![SkillKind::BareHands, SkillKind::Sword, SkillKind::Smith];
+
+    let v = serde_cbor::to_vec(&kinds).unwrap();
+    let deserialized: Vec<SkillKind> = serde_cbor::from_slice(&v).unwrap();
+    assert_eq!(kinds, deserialized);
+
+    // Old enum variant names
+    let v = serde_cbor::to_vec(&["BareHands", "Sword", "Smith"]).unwrap();
+    let deserialized: Vec<SkillKind> = serde_cbor::from_slice(&v).unwrap();
+    assert_eq!(kinds, deserialized);
+
+    assert_eq!("magic_device".parse(), Ok(SkillKind::MagicDevice));
+    assert_eq!("sword".parse(), Ok(WeaponKind::Sword));
+    assert!("sword".parse::<CreationKind>().is_err());
+
+    legacy_skill_kind_test();
+}
+
+/// Called from skill_kind_serde_test because the id conversion table is global
+#[cfg(test)]
+fn legacy_skill_kind_test() {
+    // Old enums are stored as variant indices, including dummy variants
+    #[allow(dead_code)]
+    #[derive(Serialize)]
+    enum OldSkillKind {
+        DummyBasicSkill,
+        BareHands,
+        Carrying,
+        Defence,
+        Endurance,
+        Evasion,
+        Healing,
+        Conceal,
+        Detection,
+        Throwing,
+        MagicDevice,
+        Mining,
+        Plants,
+        Animal,
+        DummyMeleeWeaponSkill,
+        Sword,
+        Spear,
+        Axe,
+        Whip,
+        DummyRangedWeaponSkill,
+        Bow,
+        Crossbow,
+        Firearm,
+        DummyCreationSkill,
+        Art,
+        Construction,
+        Cooking,
+        Craft,
+        Pharmacy,
+        Smith,
+    }
+    #[allow(dead_code)]
+    #[derive(Serialize)]
+    enum OldWeaponKind {
+        Sword,
+        Spear,
+        Axe,
+        Whip,
+        DummyWeapon,
+        Bow,
+        Crossbow,
+        Firearm,
+    }
+    #[allow(dead_code)]
+    #[derive(Serialize)]
+    enum OldCreationKind {
+        Art,
+        Construction,
+        Cooking,
+        Craft,
+        Pharmacy,
+        Smith,
+    }
+
+    // Savefiles without the skill section in the id table
+    let _lock = crate::idx_conv::IDX_CONV_TABLE_TEST_LOCK.lock().unwrap();
+    crate::idx_conv::set_idx_conv_table(Some(crate::idx_conv::IdxConvTable::default()));
+
+    let v = serde_cbor::ser::to_vec_packed(&[
+        OldSkillKind::BareHands,
+        OldSkillKind::Animal,
+        OldSkillKind::Sword,
+        OldSkillKind::Firearm,
+        OldSkillKind::Smith,
+    ])
+    .unwrap();
+    let deserialized: Vec<SkillKind> = serde_cbor::from_slice(&v).unwrap();
+    assert_eq!(
+        deserialized,
+        vec![
+            SkillKind::BareHands,
+            SkillKind::Animal,
+            SkillKind::Sword,
+            SkillKind::Firearm,
+            SkillKind::Smith
+        ]
+    );
+
+    let v = serde_cbor::ser::to_vec_packed(&[OldWeaponKind::Sword, OldWeaponKind::Bow]).unwrap();
+    let deserialized: Vec<WeaponKind> = serde_cbor::from_slice(&v).unwrap();
+    assert_eq!(deserialized, vec![WeaponKind::Sword, WeaponKind::Bow]);
+
+    let v =
+        serde_cbor::ser::to_vec_packed(&[OldCreationKind::Art, OldCreationKind::Smith]).unwrap();
+    let deserialized: Vec<CreationKind> = serde_cbor::from_slice(&v).unwrap();
+    assert_eq!(deserialized, vec![CreationKind::Art, CreationKind::Smith]);
+
+    let v = serde_cbor::ser::to_vec_packed(&[OldSkillKind::DummyBasicSkill]).unwrap();
+    assert!(serde_cbor::from_slice::<Vec<SkillKind>>(&v).is_err());
+
+    crate::idx_conv::set_idx_conv_table(None);
 }
@@ -1,5 +1,6 @@
 use crate::gamedata::quest::Quest;
 use crate::gamedata::shop::*;
+use crate::gamedata::time::Time;
 use fnv::FnvHashMap;
 use std::collections::hash_map::{Values, ValuesMut};
 
@@ -8,6 +9,12 @@ pub struct Town {
     id: String,
     shops: FnvHashMap<u32, Shop>,
     pub quests: Vec<Quest>,
+    /// The time when quests were updated last
+    #[serde(default)]
+    pub quests_updated: Option<Time>,
+    /// The number of items recently sold by the player for each item id
+    #[serde(default)]
+    sold_items: FnvHashMap<String, u32>,
 }
 
 impl Town {
@@ -16,6 +23,8 @@ impl Town {
             id: id.to_owned(),
             shops: FnvHashMap::default(),
             quests: Vec::new(),
+            quests_updated: None,
+            sold_items: FnvHashMap::default(),
         }
     }
 
@@ -42,4 +51,20 @@ impl Town {
     pub fn add_shop(&mut self, shop: Shop, n: u32) {
         self.shops.insert(n, shop);
     }
+
+    pub fn sold_count(&self, id: &str) -> u32 {
+        self.sold_items.get(id).copied().unwrap_or(0)
+    }
+
+    pub fn add_sold_count(&mut self, id: &str, n: u32) {
+        *self.sold_items.entry(id.to_owned()).or_insert(0) += n;
+    }
+
+    /// Decrease the number of recently sold items
+    pub fn recover_sold_count(&mut self, n: u32) {
+        for count in self.sold_items.values_mut() {
+            *count = count.saturating_sub(n);
+        }
+        self.sold_items.retain(|_, count| *count > 0);
+    }
 }
@@ -1,10 +1,15 @@
 use crate::hashmap::HashMap;
+use std::collections::BTreeMap;
 
 /// Value is used to be stored in Variable.
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub enum Value {
     Bool(bool),
     Int(i64),
+    Str(String),
+    Float(f64),
+    List(Vec<Value>),
+    Dict(BTreeMap<String, Value>),
 }
 
 /// Stores variables which are referenced in scripts
@@ -55,3 +60,33 @@ impl Variables {
             .insert((script_id.to_string(), name.to_string()), v);
     }
 }
+
+#[test]
+fn variables_serialize_test() {
+    let list = Value::List(vec![
+        Value::Int(1),
+        Value::Str("a".into()),
+        Value::Bool(true),
+    ]);
+    let mut dict = BTreeMap::new();
+    dict.insert("x".to_owned(), Value::Float(-0.25));
+    dict.insert("y".to_owned(), list.clone());
+    let dict = Value::Dict(dict);
+
+    let mut vars = Variables::default();
+    vars.set_global_var("str", Value::Str("text".into()));
+    vars.set_global_var("float", Value::Float(1.5));
+    vars.set_global_var("list", list.clone());
+    vars.set_local_var("script", "dict", dict.clone());
+    vars.set_local_var("script", "bool", Value::Bool(false));
+
+    let mut buf = Vec::new();
+    crate::utils::to_writer_with_mode(&mut buf, &vars).unwrap();
+    let vars: Variables = serde_cbor::from_slice(&buf).unwrap();
+
+    assert_eq!(vars.global_var("str"), Some(&Value::Str("text".into())));
+    assert_eq!(vars.global_var("float"), Some(&Value::Float(1.5)));
+    assert_eq!(vars.global_var("list"), Some(&list));
+    assert_eq!(vars.local_var("script", "dict"), Some(&dict));
+    assert_eq!(vars.local_var("script", "bool"), Some(&Value::Bool(false)));
+}
@@ -70,6 +70,7 @@ pub fn get_by_id_checked<T: FromId>(id: &str) -> Option<&'static T> {
 mod serde_with_impl {
     use super::*;
     use serde_with::{DeserializeAs, SerializeAs};
+    use std::cell::RefCell;
 
     /// serialize/deserialize object index as id string.
     /// gobj::init() must be called before using this.
@@ -99,9 +100,26 @@ mod serde_with_impl {
         {
             use serde::Deserialize;
             let id = String::deserialize(deserializer)?;
-            Ok(id_to_idx(&id))
+            if let Some(idx) = T::search_idx(&id, &OBJ_HOLDER) {
+                Ok(idx)
+            } else {
+                let kind = std::any::type_name::<T>().rsplit("::").next().unwrap();
+                UNKNOWN_IDS.with(|ids| ids.borrow_mut().push((id, kind)));
+                Ok(T::default())
+            }
         }
     }
+
+    thread_local! {
+        pub(super) static UNKNOWN_IDS: RefCell<Vec<(String, &'static str)>> =
+            const { RefCell::new(Vec::new()) };
+    }
 }
 
 pub use serde_with_impl::ObjIdxAsId;
+
+/// Take ids and their index type names that are not found in deserialization by `ObjIdxAsId`.
+/// Unknown ids are deserialized as the default index.
+pub fn take_unknown_ids() -> Vec<(String, &'static str)> {
+    serde_with_impl::UNKNOWN_IDS.with(|ids| std::mem::take(&mut *ids.borrow_mut()))
+}
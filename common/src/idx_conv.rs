@@ -1,3 +1,4 @@
+use crate::gamedata::SkillKind;
 pub use crate::objholder::IdxConvTable;
 use once_cell::sync::Lazy;
 use std::sync::RwLock;
@@ -5,10 +6,28 @@ use std::sync::RwLock;
 pub(crate) static IDX_CONV_TABLE: Lazy<RwLock<Option<IdxConvTable>>> =
     Lazy::new(|| RwLock::new(None));
 
+/// Tests that depend on the global conversion table must hold this lock
+#[cfg(test)]
+pub(crate) static IDX_CONV_TABLE_TEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
 pub fn set_idx_conv_table(idx_conv_table: Option<IdxConvTable>) {
     *IDX_CONV_TABLE.write().expect("IDX_CONV_TABLE lock error") = idx_conv_table;
 }
 
+/// Convert skill kind from savefiles. Returns the skill id if it is not defined currently.
+/// legacy_order is the variant order of the old enum, used for savefiles without the skill table.
+pub(crate) fn conv_skill_kind(
+    i: u16,
+    legacy_order: &[Option<SkillKind>],
+) -> Result<SkillKind, String> {
+    let lock = IDX_CONV_TABLE.read().expect("IDX_CONV_TABLE lock error");
+    if let Some(idx_conv_table) = lock.as_ref() {
+        idx_conv_table.skill(i, legacy_order)
+    } else {
+        Ok(SkillKind::from_raw(i))
+    }
+}
+
 #[macro_export]
 macro_rules! idx_conv {
     ($({$a:ident, $obj:ty, $mem:ident, $idx:ident}),*) => {
@@ -18,6 +37,8 @@ macro_rules! idx_conv {
             $(
                 $mem: Vec<u32>,
             )*
+            skill: Vec<Result<crate::gamedata::SkillKind, String>>,
+            has_skill_table: bool,
         }
 
         impl IdxConvTable {
@@ -43,6 +64,9 @@ macro_rules! idx_conv {
                     if line.starts_with(ID_TABLE_SECTION_TAG) {
                         current_obj_type =
                             line.trim_start_matches(ID_TABLE_SECTION_TAG).trim_end().to_owned();
+                        if current_obj_type == "Skill" {
+                            table.has_skill_table = true;
+                        }
                     } else {
                         match current_obj_type.as_str() {
                             $(
@@ -54,6 +78,11 @@ macro_rules! idx_conv {
                                     }
                                 }
                             )*
+                            "Skill" => {
+                                table.skill.push(
+                                    crate::gamedata::SkillKind::from_id(&line).ok_or(line)
+                                );
+                            }
                             _ => (), // TODO: Should generate error
                         }
                     }
@@ -67,6 +96,23 @@ macro_rules! idx_conv {
                     $idx::from_usize(self.$mem[i.as_usize()] as usize)
                 }
             )*
+
+            /// Savefiles without the skill section store skills as variant indices of the old enum
+            pub fn skill(&self, i: u16, legacy_order: &[Option<crate::gamedata::SkillKind>])
+                         -> Result<crate::gamedata::SkillKind, String> {
+                if self.has_skill_table {
+                    self.skill
+                        .get(i as usize)
+                        .cloned()
+                        .unwrap_or_else(|| Err(format!("#{}", i)))
+                } else {
+                    legacy_order
+                        .get(i as usize)
+                        .copied()
+                        .flatten()
+                        .ok_or_else(|| format!("#{}", i))
+                }
+            }
         }
 
         $(
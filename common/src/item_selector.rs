@@ -25,6 +25,16 @@ impl ItemSelector {
         self
     }
 
+    /// Item ids given to this selector
+    pub fn ids(&self) -> &[String] {
+        &self.ids
+    }
+
+    /// Item groups given to this selector
+    pub fn groups(&self) -> &[String] {
+        &self.groups
+    }
+
     pub fn is(&self, obj: &ItemObject) -> bool {
         let id = &obj.id;
         let group = &obj.group;
@@ -65,7 +75,7 @@ impl FromStr for ItemSelector {
         let mut item_selector = ItemSelector::default();
 
         if s == "*" {
-            item_selector.all = false;
+            item_selector.all = true;
             return Ok(item_selector);
         }
 
@@ -125,4 +135,8 @@ fn item_selector_test() {
     let item_selector: ItemSelector = s.parse().unwrap();
 
     eprintln!("{:?}", item_selector);
+
+    let item_selector: ItemSelector = "*".parse().unwrap();
+    assert!(item_selector.all);
+    assert_eq!(item_selector.to_string(), "*");
 }
@@ -69,6 +69,17 @@ pub struct MapTemplateBoundary {
     pub w: MapTemplateBoundaryBehavior,
 }
 
+impl ConvertedIdxPp {
+    /// Returns the index in the conversion table, or None if empty
+    pub fn table_idx(&self) -> Option<usize> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.as_raw_int() as usize - 1)
+        }
+    }
+}
+
 /// Helper trait to convert between object index and u32 in maptemplate
 pub trait ConvertableIndex {
     fn conv_into(self, table: &[String]) -> u32;
@@ -39,6 +39,10 @@ pub struct AnimImgObject {
 pub struct DecoObject {
     pub id: String,
     pub img: Img,
+    /// Needed skill level to build this deco
+    pub build_skill: Option<u32>,
+    /// Needed materials to build this deco
+    pub materials: Option<Vec<(String, u32)>>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -52,6 +56,9 @@ pub struct EffectImgObject {
 pub struct ScriptObject {
     pub id: String,
     pub script: String,
+    /// Events that start this script
+    #[serde(default)]
+    pub hooks: Vec<gamedata::ScriptHookKind>,
     // pub byte_code: Option<Vec<u8>>,
 }
 
@@ -69,6 +76,10 @@ pub struct TileObject {
     pub kind: TileKind,
     pub symbol_color: (u8, u8, u8),
     pub fertility: u8,
+    /// Needed skill level to build this tile
+    pub build_skill: Option<u32>,
+    /// Needed materials to build this tile
+    pub materials: Option<Vec<(String, u32)>>,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
@@ -1,5 +1,6 @@
 use crate::obj::*;
 use crate::pakutil::load_objs_dir;
+use fnv::FnvHashMap;
 use std::num::NonZeroU32;
 use std::path::Path;
 
@@ -18,12 +19,7 @@ macro_rules! impl_idx {
             }
 
             fn search_idx(id: &str, objholder: &ObjectHolder) -> Option<$idx> {
-                for (i, ref o) in (&objholder.$mem).into_iter().enumerate() {
-                    if o.id == id {
-                        return Some($idx::from_usize(i));
-                    }
-                }
-                None
+                objholder.id_maps.$mem.get(id).map(|&i| $idx::from_usize(i))
             }
 
             fn as_raw_int(&self) -> u32 {
@@ -40,12 +36,8 @@ macro_rules! impl_idx {
                 id: &str,
                 objholder: &'a ObjectHolder,
             ) -> Option<&'a $obj> {
-                for ref o in (&objholder.$mem).iter() {
-                    if o.id == id {
-                        return Some(o);
-                    }
-                }
-                None
+                let i = *objholder.id_maps.$mem.get(id)?;
+                Some(&objholder.$mem[i])
             }
         }
 
@@ -87,13 +79,21 @@ macro_rules! impl_idx {
 macro_rules! impl_objholder {
     ($({$a:ident, $obj:ty, $mem:ident, $idx:ident}),*) => {
         pub struct ObjectHolder {
-            $(pub $mem: Vec<$obj>),*
+            $(pub $mem: Vec<$obj>,)*
+            id_maps: IdMaps,
+        }
+
+        /// Maps from id to index for each object type
+        #[derive(Default)]
+        struct IdMaps {
+            $($mem: FnvHashMap<String, usize>),*
         }
 
         impl Default for ObjectHolder {
             fn default() -> ObjectHolder {
                 ObjectHolder {
-                    $($mem: Vec::new()),*
+                    $($mem: Vec::new(),)*
+                    id_maps: IdMaps::default(),
                 }
             }
         }
@@ -116,6 +116,7 @@ macro_rules! impl_objholder {
                 }
 
                 objholder.sort();
+                objholder.build_id_maps();
                 objholder
             }
 
@@ -129,6 +130,18 @@ macro_rules! impl_objholder {
                 self.chara_template.sort_by(|a, b| cmp_chara_template(a, b));
             }
 
+            /// Build maps for searching index by id. Must be called after sorting.
+            fn build_id_maps(&mut self) {
+                $(
+                    let map = &mut self.id_maps.$mem;
+                    map.clear();
+                    for (i, o) in self.$mem.iter().enumerate() {
+                        // If the same id exists, the first one is used
+                        map.entry(o.id.clone()).or_insert(i);
+                    }
+                )*
+            }
+
             /// Write id table
             pub fn write_table<W: std::io::Write>(&self, mut w: W) -> Result<(), std::io::Error> {
                 $({
@@ -15,15 +15,25 @@ impl GameData {
             print_save_data_size(self); // Debug code for save file size optimization
         }
 
-        let save_dir = path.as_ref();
+        self.save_to(path.as_ref(), false)
+    }
+
+    /// Save game data to the specified directory as a copy.
+    /// All loaded maps are written, and changed flags of maps are kept for the next `save()`.
+    /// Unloaded maps are not written, so they need to be copied from the original save directory.
+    pub fn save_copy<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn std::error::Error>> {
+        self.save_to(path.as_ref(), true)
+    }
 
+    fn save_to(&self, save_dir: &Path, copy: bool) -> Result<(), Box<dyn std::error::Error>> {
         // Create directory
         create_dir_all(&save_dir)?;
 
         // Write id table file
         let mut file = BufWriter::new(File::create(save_dir.join("idtable"))?);
-        writeln!(file, "{:016x}", *crate::gobj::OBJ_HOLDER_HASH)?;
+        writeln!(file, "{:016x}", id_table_hash())?;
         crate::gobj::get_objholder().write_table(&mut file)?;
+        crate::gamedata::skill::write_skill_table(&mut file)?;
 
         // Write metadata file
         let mut file = BufWriter::new(File::create(save_dir.join("metadata"))?);
@@ -33,16 +43,25 @@ impl GameData {
         let mut file = BufWriter::new(File::create(save_dir.join("gamedata"))?);
         to_writer_with_mode(&mut file, &self)?;
 
+        // Write RNG state
+        let mut file = BufWriter::new(File::create(save_dir.join("rngstate"))?);
+        to_writer_with_mode(&mut file, &rng::state())?;
+
         // Write maps
         let map_dir = save_dir.join("maps");
         create_dir_all(&map_dir)?;
 
         let mut errors: Vec<MapLoadError> = Vec::new();
-        self.region
-            .visit_all_maps(|_mid, map| match BoxedMap::write(map, &map_dir) {
-                Ok(_) => (),
-                Err(e) => errors.push(e),
-            });
+        self.region.visit_all_maps(|_mid, map| {
+            let result = if copy {
+                BoxedMap::write_copy(map, &map_dir)
+            } else {
+                BoxedMap::write(map, &map_dir)
+            };
+            if let Err(e) = result {
+                errors.push(e);
+            }
+        });
 
         if !errors.is_empty() {
             return Err(errors.into_iter().next().unwrap().into());
@@ -51,8 +70,12 @@ impl GameData {
         Ok(())
     }
 
-    /// Load game data from specified directory
-    pub fn load<P: AsRef<Path>>(path: P) -> Result<GameData, Box<dyn std::error::Error>> {
+    /// Load game data from specified directory.
+    /// If the save data has no RNG state, RNG is reseeded, with the fixed seed if fix_rand is true.
+    pub fn load<P: AsRef<Path>>(
+        path: P,
+        fix_rand: bool,
+    ) -> Result<GameData, Box<dyn std::error::Error>> {
         let save_dir = path.as_ref();
 
         // Read metadata file
@@ -61,8 +84,7 @@ impl GameData {
 
         // Read index conversion table
         let mut file = BufReader::new(File::open(save_dir.join("idtable"))?);
-        let idx_conv_table =
-            crate::idx_conv::IdxConvTable::read(&mut file, *crate::gobj::OBJ_HOLDER_HASH)?;
+        let idx_conv_table = crate::idx_conv::IdxConvTable::read(&mut file, id_table_hash())?;
         let is_table_changed = idx_conv_table.is_some();
         if is_table_changed {
             info!("Detected changes in the id table. Conversion table is created.");
@@ -74,6 +96,17 @@ impl GameData {
         let mut gamedata: GameData = from_reader(&mut file)?;
         gamedata.meta = meta;
 
+        // Read RNG state. Old save data may not have this file.
+        let rng_state_path = save_dir.join("rngstate");
+        if rng_state_path.exists() {
+            let mut file = BufReader::new(File::open(rng_state_path)?);
+            let rng_state: rng::RngState = from_reader(&mut file)?;
+            rng::set_state(rng_state);
+        } else {
+            info!("RNG state file is not found. Reseed RNG.");
+            rng::reseed(fix_rand);
+        }
+
         let map_dir = save_dir.join("maps");
         if is_table_changed {
             // Preload is needed if id table is changed
@@ -85,9 +118,12 @@ impl GameData {
                 gamedata.region.preload_map(*mid, &map_dir);
             }
         } else {
-            // Preload current map
+            // Preload current map and its region map
             let mid = gamedata.get_current_mapid();
             gamedata.region.preload_map(mid, &map_dir);
+            gamedata
+                .region
+                .preload_map(MapId::from(mid.rid()), &map_dir);
         }
 
         Ok(gamedata)
@@ -131,6 +167,16 @@ impl GameData {
     }
 }
 
+/// Hash of objects and skills to verify the identity of id tables
+#[cfg(feature = "global_state_obj")]
+fn id_table_hash() -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = fnv::FnvHasher::default();
+    crate::gobj::OBJ_HOLDER_HASH.hash(&mut hasher);
+    crate::gamedata::skill::hash_skill_table(&mut hasher);
+    hasher.finish()
+}
+
 /// Print save data size
 #[cfg(debug_assertions)]
 fn print_save_data_size(gd: &GameData) {
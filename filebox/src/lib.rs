@@ -73,13 +73,23 @@ impl<T: WithId> FileBox<T> {
     }
 
     pub fn write_force<P: AsRef<Path>>(s: &Self, p: P) -> Result<(), T::Error> {
+        Self::write_copy(s, p)?;
+        if s.inner.is_some() {
+            s.changed.set(false);
+        }
+
+        Ok(())
+    }
+
+    /// Write the inner data without clearing the changed flag.
+    /// The data will be written again by the next `write()`.
+    pub fn write_copy<P: AsRef<Path>>(s: &Self, p: P) -> Result<(), T::Error> {
         if let Some(a) = &s.inner {
             let mut file = GzEncoder::new(
                 BufWriter::new(File::create(s.path(p))?),
                 Compression::fast(),
             );
             T::write(&mut file, a)?;
-            s.changed.set(false);
         }
 
         Ok(())
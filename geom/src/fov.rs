@@ -0,0 +1,207 @@
+//! Field of view calculation by symmetric shadowcasting.
+//! See <https://www.albertford.com/shadowcasting/> for the algorithm.
+
+use crate::Vec2d;
+
+/// Calculate the field of view from origin.
+/// `is_opaque` returns true if the tile blocks sight. Tiles outside the map should be opaque.
+/// `mark_visible` is called for visible tiles within radius.
+/// It may be called more than once for tiles on the diagonals.
+///
+/// The result is symmetric: if a floor tile A is visible from B, B is visible from A.
+pub fn fov<F, G>(origin: Vec2d, radius: i32, mut is_opaque: F, mut mark_visible: G)
+where
+    F: FnMut(Vec2d) -> bool,
+    G: FnMut(Vec2d),
+{
+    mark_visible(origin);
+
+    for &quadrant in &[
+        Quadrant::North,
+        Quadrant::East,
+        Quadrant::South,
+        Quadrant::West,
+    ] {
+        let mut rows = vec![Row {
+            depth: 1,
+            start_slope: Slope::new(-1, 1),
+            end_slope: Slope::new(1, 1),
+        }];
+
+        while let Some(mut row) = rows.pop() {
+            if row.depth > radius {
+                continue;
+            }
+            let mut prev_is_wall = None;
+
+            for col in row.min_col()..=row.max_col() {
+                let pos = quadrant.transform(origin, row.depth, col);
+                let is_wall = is_opaque(pos);
+
+                if (is_wall || row.is_symmetric(col)) && in_radius(row.depth, col, radius) {
+                    mark_visible(pos);
+                }
+                if prev_is_wall == Some(true) && !is_wall {
+                    row.start_slope = Slope::of_tile(row.depth, col);
+                }
+                if prev_is_wall == Some(false) && is_wall {
+                    let mut next_row = row.next();
+                    next_row.end_slope = Slope::of_tile(row.depth, col);
+                    rows.push(next_row);
+                }
+                prev_is_wall = Some(is_wall);
+            }
+
+            if prev_is_wall == Some(false) {
+                rows.push(row.next());
+            }
+        }
+    }
+}
+
+fn in_radius(depth: i32, col: i32, radius: i32) -> bool {
+    depth * depth + col * col <= radius * radius
+}
+
+#[derive(Clone, Copy)]
+enum Quadrant {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Quadrant {
+    fn transform(self, origin: Vec2d, depth: i32, col: i32) -> Vec2d {
+        match self {
+            Quadrant::North => Vec2d(origin.0 + col, origin.1 - depth),
+            Quadrant::East => Vec2d(origin.0 + depth, origin.1 + col),
+            Quadrant::South => Vec2d(origin.0 + col, origin.1 + depth),
+            Quadrant::West => Vec2d(origin.0 - depth, origin.1 + col),
+        }
+    }
+}
+
+/// Rational slope to avoid rounding errors. The denominator is always positive.
+#[derive(Clone, Copy)]
+struct Slope {
+    num: i32,
+    den: i32,
+}
+
+impl Slope {
+    fn new(num: i32, den: i32) -> Slope {
+        Slope { num, den }
+    }
+
+    /// Slope of the left edge of the tile
+    fn of_tile(depth: i32, col: i32) -> Slope {
+        Slope::new(2 * col - 1, 2 * depth)
+    }
+}
+
+struct Row {
+    depth: i32,
+    start_slope: Slope,
+    end_slope: Slope,
+}
+
+impl Row {
+    fn min_col(&self) -> i32 {
+        // Round depth * start_slope with ties up
+        let s = self.start_slope;
+        (2 * self.depth * s.num + s.den).div_euclid(2 * s.den)
+    }
+
+    fn max_col(&self) -> i32 {
+        // Round depth * end_slope with ties down
+        let s = self.end_slope;
+        -(-2 * self.depth * s.num + s.den).div_euclid(2 * s.den)
+    }
+
+    fn is_symmetric(&self, col: i32) -> bool {
+        col * self.start_slope.den >= self.depth * self.start_slope.num
+            && col * self.end_slope.den <= self.depth * self.end_slope.num
+    }
+
+    fn next(&self) -> Row {
+        Row {
+            depth: self.depth + 1,
+            start_slope: self.start_slope,
+            end_slope: self.end_slope,
+        }
+    }
+}
+
+#[cfg(test)]
+mod fov_test {
+    use super::*;
+    use crate::Array2d;
+
+    fn calc_fov(map: &[&str], origin: Vec2d, radius: i32) -> Array2d<bool> {
+        let w = map[0].len() as u32;
+        let h = map.len() as u32;
+        let mut visible = Array2d::new(w, h, false);
+        let is_inside =
+            |pos: Vec2d| pos.0 >= 0 && pos.1 >= 0 && pos.0 < w as i32 && pos.1 < h as i32;
+        fov(
+            origin,
+            radius,
+            |pos| !is_inside(pos) || map[pos.1 as usize].as_bytes()[pos.0 as usize] == b'#',
+            |pos| {
+                if is_inside(pos) {
+                    visible[pos] = true;
+                }
+            },
+        );
+        visible
+    }
+
+    #[test]
+    fn fov_test() {
+        let map = [
+            "........", //
+            "........", //
+            "...#....", //
+            "........", //
+            "........", //
+        ];
+        let visible = calc_fov(&map, Vec2d(1, 2), 10);
+        assert!(visible[Vec2d(3, 2)]);
+        assert!(!visible[Vec2d(4, 2)]);
+        assert!(!visible[Vec2d(7, 2)]);
+        assert!(visible[Vec2d(7, 0)]);
+        assert!(visible[Vec2d(0, 4)]);
+
+        // Out of radius
+        let visible = calc_fov(&map, Vec2d(0, 0), 3);
+        assert!(visible[Vec2d(3, 0)]);
+        assert!(!visible[Vec2d(3, 2)]);
+    }
+
+    #[test]
+    fn fov_symmetry_test() {
+        let map = [
+            "..#.....", //
+            "......#.", //
+            ".#..#...", //
+            "....##..", //
+            "#.......", //
+            "...#..#.", //
+        ];
+        let is_floor = |pos: Vec2d| map[pos.1 as usize].as_bytes()[pos.0 as usize] != b'#';
+        let w = map[0].len() as i32;
+        let h = map.len() as i32;
+        let tiles: Vec<Vec2d> = (0..h)
+            .flat_map(|y| (0..w).map(move |x| Vec2d(x, y)))
+            .filter(|&pos| is_floor(pos))
+            .collect();
+        let fovs: Vec<Array2d<bool>> = tiles.iter().map(|&a| calc_fov(&map, a, 20)).collect();
+
+        for (i, &a) in tiles.iter().enumerate() {
+            for (j, &b) in tiles.iter().enumerate() {
+                assert_eq!(fovs[i][b], fovs[j][a], "{:?} and {:?}", a, b);
+            }
+        }
+    }
+}
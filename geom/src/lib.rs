@@ -4,8 +4,10 @@ extern crate serde;
 #[macro_use]
 extern crate serde_derive;
 
+mod fov;
 mod pathfinding;
 mod shape;
+pub use fov::*;
 pub use pathfinding::*;
 pub use shape::*;
 
@@ -1,5 +1,6 @@
 use crate::{Array2d, Direction, Vec2d};
-use std::collections::VecDeque;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
 
 pub struct PathFinding<F> {
     w: u32,
@@ -74,6 +75,143 @@ impl<F: Fn(Vec2d) -> bool> PathFinding<F> {
     }
 }
 
+/// A* search with movement costs.
+/// Buffers are kept between searches to avoid allocation for each search.
+pub struct AStar {
+    nodes: Array2d<AStarNode>,
+    /// Used to distinguish nodes of the current search from old ones without clearing
+    generation: u32,
+    open: BinaryHeap<Reverse<(u32, u32, Vec2d)>>,
+}
+
+#[derive(Clone, Copy, Default)]
+struct AStarNode {
+    generation: u32,
+    closed: bool,
+    cost: u32,
+    step: u32,
+    dir: Direction,
+}
+
+impl Default for AStar {
+    fn default() -> Self {
+        AStar {
+            nodes: Array2d::new(0, 0, AStarNode::default()),
+            generation: 0,
+            open: BinaryHeap::new(),
+        }
+    }
+}
+
+impl AStar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Calculate the lowest cost route on a w x h map.
+    /// `cost` returns the cost to enter the tile, or None if the tile is impassable.
+    /// Costs less than 1 are regarded as 1 to keep the heuristic admissible.
+    /// The end tile can be entered even if it is impassable, like a tile occupied by the target.
+    /// Routes longer than max_step are not searched.
+    pub fn route<F: FnMut(Vec2d) -> Option<u32>>(
+        &mut self,
+        w: u32,
+        h: u32,
+        start: Vec2d,
+        end: Vec2d,
+        max_step: u32,
+        mut cost: F,
+    ) -> Option<Vec<Vec2d>> {
+        let is_inside =
+            |pos: Vec2d| pos.0 >= 0 && pos.1 >= 0 && pos.0 < w as i32 && pos.1 < h as i32;
+        if !is_inside(start) || !is_inside(end) {
+            return None;
+        }
+        self.prepare(w, h);
+        let generation = self.generation;
+
+        // Chebyshev distance is admissible for 8 directional moves with cost >= 1
+        let heuristic =
+            |pos: Vec2d| std::cmp::max((pos.0 - end.0).abs(), (pos.1 - end.1).abs()) as u32;
+
+        self.nodes[start] = AStarNode {
+            generation,
+            closed: false,
+            cost: 0,
+            step: 0,
+            dir: Direction::NONE,
+        };
+        self.open.push(Reverse((heuristic(start), 0, start)));
+
+        while let Some(Reverse((_, pos_cost, pos))) = self.open.pop() {
+            let node = self.nodes[pos];
+            if node.closed || node.cost < pos_cost {
+                continue;
+            }
+            if pos == end {
+                return Some(self.trace_route(start, end));
+            }
+            self.nodes[pos].closed = true;
+            if node.step >= max_step {
+                continue;
+            }
+
+            for &dir in Direction::EIGHT_DIRS.iter() {
+                let next_pos = pos + dir.as_vec();
+                if !is_inside(next_pos) {
+                    continue;
+                }
+                let tile_cost = match cost(next_pos) {
+                    Some(tile_cost) => tile_cost.max(1),
+                    None if next_pos == end => 1,
+                    None => continue,
+                };
+                let next_cost = pos_cost + tile_cost;
+                let next_node = &mut self.nodes[next_pos];
+                if next_node.generation == generation
+                    && (next_node.closed || next_node.cost <= next_cost)
+                {
+                    continue;
+                }
+                *next_node = AStarNode {
+                    generation,
+                    closed: false,
+                    cost: next_cost,
+                    step: node.step + 1,
+                    dir,
+                };
+                self.open.push(Reverse((
+                    next_cost + heuristic(next_pos),
+                    next_cost,
+                    next_pos,
+                )));
+            }
+        }
+        None
+    }
+
+    /// Prepare buffers for a new search
+    fn prepare(&mut self, w: u32, h: u32) {
+        self.open.clear();
+        if self.nodes.size() != (w, h) || self.generation == u32::MAX {
+            self.nodes = Array2d::new(w, h, AStarNode::default());
+            self.generation = 0;
+        }
+        self.generation += 1;
+    }
+
+    fn trace_route(&self, start: Vec2d, end: Vec2d) -> Vec<Vec2d> {
+        let mut route = vec![end];
+        let mut pos = end;
+        while pos != start {
+            pos = pos - self.nodes[pos].dir.as_vec();
+            route.push(pos);
+        }
+        route.reverse();
+        route
+    }
+}
+
 #[cfg(test)]
 mod pathfinding_test {
     use super::*;
@@ -94,4 +232,50 @@ mod pathfinding_test {
 
         assert!(route.is_none());
     }
+
+    #[test]
+    fn astar_test() {
+        let map = [[1, 1, 1, 1], [1, 0, 1, 1], [1, 0, 0, 1], [1, 1, 0, 1]];
+        let cost = |pos: Vec2d| {
+            if map[pos.1 as usize][pos.0 as usize] != 0 {
+                Some(1)
+            } else {
+                None
+            }
+        };
+        let mut astar = AStar::new();
+
+        let route = astar.route(4, 4, Vec2d(0, 0), Vec2d(3, 3), 100, cost);
+        assert_eq!(route.unwrap().len(), 5);
+
+        // Buffers are reused for the next search
+        let route = astar.route(4, 4, Vec2d(0, 3), Vec2d(1, 3), 100, cost);
+        assert_eq!(route, Some(vec![Vec2d(0, 3), Vec2d(1, 3)]));
+
+        let route = astar.route(4, 4, Vec2d(0, 0), Vec2d(3, 3), 2, cost);
+        assert!(route.is_none());
+
+        let map = [[1, 1, 1], [0, 0, 0], [1, 1, 1]];
+        let route = AStar::new().route(3, 3, Vec2d(0, 0), Vec2d(2, 2), 100, |pos| {
+            if map[pos.1 as usize][pos.0 as usize] != 0 {
+                Some(1)
+            } else {
+                None
+            }
+        });
+        assert!(route.is_none());
+    }
+
+    #[test]
+    fn astar_cost_test() {
+        // The straight route is expensive, so the route goes around it
+        let map = [[1, 1, 1], [1, 9, 1], [1, 9, 1], [1, 1, 1]];
+        let route = AStar::new()
+            .route(3, 4, Vec2d(1, 0), Vec2d(1, 3), 100, |pos| {
+                Some(map[pos.1 as usize][pos.0 as usize])
+            })
+            .unwrap();
+        assert!(!route.contains(&Vec2d(1, 1)) && !route.contains(&Vec2d(1, 2)));
+        assert_eq!(route.len(), 4);
+    }
 }
@@ -5,8 +5,8 @@ pub enum ShapeKind {
     OneTile,
     Line,
     Circle,
-    // Sector,
-    // All,
+    Sector,
+    All,
 }
 
 impl Default for ShapeKind {
@@ -17,24 +17,63 @@ impl Default for ShapeKind {
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum Shape {
-    OneTile { pos: Vec2d },
-    Line { start: Vec2d, end: Vec2d },
-    Circle { center: Vec2d, radius: u32 },
+    OneTile {
+        pos: Vec2d,
+    },
+    /// Tiles on the line from start to end, excluding start
+    Line {
+        start: Vec2d,
+        end: Vec2d,
+    },
+    Circle {
+        center: Vec2d,
+        radius: u32,
+    },
+    /// Fan-shaped area spreading from center to the direction of target.
+    /// angle is the central angle in degrees. center is not included.
+    Sector {
+        center: Vec2d,
+        target: Vec2d,
+        radius: u32,
+        angle: u32,
+    },
+    /// All tiles in the rectangle
+    All {
+        top_left: Vec2d,
+        bottom_right: Vec2d,
+    },
 }
 
 impl Shape {
     pub fn is_inside(&self, p: Vec2d) -> bool {
         match *self {
             Shape::OneTile { pos } => pos == p,
-            Shape::Line { .. } => unimplemented!(),
+            Shape::Line { start, end } => {
+                p != start && super::LineIter::new(start, end).any(|pos| pos == p)
+            }
             Shape::Circle { center, radius } => (center.mdistance(p) as u32) < radius,
+            Shape::Sector {
+                center,
+                target,
+                radius,
+                angle,
+            } => in_sector(center, target, radius, angle, p),
+            Shape::All {
+                top_left,
+                bottom_right,
+            } => {
+                top_left.0 <= p.0
+                    && p.0 <= bottom_right.0
+                    && top_left.1 <= p.1
+                    && p.1 <= bottom_right.1
+            }
         }
     }
 
     pub fn iter(&self) -> Vec<Vec2d> {
         match *self {
             Shape::OneTile { pos } => vec![pos],
-            Shape::Line { .. } => unimplemented!(),
+            Shape::Line { start, end } => super::LineIter::new(start, end).skip(1).collect(),
             Shape::Circle { center, radius } => {
                 if radius == 0 {
                     return vec![center];
@@ -49,6 +88,88 @@ impl Shape {
                 .filter(|pos| pos.distance2(center) < r2)
                 .collect()
             }
+            Shape::Sector {
+                center,
+                target,
+                radius,
+                angle,
+            } => {
+                let r = radius as i32;
+                super::RectIter::new(center - Vec2d::new(r, r), center + Vec2d::new(r, r))
+                    .filter(|pos| in_sector(center, target, radius, angle, *pos))
+                    .collect()
+            }
+            Shape::All {
+                top_left,
+                bottom_right,
+            } => super::RectIter::new(top_left, bottom_right).collect(),
+        }
+    }
+}
+
+fn in_sector(center: Vec2d, target: Vec2d, radius: u32, angle: u32, p: Vec2d) -> bool {
+    if p == center || target == center {
+        return false;
+    }
+    let r = radius as f32 + 0.5;
+    if p.distance2(center) >= r * r {
+        return false;
+    }
+    let v = p - center;
+    let d = target - center;
+    let dot = (v.0 * d.0 + v.1 * d.1) as f32;
+    let cos = dot / (p.distance2(center).sqrt() * target.distance2(center).sqrt());
+    // Small margin to include tiles just on the edges
+    let half_angle = (angle as f32 / 2.0).to_radians();
+    cos >= half_angle.cos() - 1.0e-4
+}
+
+#[cfg(test)]
+mod shape_test {
+    use super::*;
+
+    #[test]
+    fn line_test() {
+        let shape = Shape::Line {
+            start: Vec2d(0, 0),
+            end: Vec2d(3, 0),
+        };
+        assert_eq!(shape.iter(), vec![Vec2d(1, 0), Vec2d(2, 0), Vec2d(3, 0)]);
+        assert!(shape.is_inside(Vec2d(2, 0)));
+        assert!(!shape.is_inside(Vec2d(0, 0)));
+        assert!(!shape.is_inside(Vec2d(2, 1)));
+    }
+
+    #[test]
+    fn sector_test() {
+        let shape = Shape::Sector {
+            center: Vec2d(0, 0),
+            target: Vec2d(1, 0),
+            radius: 3,
+            angle: 90,
+        };
+        let tiles = shape.iter();
+        assert!(tiles.contains(&Vec2d(1, 0)));
+        assert!(tiles.contains(&Vec2d(3, 0)));
+        assert!(tiles.contains(&Vec2d(2, 2)));
+        assert!(tiles.contains(&Vec2d(2, -2)));
+        assert!(!tiles.contains(&Vec2d(0, 0)));
+        assert!(!tiles.contains(&Vec2d(1, 2)));
+        assert!(!tiles.contains(&Vec2d(-1, 0)));
+        assert!(!tiles.contains(&Vec2d(4, 0)));
+        for pos in &tiles {
+            assert!(shape.is_inside(*pos));
         }
     }
+
+    #[test]
+    fn all_test() {
+        let shape = Shape::All {
+            top_left: Vec2d(0, 0),
+            bottom_right: Vec2d(2, 1),
+        };
+        assert_eq!(shape.iter().len(), 6);
+        assert!(shape.is_inside(Vec2d(2, 1)));
+        assert!(!shape.is_inside(Vec2d(3, 1)));
+    }
 }
@@ -32,10 +32,17 @@ pub fn build_object(input: Input) -> Result<Object, Error> {
 
 fn build_deco_object(input: Input) -> Result<DecoObject, Error> {
     let img = get_optional_field!(input, image);
+    let (build_skill, materials) = if let Some(deco) = input.deco {
+        (deco.build_skill, deco.materials)
+    } else {
+        (None, None)
+    };
 
     Ok(DecoObject {
         id: input.id,
         img: build_img(img)?.0,
+        build_skill,
+        materials,
     })
 }
 
@@ -74,6 +81,8 @@ fn build_tile_object(input: Input) -> Result<TileObject, Error> {
         kind: tile_dep_input.kind,
         symbol_color: imgdata.calc_average_color(),
         fertility: tile_dep_input.fertility,
+        build_skill: tile_dep_input.build_skill,
+        materials: tile_dep_input.materials,
     })
 }
 
@@ -174,6 +183,7 @@ fn build_script_object(input: Input) -> Result<ScriptObject, Error> {
     Ok(ScriptObject {
         id: input.id,
         script: s.script,
+        hooks: s.hooks,
     })
 }
 
@@ -1,6 +1,7 @@
 use common::basic::BonusLevel;
 use common::gamedata::{
-    self, ActiveSkillId, CharaClass, Effect, ElementArray, FactionId, Harvest, SkillKind,
+    self, ActiveSkillId, CharaClass, Effect, ElementArray, FactionId, Harvest, ScriptHookKind,
+    SkillKind,
 };
 use common::sitegen;
 use geom::Vec2d;
@@ -17,6 +18,8 @@ pub struct Input {
     #[serde(default, with = "::serde_with::rust::unwrap_or_skip")]
     pub item: Option<ItemDepInput>,
     #[serde(default, with = "::serde_with::rust::unwrap_or_skip")]
+    pub deco: Option<DecoDepInput>,
+    #[serde(default, with = "::serde_with::rust::unwrap_or_skip")]
     pub tile: Option<TileDepInput>,
     #[serde(default, with = "::serde_with::rust::unwrap_or_skip")]
     pub wall: Option<WallDepInput>,
@@ -103,6 +106,15 @@ pub struct TileDepInput {
     pub kind: ::common::obj::TileKind,
     #[serde(default)]
     pub fertility: u8,
+    pub build_skill: Option<u32>,
+    pub materials: Option<Vec<(String, u32)>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DecoDepInput {
+    pub build_skill: Option<u32>,
+    pub materials: Option<Vec<(String, u32)>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -202,4 +214,6 @@ pub struct SiteGenDepInput {
 #[serde(deny_unknown_fields)]
 pub struct ScriptDepInput {
     pub script: String,
+    #[serde(default)]
+    pub hooks: Vec<ScriptHookKind>,
 }
@@ -1,5 +1,6 @@
 use crate::verbose::print_verbose;
 use anyhow::*;
+use common::gamedata::ScriptHookKind;
 use common::obj::{Object, ScriptObject};
 use once_cell::sync::Lazy;
 use regex::Regex;
@@ -10,6 +11,7 @@ use std::path::Path;
 static FIRST_LINE: Lazy<Regex> = Lazy::new(|| Regex::new("# rusted-ruins-script").unwrap());
 static ID_LINE: Lazy<Regex> =
     Lazy::new(|| Regex::new("# id = \"([a-zA-Z!][a-zA-Z0-9_.-]*)\"").unwrap());
+static HOOK_LINE: Lazy<Regex> = Lazy::new(|| Regex::new("# hook = \"([a-z_]+)\"").unwrap());
 
 /// Read python script file
 pub fn read_pyscript<P: AsRef<Path>>(path: P) -> Result<Object> {
@@ -27,11 +29,28 @@ pub fn read_pyscript<P: AsRef<Path>>(path: P) -> Result<Object> {
         );
     }
 
-    // Check the second line for id
-    let mut second_line = String::new();
-    f.read_line(&mut second_line)?;
-    let id = if let Some(caps) = ID_LINE.captures(&second_line) {
-        caps.get(1).unwrap().as_str().to_owned()
+    // Check the following comment lines for id and hooks
+    let mut id = None;
+    let mut hooks = Vec::new();
+    loop {
+        let mut line = String::new();
+        if f.read_line(&mut line)? == 0 || !line.starts_with('#') {
+            break;
+        }
+        if let Some(caps) = ID_LINE.captures(&line) {
+            id = Some(caps.get(1).unwrap().as_str().to_owned());
+        } else if let Some(caps) = HOOK_LINE.captures(&line) {
+            let hook: ScriptHookKind = caps
+                .get(1)
+                .unwrap()
+                .as_str()
+                .parse()
+                .map_err(|e: String| anyhow!("{} in {}", e, path.to_string_lossy()))?;
+            hooks.push(hook);
+        }
+    }
+    let id = if let Some(id) = id {
+        id
     } else if let Some(file_stem) = path
         .file_stem()
         .and_then(|file_stem| file_stem.to_os_string().into_string().ok())
@@ -48,5 +67,5 @@ pub fn read_pyscript<P: AsRef<Path>>(path: P) -> Result<Object> {
     let mut script = String::new();
     f.read_to_string(&mut script)?;
 
-    Ok(Object::Script(ScriptObject { id, script }))
+    Ok(Object::Script(ScriptObject { id, script, hooks }))
 }
@@ -0,0 +1,327 @@
+//! Command line operations for batch editing of map template files
+
+use crate::edit_map::EditingMap;
+use crate::file::{load_from_file, save_to_file};
+use crate::pixbuf_holder::PixbufHolder;
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use common::basic::{N_TILE_IMG_LAYER, TILE_SIZE_I};
+use common::gobj;
+use common::obj::MapTemplateObject;
+use common::objholder::*;
+use geom::*;
+use std::error::Error;
+use std::path::Path;
+
+pub fn app() -> App<'static, 'static> {
+    App::new("Rusted Ruins Map Editor")
+        .version(env!("CARGO_PKG_VERSION"))
+        .setting(AppSettings::VersionlessSubcommands)
+        .subcommand(
+            SubCommand::with_name("resize")
+                .about("Resizes maps")
+                .arg(files_arg())
+                .arg(size_arg("width", "W").required(true))
+                .arg(size_arg("height", "H").required(true))
+                .arg(size_arg("offset-x", "X").help("Left position of new map in the old map"))
+                .arg(size_arg("offset-y", "Y").help("Top position of new map in the old map")),
+        )
+        .subcommand(
+            SubCommand::with_name("replace")
+                .about("Replaces tile, wall or deco ids in maps")
+                .arg(files_arg())
+                .arg(replace_arg("tile"))
+                .arg(replace_arg("wall"))
+                .arg(replace_arg("deco")),
+        )
+        .subcommand(
+            SubCommand::with_name("render")
+                .about("Renders a map to PNG file")
+                .arg(Arg::with_name("FILE").required(true))
+                .arg(Arg::with_name("OUTPUT").required(true)),
+        )
+        .subcommand(
+            SubCommand::with_name("validate")
+                .about("Checks that maps only use objects in the loaded paks")
+                .arg(files_arg()),
+        )
+}
+
+fn files_arg() -> Arg<'static, 'static> {
+    Arg::with_name("FILES").required(true).multiple(true)
+}
+
+fn size_arg(name: &'static str, value_name: &'static str) -> Arg<'static, 'static> {
+    Arg::with_name(name)
+        .long(name)
+        .value_name(value_name)
+        .allow_hyphen_values(true)
+}
+
+fn replace_arg(name: &'static str) -> Arg<'static, 'static> {
+    Arg::with_name(name)
+        .long(name)
+        .value_names(&["FROM", "TO"])
+        .number_of_values(2)
+        .multiple(true)
+}
+
+/// Run a subcommand. Returns None if no subcommand is given.
+pub fn run(matches: &ArgMatches) -> Option<Result<(), Box<dyn Error>>> {
+    let result = match matches.subcommand() {
+        ("resize", Some(matches)) => resize(matches),
+        ("replace", Some(matches)) => replace(matches),
+        ("render", Some(matches)) => render(matches),
+        ("validate", Some(matches)) => validate(matches),
+        _ => return None,
+    };
+    Some(result)
+}
+
+fn resize(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let width: u32 = matches.value_of("width").unwrap().parse()?;
+    let height: u32 = matches.value_of("height").unwrap().parse()?;
+    let offset_x: i32 = matches.value_of("offset-x").unwrap_or("0").parse()?;
+    let offset_y: i32 = matches.value_of("offset-y").unwrap_or("0").parse()?;
+
+    for path in matches.values_of("FILES").unwrap() {
+        let path = Path::new(path);
+        let mut map = load_map(path)?;
+        map.resize(width, height, offset_x, offset_y);
+        save_to_file(path, map.create_mapobj())?;
+        println!("{}: resized to {}x{}", path.display(), width, height);
+    }
+    Ok(())
+}
+
+fn replace(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let tiles = replace_pairs::<TileIdx>(matches, "tile")?;
+    let walls = replace_pairs::<WallIdx>(matches, "wall")?;
+    let decos = replace_pairs::<DecoIdx>(matches, "deco")?;
+    if tiles.is_empty() && walls.is_empty() && decos.is_empty() {
+        return Err("no replacement is given".into());
+    }
+
+    for path in matches.values_of("FILES").unwrap() {
+        let path = Path::new(path);
+        let mut map = load_map(path)?;
+        let mut n = 0;
+        for &(from, to) in &tiles {
+            n += replace_tile(&mut map, from, to);
+        }
+        for &(from, to) in &walls {
+            n += replace_wall(&mut map, from, to);
+        }
+        for &(from, to) in &decos {
+            n += replace_deco(&mut map, from, to);
+        }
+        if n > 0 {
+            save_to_file(path, map.create_mapobj())?;
+        }
+        println!("{}: {} replaced", path.display(), n);
+    }
+    Ok(())
+}
+
+fn replace_pairs<T: ObjectIndex>(
+    matches: &ArgMatches,
+    name: &str,
+) -> Result<Vec<(T, T)>, Box<dyn Error>> {
+    let ids: Vec<&str> = matches
+        .values_of(name)
+        .map(|values| values.collect())
+        .unwrap_or_default();
+    let mut pairs = Vec::new();
+    for pair in ids.chunks(2) {
+        let from = gobj::id_to_idx_checked::<T>(pair[0])
+            .ok_or_else(|| format!("unknown {} id \"{}\"", name, pair[0]))?;
+        let to = gobj::id_to_idx_checked::<T>(pair[1])
+            .ok_or_else(|| format!("unknown {} id \"{}\"", name, pair[1]))?;
+        pairs.push((from, to));
+    }
+    Ok(pairs)
+}
+
+fn replace_tile(map: &mut EditingMap, from: TileIdx, to: TileIdx) -> usize {
+    let mut replaced = Vec::new();
+    for (pos, tile) in map.tile.iter_with_idx() {
+        for layer in 0..N_TILE_IMG_LAYER {
+            if tile[layer].idx() == Some(from) {
+                replaced.push((pos, layer));
+            }
+        }
+    }
+    for &(pos, layer) in &replaced {
+        map.set_tile(pos, to, layer);
+    }
+    // Piece patterns of replaced tiles and their neighbors are updated after all tiles are replaced
+    let mut redrawn: Vec<(Vec2d, usize)> = replaced
+        .iter()
+        .flat_map(|&(pos, layer)| {
+            std::iter::once(pos)
+                .chain(
+                    Direction::EIGHT_DIRS
+                        .iter()
+                        .map(move |dir| pos + dir.as_vec()),
+                )
+                .map(move |pos| (pos, layer))
+        })
+        .filter(|&(pos, _)| map.is_inside(pos))
+        .collect();
+    redrawn.sort_unstable();
+    redrawn.dedup();
+    for (pos, layer) in redrawn {
+        if let Some(idx) = map.tile[pos][layer].idx() {
+            map.tile_layer_draw(pos, idx, layer);
+        }
+    }
+    replaced.len()
+}
+
+fn replace_wall(map: &mut EditingMap, from: WallIdx, to: WallIdx) -> usize {
+    let replaced: Vec<_> = map
+        .wall
+        .iter_with_idx()
+        .filter(|(_, wall)| wall.idx() == Some(from))
+        .map(|(pos, _)| pos)
+        .collect();
+    for &pos in &replaced {
+        map.set_wall(pos, Some(to));
+    }
+    replaced.len()
+}
+
+fn replace_deco(map: &mut EditingMap, from: DecoIdx, to: DecoIdx) -> usize {
+    let replaced: Vec<_> = map
+        .deco
+        .iter_with_idx()
+        .filter(|(_, deco)| **deco == Some(from))
+        .map(|(pos, _)| pos)
+        .collect();
+    for &pos in &replaced {
+        map.set_deco(pos, Some(to));
+    }
+    replaced.len()
+}
+
+fn render(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let path = Path::new(matches.value_of("FILE").unwrap());
+    let output = matches.value_of("OUTPUT").unwrap();
+    let map = load_map(path)?;
+    let pbh = PixbufHolder::new();
+
+    let width = map.width as i32 * TILE_SIZE_I;
+    let height = map.height as i32 * TILE_SIZE_I;
+    let surface = cairo::ImageSurface::create(cairo::Format::ARgb32, width, height)?;
+    {
+        let cr = cairo::Context::new(&surface)?;
+        crate::draw_map::draw_map(
+            &cr,
+            &map,
+            &pbh,
+            width,
+            height,
+            (0, 0),
+            [true; N_TILE_IMG_LAYER],
+            true,
+            true,
+            true,
+        );
+    }
+    let pixbuf = gdk::pixbuf_get_from_surface(&surface, 0, 0, width, height)
+        .ok_or("failed to get image from surface")?;
+    pixbuf.savev(output, "png", &[])?;
+    println!("{}: rendered to {}", path.display(), output);
+    Ok(())
+}
+
+fn validate(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let mut n_invalid = 0;
+    for path in matches.values_of("FILES").unwrap() {
+        let path = Path::new(path);
+        let errors = validate_mapobj(&load_from_file(path)?);
+        if errors.is_empty() {
+            println!("{}: ok", path.display());
+        } else {
+            n_invalid += 1;
+            for e in &errors {
+                println!("{}: {}", path.display(), e);
+            }
+        }
+    }
+    if n_invalid > 0 {
+        Err(format!("{} invalid map(s)", n_invalid).into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Load a map for editing. Maps that use unknown objects are not loaded.
+fn load_map(path: &Path) -> Result<EditingMap, Box<dyn Error>> {
+    let obj = load_from_file(path)?;
+    let errors = validate_mapobj(&obj);
+    if !errors.is_empty() {
+        return Err(format!("{}: {}", path.display(), errors.join(", ")).into());
+    }
+    Ok(EditingMap::from(obj))
+}
+
+/// Returns error messages for objects not in the paks and inconsistent data
+fn validate_mapobj(obj: &MapTemplateObject) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    for id in &obj.tile_table {
+        if gobj::id_to_idx_checked::<TileIdx>(id).is_none() {
+            errors.push(format!("unknown tile \"{}\"", id));
+        }
+    }
+    for id in &obj.wall_table {
+        if gobj::id_to_idx_checked::<WallIdx>(id).is_none() {
+            errors.push(format!("unknown wall \"{}\"", id));
+        }
+    }
+    for id in &obj.deco_table {
+        if gobj::id_to_idx_checked::<DecoIdx>(id).is_none() {
+            errors.push(format!("unknown deco \"{}\"", id));
+        }
+    }
+    for (pos, item_gen) in &obj.items {
+        if gobj::id_to_idx_checked::<ItemIdx>(&item_gen.id).is_none() {
+            errors.push(format!("unknown item \"{}\" at {:?}", item_gen.id, pos));
+        }
+    }
+
+    let size = (obj.w, obj.h);
+    if obj.tile.size() != size || obj.wall.size() != size || obj.deco.size() != size {
+        errors.push(format!("layer size is not {}x{}", obj.w, obj.h));
+    }
+    if obj
+        .deco
+        .iter()
+        .any(|i| i.is_some_and(|i| i as usize >= obj.deco_table.len()))
+    {
+        errors.push("invalid deco index".to_owned());
+    }
+    if obj.tile.iter().any(|tile| {
+        (0..N_TILE_IMG_LAYER).any(|layer| {
+            tile[layer]
+                .table_idx()
+                .is_some_and(|i| i >= obj.tile_table.len())
+        })
+    }) {
+        errors.push("invalid tile index".to_owned());
+    }
+    if obj
+        .wall
+        .iter()
+        .any(|wall| wall.table_idx().is_some_and(|i| i >= obj.wall_table.len()))
+    {
+        errors.push("invalid wall index".to_owned());
+    }
+    for pos in &obj.entrance {
+        if pos.0 < 0 || pos.1 < 0 || pos.0 >= obj.w as i32 || pos.1 >= obj.h as i32 {
+            errors.push(format!("entrance {:?} is out of the map", pos));
+        }
+    }
+
+    errors
+}
@@ -7,6 +7,7 @@ use common::objholder::*;
 use common::piece_pattern::*;
 use geom::*;
 
+#[derive(Clone)]
 pub struct EditingMap {
     pub property: MapProperty,
     pub width: u32,
@@ -234,7 +235,7 @@ impl EditingMap {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct MapProperty {
     pub id: String,
     pub is_region_map: bool,
@@ -0,0 +1,404 @@
+//! Undo/redo history of map editing
+
+use crate::edit_map::{EditingMap, MapProperty};
+use common::gamedata::{ItemGen, TileLayers};
+use common::objholder::DecoIdx;
+use common::piece_pattern::WallIdxPp;
+use geom::*;
+
+/// Max number of commands kept in the undo stack
+const MAX_HISTORY: usize = 200;
+
+/// A reversible edit of a map
+pub enum EditCommand {
+    /// Changes of tiles, walls, decos and items
+    Cells(Vec<CellChange>),
+    /// Changes of map properties
+    Property {
+        before: Box<MapProperty>,
+        after: Box<MapProperty>,
+    },
+    /// Map resizing. Whole maps are kept because resizing may clip the map.
+    Resize {
+        before: Box<EditingMap>,
+        after: Box<EditingMap>,
+    },
+}
+
+pub struct CellChange {
+    pub pos: Vec2d,
+    pub before: CellState,
+    pub after: CellState,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct CellState {
+    pub tile: TileLayers,
+    pub wall: WallIdxPp,
+    pub deco: Option<DecoIdx>,
+    pub items: Vec<ItemGen>,
+}
+
+impl CellState {
+    fn get(map: &EditingMap, pos: Vec2d) -> CellState {
+        CellState {
+            tile: map.tile[pos],
+            wall: map.wall[pos],
+            deco: map.deco[pos],
+            items: map.items[pos].clone(),
+        }
+    }
+
+    fn set(&self, map: &mut EditingMap, pos: Vec2d) {
+        map.tile[pos] = self.tile;
+        map.wall[pos] = self.wall;
+        map.deco[pos] = self.deco;
+        map.items[pos] = self.items.clone();
+    }
+}
+
+impl EditCommand {
+    fn undo(&self, map: &mut EditingMap) {
+        match self {
+            EditCommand::Cells(changes) => {
+                for change in changes {
+                    change.before.set(map, change.pos);
+                }
+            }
+            EditCommand::Property { before, .. } => {
+                map.property = (**before).clone();
+            }
+            EditCommand::Resize { before, .. } => {
+                *map = (**before).clone();
+            }
+        }
+    }
+
+    fn redo(&self, map: &mut EditingMap) {
+        match self {
+            EditCommand::Cells(changes) => {
+                for change in changes {
+                    change.after.set(map, change.pos);
+                }
+            }
+            EditCommand::Property { after, .. } => {
+                map.property = (**after).clone();
+            }
+            EditCommand::Resize { after, .. } => {
+                *map = (**after).clone();
+            }
+        }
+    }
+
+    /// Returns true if this command changes nothing
+    fn is_empty(&self) -> bool {
+        match self {
+            EditCommand::Cells(changes) => changes.is_empty(),
+            EditCommand::Property { before, after } => before == after,
+            EditCommand::Resize { .. } => false,
+        }
+    }
+
+    /// Merge a following command into this command.
+    /// Returns the given command if it cannot be merged.
+    fn merge(&mut self, other: EditCommand) -> Option<EditCommand> {
+        match (self, other) {
+            (EditCommand::Cells(changes), EditCommand::Cells(other_changes)) => {
+                for other_change in other_changes {
+                    if let Some(change) = changes.iter_mut().find(|c| c.pos == other_change.pos) {
+                        change.after = other_change.after;
+                    } else {
+                        changes.push(other_change);
+                    }
+                }
+                changes.retain(|c| c.before != c.after);
+                None
+            }
+            (EditCommand::Property { after, .. }, EditCommand::Property { after: other, .. }) => {
+                *after = other;
+                None
+            }
+            (_, other) => Some(other),
+        }
+    }
+}
+
+/// Undo/redo stacks of edit commands
+#[derive(Default)]
+pub struct EditHistory {
+    undo_stack: Vec<EditCommand>,
+    redo_stack: Vec<EditCommand>,
+    /// Cells before the current drawing operation
+    pending: Option<Array2d<CellState>>,
+    /// Commands with the same key are merged while the key is unchanged
+    merge_key: Option<&'static str>,
+}
+
+impl EditHistory {
+    pub fn clear(&mut self) {
+        *self = EditHistory::default();
+    }
+
+    /// Push an edit command. Commands pushed successively with the same merge key
+    /// are undone at once, e.g. typing into a text entry.
+    pub fn push(&mut self, command: EditCommand, merge_key: Option<&'static str>) {
+        self.redo_stack.clear();
+        let command = match self.undo_stack.last_mut() {
+            Some(last) if merge_key.is_some() && merge_key == self.merge_key => last.merge(command),
+            _ => Some(command),
+        };
+        self.merge_key = merge_key;
+        if let Some(command) = command {
+            self.undo_stack.push(command);
+        } else if self.undo_stack.last().is_some_and(|c| c.is_empty()) {
+            self.undo_stack.pop();
+            self.merge_key = None;
+        }
+        if self.undo_stack.len() > MAX_HISTORY {
+            self.undo_stack.remove(0);
+        }
+    }
+
+    /// Start an operation that may change many cells, such as dragging or rectangle filling
+    pub fn begin_cells(&mut self, map: &EditingMap) {
+        self.merge_key = None;
+        self.pending = Some(Array2d::from_fn(map.width, map.height, |(x, y)| {
+            CellState::get(map, Vec2d(x as i32, y as i32))
+        }));
+    }
+
+    /// Finish the operation started by `begin_cells`, and record changed cells as a command
+    pub fn end_cells(&mut self, map: &EditingMap) {
+        let pending = if let Some(pending) = self.pending.take() {
+            pending
+        } else {
+            return;
+        };
+        if pending.size() != (map.width, map.height) {
+            return;
+        }
+        let changes: Vec<CellChange> = pending
+            .iter_with_idx()
+            .filter_map(|(pos, before)| {
+                let after = CellState::get(map, pos);
+                if *before != after {
+                    Some(CellChange {
+                        pos,
+                        before: before.clone(),
+                        after,
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect();
+        if !changes.is_empty() {
+            self.push(EditCommand::Cells(changes), None);
+        }
+    }
+
+    /// Edit a cell and record the change
+    pub fn edit_cell<F: FnOnce(&mut EditingMap)>(
+        &mut self,
+        map: &mut EditingMap,
+        pos: Vec2d,
+        merge_key: Option<&'static str>,
+        f: F,
+    ) {
+        let before = CellState::get(map, pos);
+        f(map);
+        let after = CellState::get(map, pos);
+        if before != after {
+            let change = CellChange { pos, before, after };
+            self.push(EditCommand::Cells(vec![change]), merge_key);
+        }
+    }
+
+    /// Edit map properties and record the change
+    pub fn edit_property<F: FnOnce(&mut MapProperty)>(
+        &mut self,
+        map: &mut EditingMap,
+        merge_key: Option<&'static str>,
+        f: F,
+    ) {
+        let before = map.property.clone();
+        f(&mut map.property);
+        if before != map.property {
+            let command = EditCommand::Property {
+                before: Box::new(before),
+                after: Box::new(map.property.clone()),
+            };
+            self.push(command, merge_key);
+        }
+    }
+
+    /// Resize the map and record the change
+    pub fn resize(&mut self, map: &mut EditingMap, width: u32, height: u32, x: i32, y: i32) {
+        self.end_cells(map);
+        let before = map.clone();
+        map.resize(width, height, x, y);
+        let command = EditCommand::Resize {
+            before: Box::new(before),
+            after: Box::new(map.clone()),
+        };
+        self.push(command, None);
+    }
+
+    pub fn undo(&mut self, map: &mut EditingMap) -> bool {
+        self.end_cells(map);
+        self.merge_key = None;
+        if let Some(command) = self.undo_stack.pop() {
+            command.undo(map);
+            self.redo_stack.push(command);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn redo(&mut self, map: &mut EditingMap) -> bool {
+        self.end_cells(map);
+        self.merge_key = None;
+        if let Some(command) = self.redo_stack.pop() {
+            command.redo(map);
+            self.undo_stack.push(command);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::objholder::TileIdx;
+
+    fn tile(i: usize) -> TileLayers {
+        TileLayers::from(TileIdx::from_usize(i))
+    }
+
+    fn set_tile(history: &mut EditHistory, map: &mut EditingMap, pos: Vec2d, i: usize) {
+        history.edit_cell(map, pos, None, |map| {
+            map.set_tile(pos, TileIdx::from_usize(i), 0)
+        });
+    }
+
+    #[test]
+    fn cells_undo_redo_test() {
+        let mut map = EditingMap::new("test", 4, 4);
+        let mut history = EditHistory::default();
+        let (p0, p1) = (Vec2d(1, 1), Vec2d(2, 3));
+
+        set_tile(&mut history, &mut map, p0, 1);
+        set_tile(&mut history, &mut map, p1, 2);
+        assert_eq!((map.tile[p0], map.tile[p1]), (tile(1), tile(2)));
+
+        assert!(history.undo(&mut map));
+        assert_eq!((map.tile[p0], map.tile[p1]), (tile(1), tile(0)));
+        assert!(history.undo(&mut map));
+        assert_eq!((map.tile[p0], map.tile[p1]), (tile(0), tile(0)));
+        assert!(!history.undo(&mut map));
+
+        assert!(history.redo(&mut map));
+        assert!(history.redo(&mut map));
+        assert_eq!((map.tile[p0], map.tile[p1]), (tile(1), tile(2)));
+        assert!(!history.redo(&mut map));
+
+        // Cells changed between begin_cells and end_cells are undone at once
+        history.begin_cells(&map);
+        map.set_tile(p0, TileIdx::from_usize(3), 0);
+        map.deco[p1] = Some(DecoIdx::from_usize(1));
+        history.end_cells(&map);
+        assert!(history.undo(&mut map));
+        assert_eq!((map.tile[p0], map.deco[p1]), (tile(1), None));
+        assert!(history.redo(&mut map));
+        assert_eq!(
+            (map.tile[p0], map.deco[p1]),
+            (tile(3), Some(DecoIdx::from_usize(1)))
+        );
+    }
+
+    #[test]
+    fn merge_test() {
+        let mut map = EditingMap::new("test", 4, 4);
+        let mut history = EditHistory::default();
+
+        // Cell edits with the same merge key
+        for (i, pos) in [Vec2d(0, 0), Vec2d(1, 0), Vec2d(0, 0)].iter().enumerate() {
+            history.edit_cell(&mut map, *pos, Some("pencil"), |map| {
+                map.set_tile(*pos, TileIdx::from_usize(i + 1), 0)
+            });
+        }
+        assert!(history.undo(&mut map));
+        assert_eq!(
+            (map.tile[Vec2d(0, 0)], map.tile[Vec2d(1, 0)]),
+            (tile(0), tile(0))
+        );
+        assert!(!history.undo(&mut map));
+
+        // Property edits with the same merge key
+        for music in &["a", "ab"] {
+            history.edit_property(&mut map, Some("music"), |property| {
+                property.music = music.to_string()
+            });
+        }
+        history.edit_property(&mut map, Some("id"), |property| property.id = "new".into());
+        assert!(history.undo(&mut map));
+        assert_eq!(
+            (map.property.id.as_str(), map.property.music.as_str()),
+            ("test", "ab")
+        );
+        assert!(history.undo(&mut map));
+        assert_eq!(map.property.music, "");
+        assert!(!history.undo(&mut map));
+
+        // Merged edits that change nothing are dropped
+        for music in &["a", ""] {
+            history.edit_property(&mut map, Some("music"), |property| {
+                property.music = music.to_string()
+            });
+        }
+        assert!(!history.undo(&mut map));
+    }
+
+    #[test]
+    fn resize_test() {
+        let mut map = EditingMap::new("test", 4, 4);
+        let mut history = EditHistory::default();
+        set_tile(&mut history, &mut map, Vec2d(3, 3), 1);
+
+        history.resize(&mut map, 2, 3, 0, 0);
+        assert_eq!((map.width, map.height), (2, 3));
+
+        assert!(history.undo(&mut map));
+        assert_eq!((map.width, map.height), (4, 4));
+        assert_eq!(map.tile[Vec2d(3, 3)], tile(1));
+
+        assert!(history.redo(&mut map));
+        assert_eq!((map.width, map.height), (2, 3));
+    }
+
+    #[test]
+    fn push_test() {
+        let mut map = EditingMap::new("test", 4, 4);
+        let mut history = EditHistory::default();
+
+        // Pushing a command clears the redo stack
+        set_tile(&mut history, &mut map, Vec2d(0, 0), 1);
+        assert!(history.undo(&mut map));
+        set_tile(&mut history, &mut map, Vec2d(1, 1), 1);
+        assert!(!history.redo(&mut map));
+
+        // Old commands are dropped over MAX_HISTORY
+        for i in 0..MAX_HISTORY {
+            set_tile(&mut history, &mut map, Vec2d(2, 2), i % 2 + 2);
+        }
+        let mut n_undo = 0;
+        while history.undo(&mut map) {
+            n_undo += 1;
+        }
+        assert_eq!(n_undo, MAX_HISTORY);
+        assert_eq!(map.tile[Vec2d(1, 1)], tile(1));
+    }
+}
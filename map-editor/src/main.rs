@@ -4,8 +4,10 @@ extern crate rusted_ruins_geom as geom;
 mod edit_map;
 #[macro_use]
 mod ui;
+mod cli;
 mod draw_map;
 mod file;
+mod history;
 mod iconview;
 mod pixbuf_holder;
 mod property_controls;
@@ -16,11 +18,7 @@ use std::path::PathBuf;
 
 pub fn main() {
     env_logger::init();
-
-    let application = gtk::Application::new(
-        Some("com.github.rusted-ruins-map-editor"),
-        gio::ApplicationFlags::empty(),
-    );
+    let matches = cli::app().get_matches();
 
     let mut app_dir = get_app_dir().expect("Could not found application directory");
     app_dir.push("paks");
@@ -31,6 +29,19 @@ pub fn main() {
     }
 
     common::gobj::init(pak_dirs);
+
+    if let Some(result) = cli::run(&matches) {
+        if let Err(e) = result {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let application = gtk::Application::new(
+        Some("com.github.rusted-ruins-map-editor"),
+        gio::ApplicationFlags::empty(),
+    );
     application.connect_startup(move |app| {
         ui::build_ui(app);
     });
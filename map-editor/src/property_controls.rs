@@ -70,6 +70,8 @@ impl PropertyControls {
         if let Some(entrance) = map.property.entrance.get(0) {
             self.entrance_pos
                 .set_text(&format!("{},{}", entrance.0, entrance.1));
+        } else {
+            self.entrance_pos.set_text("");
         }
         self.music.set_text(&map.property.music);
         match map.property.boundary.n {
@@ -142,7 +144,7 @@ pub fn connect_for_property_controls(ui: &Ui) {
     ui.property_controls.map_id.connect_changed(move |widget| {
         if uic.get_signal_mode() {
             let text = widget.text();
-            uic.map.borrow_mut().property.id = text.into();
+            uic.edit_property(Some("id"), |property| property.id = text.into());
         }
     });
 
@@ -152,7 +154,7 @@ pub fn connect_for_property_controls(ui: &Ui) {
         .connect_toggled(move |widget| {
             if uic.get_signal_mode() {
                 let mode = widget.is_active();
-                uic.map.borrow_mut().property.is_region_map = mode;
+                uic.edit_property(None, |property| property.is_region_map = mode);
                 uic.iconview.refilter(mode);
             }
         });
@@ -164,10 +166,12 @@ pub fn connect_for_property_controls(ui: &Ui) {
         .connect_changed(move |widget| {
             if uic.get_signal_mode() {
                 let text = widget.text();
-                uic.map.borrow_mut().property.entrance.clear();
-                if let Ok(entrance) = text.parse::<Vec2d>() {
-                    uic.map.borrow_mut().property.entrance.push(entrance);
-                }
+                uic.edit_property(Some("entrance"), |property| {
+                    property.entrance.clear();
+                    if let Ok(entrance) = text.parse::<Vec2d>() {
+                        property.entrance.push(entrance);
+                    }
+                });
             }
         });
 
@@ -176,7 +180,7 @@ pub fn connect_for_property_controls(ui: &Ui) {
     ui.property_controls.music.connect_changed(move |widget| {
         if uic.get_signal_mode() {
             let text = widget.text();
-            uic.map.borrow_mut().property.music = text.into();
+            uic.edit_property(Some("music"), |property| property.music = text.into());
         }
     });
 
@@ -191,7 +195,9 @@ fn connect_for_boundary_radio_bottons(ui: &Ui) {
         .connect_toggled(move |_| {
             // N
             if uic.get_signal_mode() {
-                uic.map.borrow_mut().property.boundary.n = MapTemplateBoundaryBehavior::None;
+                uic.edit_property(None, |property| {
+                    property.boundary.n = MapTemplateBoundaryBehavior::None
+                });
             }
         });
     let uic = ui.clone();
@@ -199,7 +205,9 @@ fn connect_for_boundary_radio_bottons(ui: &Ui) {
         .boundary_n_next
         .connect_toggled(move |_| {
             if uic.get_signal_mode() {
-                uic.map.borrow_mut().property.boundary.n = MapTemplateBoundaryBehavior::NextFloor
+                uic.edit_property(None, |property| {
+                    property.boundary.n = MapTemplateBoundaryBehavior::NextFloor
+                });
             }
         });
     let uic = ui.clone();
@@ -207,7 +215,9 @@ fn connect_for_boundary_radio_bottons(ui: &Ui) {
         .boundary_n_prev
         .connect_toggled(move |_| {
             if uic.get_signal_mode() {
-                uic.map.borrow_mut().property.boundary.n = MapTemplateBoundaryBehavior::PrevFloor
+                uic.edit_property(None, |property| {
+                    property.boundary.n = MapTemplateBoundaryBehavior::PrevFloor
+                });
             }
         });
     let uic = ui.clone();
@@ -215,7 +225,9 @@ fn connect_for_boundary_radio_bottons(ui: &Ui) {
         .boundary_n_region
         .connect_toggled(move |_| {
             if uic.get_signal_mode() {
-                uic.map.borrow_mut().property.boundary.n = MapTemplateBoundaryBehavior::Exit
+                uic.edit_property(None, |property| {
+                    property.boundary.n = MapTemplateBoundaryBehavior::Exit
+                });
             }
         });
     let uic = ui.clone();
@@ -224,7 +236,9 @@ fn connect_for_boundary_radio_bottons(ui: &Ui) {
         .connect_toggled(move |_| {
             // S
             if uic.get_signal_mode() {
-                uic.map.borrow_mut().property.boundary.s = MapTemplateBoundaryBehavior::None;
+                uic.edit_property(None, |property| {
+                    property.boundary.s = MapTemplateBoundaryBehavior::None
+                });
             }
         });
     let uic = ui.clone();
@@ -232,7 +246,9 @@ fn connect_for_boundary_radio_bottons(ui: &Ui) {
         .boundary_s_next
         .connect_toggled(move |_| {
             if uic.get_signal_mode() {
-                uic.map.borrow_mut().property.boundary.s = MapTemplateBoundaryBehavior::NextFloor
+                uic.edit_property(None, |property| {
+                    property.boundary.s = MapTemplateBoundaryBehavior::NextFloor
+                });
             }
         });
     let uic = ui.clone();
@@ -240,7 +256,9 @@ fn connect_for_boundary_radio_bottons(ui: &Ui) {
         .boundary_s_prev
         .connect_toggled(move |_| {
             if uic.get_signal_mode() {
-                uic.map.borrow_mut().property.boundary.s = MapTemplateBoundaryBehavior::PrevFloor
+                uic.edit_property(None, |property| {
+                    property.boundary.s = MapTemplateBoundaryBehavior::PrevFloor
+                });
             }
         });
     let uic = ui.clone();
@@ -248,7 +266,9 @@ fn connect_for_boundary_radio_bottons(ui: &Ui) {
         .boundary_s_region
         .connect_toggled(move |_| {
             if uic.get_signal_mode() {
-                uic.map.borrow_mut().property.boundary.s = MapTemplateBoundaryBehavior::Exit
+                uic.edit_property(None, |property| {
+                    property.boundary.s = MapTemplateBoundaryBehavior::Exit
+                });
             }
         });
     let uic = ui.clone();
@@ -257,7 +277,9 @@ fn connect_for_boundary_radio_bottons(ui: &Ui) {
         .connect_toggled(move |_| {
             // E
             if uic.get_signal_mode() {
-                uic.map.borrow_mut().property.boundary.e = MapTemplateBoundaryBehavior::None;
+                uic.edit_property(None, |property| {
+                    property.boundary.e = MapTemplateBoundaryBehavior::None
+                });
             }
         });
     let uic = ui.clone();
@@ -265,7 +287,9 @@ fn connect_for_boundary_radio_bottons(ui: &Ui) {
         .boundary_e_next
         .connect_toggled(move |_| {
             if uic.get_signal_mode() {
-                uic.map.borrow_mut().property.boundary.e = MapTemplateBoundaryBehavior::NextFloor
+                uic.edit_property(None, |property| {
+                    property.boundary.e = MapTemplateBoundaryBehavior::NextFloor
+                });
             }
         });
     let uic = ui.clone();
@@ -273,7 +297,9 @@ fn connect_for_boundary_radio_bottons(ui: &Ui) {
         .boundary_e_prev
         .connect_toggled(move |_| {
             if uic.get_signal_mode() {
-                uic.map.borrow_mut().property.boundary.e = MapTemplateBoundaryBehavior::PrevFloor
+                uic.edit_property(None, |property| {
+                    property.boundary.e = MapTemplateBoundaryBehavior::PrevFloor
+                });
             }
         });
     let uic = ui.clone();
@@ -281,7 +307,9 @@ fn connect_for_boundary_radio_bottons(ui: &Ui) {
         .boundary_e_region
         .connect_toggled(move |_| {
             if uic.get_signal_mode() {
-                uic.map.borrow_mut().property.boundary.e = MapTemplateBoundaryBehavior::Exit
+                uic.edit_property(None, |property| {
+                    property.boundary.e = MapTemplateBoundaryBehavior::Exit
+                });
             }
         });
     let uic = ui.clone();
@@ -290,7 +318,9 @@ fn connect_for_boundary_radio_bottons(ui: &Ui) {
         .connect_toggled(move |_| {
             // W
             if uic.get_signal_mode() {
-                uic.map.borrow_mut().property.boundary.w = MapTemplateBoundaryBehavior::None;
+                uic.edit_property(None, |property| {
+                    property.boundary.w = MapTemplateBoundaryBehavior::None
+                });
             }
         });
     let uic = ui.clone();
@@ -298,7 +328,9 @@ fn connect_for_boundary_radio_bottons(ui: &Ui) {
         .boundary_w_next
         .connect_toggled(move |_| {
             if uic.get_signal_mode() {
-                uic.map.borrow_mut().property.boundary.w = MapTemplateBoundaryBehavior::NextFloor
+                uic.edit_property(None, |property| {
+                    property.boundary.w = MapTemplateBoundaryBehavior::NextFloor
+                });
             }
         });
     let uic = ui.clone();
@@ -306,7 +338,9 @@ fn connect_for_boundary_radio_bottons(ui: &Ui) {
         .boundary_w_prev
         .connect_toggled(move |_| {
             if uic.get_signal_mode() {
-                uic.map.borrow_mut().property.boundary.w = MapTemplateBoundaryBehavior::PrevFloor
+                uic.edit_property(None, |property| {
+                    property.boundary.w = MapTemplateBoundaryBehavior::PrevFloor
+                });
             }
         });
     let uic = ui.clone();
@@ -314,7 +348,9 @@ fn connect_for_boundary_radio_bottons(ui: &Ui) {
         .boundary_w_region
         .connect_toggled(move |_| {
             if uic.get_signal_mode() {
-                uic.map.borrow_mut().property.boundary.w = MapTemplateBoundaryBehavior::Exit
+                uic.edit_property(None, |property| {
+                    property.boundary.w = MapTemplateBoundaryBehavior::Exit
+                });
             }
         });
 }
@@ -340,9 +376,13 @@ fn connect_for_tile_edit_controls(ui: &Ui) {
                         id: text.to_string(),
                     })
                 };
-                uic.map
-                    .borrow_mut()
-                    .set_item(uic.property_controls.selected_tile.get(), item_gen);
+                let pos = uic.property_controls.selected_tile.get();
+                uic.history.borrow_mut().edit_cell(
+                    &mut uic.map.borrow_mut(),
+                    pos,
+                    Some("item"),
+                    |map| map.set_item(pos, item_gen),
+                );
             }
         });
 }
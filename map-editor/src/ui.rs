@@ -1,4 +1,5 @@
-use crate::edit_map::EditingMap;
+use crate::edit_map::{EditingMap, MapProperty};
+use crate::history::EditHistory;
 use crate::iconview::IconView;
 use crate::pixbuf_holder::PixbufHolder;
 use crate::property_controls::PropertyControls;
@@ -54,6 +55,7 @@ pub struct Ui {
     pub property_controls: PropertyControls,
     pub pbh: Rc<PixbufHolder>,
     pub map: Rc<RefCell<EditingMap>>,
+    pub history: Rc<RefCell<EditHistory>>,
     pub selected_item: Rc<Cell<SelectedItem>>,
     pub drag_mode: Rc<Cell<DragMode>>,
     pub filepath: Rc<RefCell<Option<PathBuf>>>,
@@ -114,6 +116,7 @@ pub fn build_ui(application: &gtk::Application) {
         property_controls: PropertyControls::build(&builder),
         pbh: Rc::new(PixbufHolder::new()),
         map: Rc::new(RefCell::new(EditingMap::new("newmap", 16, 16))),
+        history: Rc::new(RefCell::new(EditHistory::default())),
         selected_item: Rc::new(Cell::new(SelectedItem::Tile(TileIdx::default()))),
         drag_mode: Rc::new(Cell::new(DragMode::None)),
         filepath: Rc::new(RefCell::new(None)),
@@ -132,6 +135,8 @@ pub fn build_ui(application: &gtk::Application) {
     let menu_save: gtk::MenuItem = get_object!(builder, "menu-save");
     let menu_save_as: gtk::MenuItem = get_object!(builder, "menu-save-as");
     let menu_quit: gtk::MenuItem = get_object!(builder, "menu-quit");
+    let menu_undo: gtk::MenuItem = get_object!(builder, "menu-undo");
+    let menu_redo: gtk::MenuItem = get_object!(builder, "menu-redo");
     let menu_resize: gtk::MenuItem = get_object!(builder, "menu-resize");
 
     ui.window.set_application(Some(application));
@@ -228,6 +233,7 @@ pub fn build_ui(application: &gtk::Application) {
                 };
                 let new_map = EditingMap::new(&new_map_id, width, height);
                 *uic.map.borrow_mut() = new_map;
+                uic.history.borrow_mut().clear();
                 uic.set_signal_mode(false);
                 uic.property_controls.update(&*uic.map.borrow());
                 uic.set_signal_mode(true);
@@ -245,6 +251,7 @@ pub fn build_ui(application: &gtk::Application) {
                     Ok(mapobj) => {
                         {
                             *uic.map.borrow_mut() = EditingMap::from(mapobj);
+                            uic.history.borrow_mut().clear();
                         }
                         uic.set_signal_mode(false);
                         uic.property_controls.update(&*uic.map.borrow());
@@ -303,6 +310,20 @@ pub fn build_ui(application: &gtk::Application) {
             uic.window.close();
         });
     }
+    {
+        // Menu (undo)
+        let uic = ui.clone();
+        menu_undo.connect_activate(move |_| {
+            undo(&uic);
+        });
+    }
+    {
+        // Menu (redo)
+        let uic = ui.clone();
+        menu_redo.connect_activate(move |_| {
+            redo(&uic);
+        });
+    }
     {
         // Menu (resize)
         let uic = ui.clone();
@@ -323,9 +344,13 @@ pub fn build_ui(application: &gtk::Application) {
                 uic.adjustment_map_pos_y.set_value(0.0);
                 uic.adjustment_map_pos_x.set_upper(width as f64);
                 uic.adjustment_map_pos_y.set_upper(height as f64);
-                uic.map
-                    .borrow_mut()
-                    .resize(width, height, offset_x, offset_y);
+                uic.history.borrow_mut().resize(
+                    &mut uic.map.borrow_mut(),
+                    width,
+                    height,
+                    offset_x,
+                    offset_y,
+                );
                 uic.map_redraw();
             }
         });
@@ -346,13 +371,24 @@ pub fn build_ui(application: &gtk::Application) {
     }
     {
         // Key press
-        use gdk::keys::constants::{Shift_L, Shift_R};
+        use gdk::keys::constants::{y, z, Shift_L, Shift_R, Y, Z};
         let uic = ui.clone();
         ui.window.connect_key_press_event(move |_, event_key| {
             let keyval = event_key.keyval();
             if keyval == Shift_L || keyval == Shift_R {
                 uic.shift.set(true);
             }
+            let state = event_key.state();
+            if state.contains(gdk::ModifierType::CONTROL_MASK) {
+                let shift = state.contains(gdk::ModifierType::SHIFT_MASK);
+                if (keyval == z || keyval == Z) && !shift {
+                    undo(&uic);
+                    return Inhibit(true);
+                } else if keyval == y || keyval == Y || keyval == z || keyval == Z {
+                    redo(&uic);
+                    return Inhibit(true);
+                }
+            }
             Inhibit(false)
         });
         let uic = ui.clone();
@@ -440,6 +476,9 @@ pub fn build_ui(application: &gtk::Application) {
 
 fn on_map_clicked(ui: &Ui, eb: &gdk::EventButton) {
     let button = eb.button();
+    if button == WRITE_BUTTON || button == ERASE_BUTTON {
+        ui.history.borrow_mut().begin_cells(&ui.map.borrow());
+    }
     if button == WRITE_BUTTON {
         ui.drag_start
             .set(Some(Vec2d::from(ui.cursor_to_tile_pos(eb.position()))));
@@ -468,6 +507,8 @@ fn on_button_released(ui: &Ui, eb: &gdk::EventButton) {
             try_write_rect(ui, start, end);
         }
     }
+    // A drag or a rectangle fill is recorded as one edit
+    ui.history.borrow_mut().end_cells(&ui.map.borrow());
 }
 
 fn on_motion(ui: &Ui, em: &gdk::EventMotion, w: i32, h: i32) {
@@ -641,6 +682,20 @@ fn try_erase(ui: &Ui, pos: (f64, f64)) {
     ui.map_redraw();
 }
 
+fn undo(ui: &Ui) {
+    let done = ui.history.borrow_mut().undo(&mut ui.map.borrow_mut());
+    if done {
+        ui.after_history_change();
+    }
+}
+
+fn redo(ui: &Ui) {
+    let done = ui.history.borrow_mut().redo(&mut ui.map.borrow_mut());
+    if done {
+        ui.after_history_change();
+    }
+}
+
 fn centering_to(ui: &Ui, pos: (i32, i32)) {
     let area_w = ui.map_drawing_area.allocated_width();
     let area_h = ui.map_drawing_area.allocated_height();
@@ -682,6 +737,35 @@ impl Ui {
         self.adjustment_map_pos_y.set_upper(height as f64);
     }
 
+    pub fn edit_property<F: FnOnce(&mut MapProperty)>(
+        &self,
+        merge_key: Option<&'static str>,
+        f: F,
+    ) {
+        self.history
+            .borrow_mut()
+            .edit_property(&mut self.map.borrow_mut(), merge_key, f);
+    }
+
+    /// Update widgets for the map changed by undo or redo
+    fn after_history_change(&self) {
+        let (width, height) = {
+            let map = self.map.borrow();
+            (map.width, map.height)
+        };
+        if self.adjustment_map_pos_x.upper() as u32 != width
+            || self.adjustment_map_pos_y.upper() as u32 != height
+        {
+            self.reset_map_size(width, height);
+        }
+        self.set_signal_mode(false);
+        self.property_controls.update(&self.map.borrow());
+        self.iconview
+            .refilter(self.map.borrow().property.is_region_map);
+        self.set_signal_mode(true);
+        self.map_redraw();
+    }
+
     pub fn set_signal_mode(&self, mode: bool) {
         self.signal_mode.set(mode);
     }
@@ -7,6 +7,7 @@ pub use rand::Rng;
 use rand::RngCore;
 use rand::SeedableRng;
 use rand_xorshift::XorShiftRng;
+use serde_derive::{Deserialize, Serialize};
 use std::cell::RefCell;
 
 #[derive(Debug, Clone, Copy)]
@@ -53,6 +54,23 @@ pub fn reseed(fixed: bool) {
     })
 }
 
+/// Snapshot of the internal state of the thread local RNG
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RngState(XorShiftRng);
+
+/// Get the current state of the thread local RNG
+pub fn state() -> RngState {
+    XORSHIFT_RNG.with(|xorshift_rng| RngState(xorshift_rng.borrow().clone()))
+}
+
+/// Restore the thread local RNG from a saved state
+pub fn set_state(state: RngState) {
+    XORSHIFT_RNG.with(|xorshift_rng| {
+        xorshift_rng.replace(state.0);
+    })
+}
+
 pub fn next_u32() -> u32 {
     let mut rng = GameRng;
     rng.next_u32()
@@ -125,4 +143,14 @@ mod tests {
         let average = sum / N as f64;
         println!("average is {}", average);
     }
+
+    #[test]
+    fn restore_state() {
+        reseed(false);
+        let state = state();
+        let a: Vec<u32> = (0..10).map(|_| next_u32()).collect();
+        set_state(state);
+        let b: Vec<u32> = (0..10).map(|_| next_u32()).collect();
+        assert_eq!(a, b);
+    }
 }
@@ -1,3 +1,4 @@
+use crate::RuleError;
 use common::gamedata::*;
 use std::collections::HashMap;
 use std::fs;
@@ -18,10 +19,11 @@ impl ActiveSkills {
         self.0.get(id)
     }
 
-    pub fn join_from_dir(&mut self, dir: &Path) -> Result<(), std::io::Error> {
-        for entry in fs::read_dir(dir)? {
-            let entry = entry?;
-            let file_type = entry.file_type()?;
+    pub fn join_from_dir(&mut self, dir: &Path) -> Result<(), RuleError> {
+        let io_err = |e| RuleError::io(dir, e);
+        for entry in fs::read_dir(dir).map_err(io_err)? {
+            let entry = entry.map_err(io_err)?;
+            let file_type = entry.file_type().map_err(io_err)?;
             if !file_type.is_file() {
                 continue;
             }
@@ -35,7 +37,7 @@ impl ActiveSkills {
                 continue;
             }
 
-            let active_skills = super::read_from_file(path);
+            let active_skills = super::read_from_file(path)?;
 
             self.join(active_skills);
         }
@@ -10,6 +10,15 @@ pub struct Chara {
     pub hp_regeneration_probability: f32,
     /// Multiplying factor of HP regeneration
     pub hp_regeneration_factor: f32,
+    /// (max MP) = ((INT) + (WIL) / 2) * (max_mp_factor)
+    #[serde(default = "default_max_mp_factor")]
+    pub max_mp_factor: f32,
+    /// The probability of MP regeneration per turn.
+    #[serde(default = "default_mp_regeneration_probability")]
+    pub mp_regeneration_probability: f32,
+    /// Multiplying factor of MP regeneration
+    #[serde(default = "default_mp_regeneration_factor")]
+    pub mp_regeneration_factor: f32,
     /// Default sp when a new character is created.
     pub sp_default: f32,
     /// Maximum sp
@@ -33,3 +42,15 @@ pub struct Chara {
     pub carrying_capacity_threshold_strained: f32,
     pub carrying_capacity_threshold_overloaded: f32,
 }
+
+fn default_max_mp_factor() -> f32 {
+    1.0
+}
+
+fn default_mp_regeneration_probability() -> f32 {
+    0.2
+}
+
+fn default_mp_regeneration_factor() -> f32 {
+    0.1
+}
@@ -14,6 +14,10 @@ impl Classes {
             .get(&chara_class)
             .unwrap_or_else(|| &self.0[&CharaClass::default()])
     }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&CharaClass, &Class)> {
+        self.0.iter()
+    }
 }
 
 /// Rules for character generation
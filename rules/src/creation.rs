@@ -1,3 +1,4 @@
+use crate::RuleError;
 use common::gamedata::{CreationKind, CreationRequiredTime, Recipe};
 use std::collections::HashMap;
 use std::fs;
@@ -8,6 +9,15 @@ pub struct Creation {
     pub required_time: HashMap<CreationRequiredTime, u16>,
     pub recipe_learning_item_initial: i32,
     pub recipe_learning_item_factor: i32,
+    /// Required turns to build walls, tiles and decos
+    #[serde(default = "default_build_turn")]
+    pub build_turn: u16,
+    /// Required turns to deconstruct walls and decos
+    #[serde(default = "default_deconstruct_turn")]
+    pub deconstruct_turn: u16,
+    /// The ratio of materials refunded by deconstruction
+    #[serde(default = "default_deconstruct_refund_rate")]
+    pub deconstruct_refund_rate: f32,
     #[serde(default)]
     art_recipes: Vec<Recipe>,
     #[serde(default)]
@@ -20,6 +30,21 @@ pub struct Creation {
     pharmacy_recipes: Vec<Recipe>,
     #[serde(default)]
     smith_recipes: Vec<Recipe>,
+    /// Recipes for creation kinds defined by rules
+    #[serde(default)]
+    other_recipes: HashMap<CreationKind, Vec<Recipe>>,
+}
+
+fn default_build_turn() -> u16 {
+    10
+}
+
+fn default_deconstruct_turn() -> u16 {
+    5
+}
+
+fn default_deconstruct_refund_rate() -> f32 {
+    0.5
 }
 
 #[derive(Serialize, Deserialize)]
@@ -36,6 +61,9 @@ pub struct Recipes {
     pharmacy_recipes: Vec<Recipe>,
     #[serde(default)]
     smith_recipes: Vec<Recipe>,
+    /// Recipes for creation kinds defined by rules
+    #[serde(default)]
+    other_recipes: HashMap<CreationKind, Vec<Recipe>>,
 }
 
 impl Creation {
@@ -51,6 +79,12 @@ impl Creation {
         self.craft_recipes.append(&mut recipes.craft_recipes);
         self.pharmacy_recipes.append(&mut recipes.pharmacy_recipes);
         self.smith_recipes.append(&mut recipes.smith_recipes);
+        for (kind, mut recipes) in recipes.other_recipes {
+            self.other_recipes
+                .entry(kind)
+                .or_default()
+                .append(&mut recipes);
+        }
     }
 
     pub fn recipes(&self, kind: CreationKind) -> &[Recipe] {
@@ -61,13 +95,19 @@ impl Creation {
             CreationKind::Craft => self.craft_recipes.as_ref(),
             CreationKind::Pharmacy => self.pharmacy_recipes.as_ref(),
             CreationKind::Smith => self.smith_recipes.as_ref(),
+            _ => self
+                .other_recipes
+                .get(&kind)
+                .map(|recipes| recipes.as_ref())
+                .unwrap_or_default(),
         }
     }
 
-    pub fn join_from_dir(&mut self, dir: &Path) -> Result<(), std::io::Error> {
-        for entry in fs::read_dir(dir)? {
-            let entry = entry?;
-            let file_type = entry.file_type()?;
+    pub fn join_from_dir(&mut self, dir: &Path) -> Result<(), RuleError> {
+        let io_err = |e| RuleError::io(dir, e);
+        for entry in fs::read_dir(dir).map_err(io_err)? {
+            let entry = entry.map_err(io_err)?;
+            let file_type = entry.file_type().map_err(io_err)?;
             if !file_type.is_file() {
                 continue;
             }
@@ -81,7 +121,7 @@ impl Creation {
                 continue;
             }
 
-            let recipes: Recipes = super::read_from_file(&path);
+            let recipes: Recipes = super::read_from_file(&path)?;
             self.join(recipes);
         }
         Ok(())
@@ -0,0 +1,37 @@
+use common::gamedata::SkillDefError;
+use std::path::Path;
+use thiserror::Error;
+
+/// Errors in loading rule files
+#[derive(Debug, Error)]
+pub enum RuleError {
+    #[error("{path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("{path}: {msg}")]
+    Parse { path: String, msg: String },
+    #[error("rule file \"{0}\" not found")]
+    NotFound(String),
+    #[error("rule file \"{name}\" is not found for \"{patch}\"")]
+    NoBaseForPatch { name: String, patch: String },
+    #[error(transparent)]
+    Skill(#[from] SkillDefError),
+}
+
+impl RuleError {
+    pub(crate) fn io(path: &Path, source: std::io::Error) -> RuleError {
+        RuleError::Io {
+            path: path.to_string_lossy().into(),
+            source,
+        }
+    }
+
+    pub(crate) fn parse<E: ToString>(path: &Path, e: E) -> RuleError {
+        RuleError::Parse {
+            path: path.to_string_lossy().into(),
+            msg: e.to_string(),
+        }
+    }
+}
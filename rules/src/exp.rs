@@ -21,6 +21,16 @@ pub struct Exp {
     pub healing_probability: f32,
     /// Base exp to Mining skill
     pub mining: u32,
+    /// Base exp to Negotiation skill after trading
+    #[serde(default)]
+    pub negotiation: u32,
     /// Base exp for creation
     pub creation_base_exp: u32,
+    /// Ratio of attack exp shared with other party members on the same map
+    #[serde(default = "default_party_share")]
+    pub party_share: f32,
+}
+
+fn default_party_share() -> f32 {
+    0.5
 }
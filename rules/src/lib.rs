@@ -15,12 +15,14 @@ pub mod combat;
 pub mod creation;
 pub mod dungeon_gen;
 pub mod effect;
+mod error;
 pub mod exp;
 pub mod faction;
 pub mod item;
 pub mod magic;
 pub mod map_gen;
 pub mod material;
+pub mod merge;
 pub mod newgame;
 pub mod npc;
 pub mod npc_ai;
@@ -28,8 +30,13 @@ pub mod params;
 pub mod quest;
 pub mod race;
 pub mod town;
+mod validate;
 pub mod world;
 
+pub use error::RuleError;
+pub use validate::RuleRefError;
+
+use common::gamedata::SkillDef;
 use once_cell::sync::Lazy;
 use serde::de::Deserialize;
 use std::fs;
@@ -38,6 +45,7 @@ use std::sync::Mutex;
 
 const ACTIVE_SKILL_DIR_NAME: &str = "active_skill";
 const RECIPE_DIR_NAME: &str = "recipe";
+const SKILL_FILE_NAME: &str = "skill.ron";
 
 /// Contain game rules
 pub struct Rules {
@@ -64,16 +72,20 @@ pub struct Rules {
     pub race: race::Races,
     pub town: town::Town,
     pub world: world::World,
+    /// Object ids not found while loading
+    unknown_ids: Vec<RuleRefError>,
 }
 
 impl Rules {
-    fn load_from_dir(rules_dir: &Path, addon_dir: Option<&Path>) -> Rules {
+    /// Load rules from the rules directory and addon directories
+    pub fn load_from_dir(rules_dir: &Path, addon_dir: Option<&Path>) -> Result<Rules, RuleError> {
         let mut dirs: Vec<PathBuf> = vec![rules_dir.into()];
 
-        // Reading addon dirs
+        // Reading addon dirs. Addons are applied in the order of their directory names.
         if let Some(addon_dir) = addon_dir {
             fn read_addon_dir(dirs: &mut Vec<PathBuf>, addon_dir: &Path) -> std::io::Result<()> {
                 let addon_dir = std::fs::read_dir(addon_dir)?;
+                let mut rule_dirs = Vec::new();
                 for d in addon_dir {
                     let d = d?;
                     if !d.file_type()?.is_dir() {
@@ -82,148 +94,195 @@ impl Rules {
                     let mut rule_dir = d.path();
                     rule_dir.push("rules");
                     if rule_dir.exists() && rule_dir.is_dir() {
-                        dirs.push(rule_dir);
+                        rule_dirs.push(rule_dir);
                     }
                 }
+                rule_dirs.sort();
+                dirs.append(&mut rule_dirs);
                 Ok(())
             }
-            if let Some(e) = read_addon_dir(&mut dirs, addon_dir).err() {
-                error!("error occurred during reading rule dir: \"{}\"", e);
-                exit_err();
+            read_addon_dir(&mut dirs, addon_dir).map_err(|e| RuleError::io(addon_dir, e))?;
+        }
+
+        // Skills must be defined before reading other rules that refer skill ids
+        let mut skills = Vec::new();
+        for dir in dirs.iter() {
+            let skill_file = dir.join(SKILL_FILE_NAME);
+            if skill_file.exists() {
+                let mut defs: Vec<SkillDef> = read_from_file(&skill_file)?;
+                skills.append(&mut defs);
             }
         }
+        common::gamedata::set_skill_defs(skills)?;
 
         let mut active_skills = active_skill::ActiveSkills::default();
-        let mut creation: creation::Creation = read_from_dirs(&dirs, "creation.ron");
+        let mut creation: creation::Creation = read_from_dirs(&dirs, "creation.ron")?;
 
         for dir in dirs.iter() {
             let active_skill_dir = dir.join(ACTIVE_SKILL_DIR_NAME);
             if active_skill_dir.exists() {
-                if let Err(e) = active_skills.join_from_dir(&active_skill_dir) {
-                    warn!(
-                        "active skill loading error at {}\n{}",
-                        active_skill_dir.to_string_lossy(),
-                        e
-                    );
-                }
+                active_skills.join_from_dir(&active_skill_dir)?;
             }
             let recipe_dir = dir.join(RECIPE_DIR_NAME);
             if recipe_dir.exists() {
-                if let Err(e) = creation.join_from_dir(&recipe_dir) {
-                    warn!(
-                        "recipe loading error at {}\n{}",
-                        recipe_dir.to_string_lossy(),
-                        e
-                    );
-                }
+                creation.join_from_dir(&recipe_dir)?;
             }
         }
 
         creation.sort();
 
-        Rules {
+        // Unknown object ids in biome.ron are replaced by default objects while deserializing
+        common::gobj::take_unknown_ids();
+        let biome = read_from_dirs(&dirs, "biome.ron")?;
+        let unknown_ids = common::gobj::take_unknown_ids()
+            .into_iter()
+            .map(|(id, kind)| {
+                RuleRefError::new(
+                    "biome.ron",
+                    "biomes",
+                    format!("unknown {} \"{}\"", kind, id),
+                )
+            })
+            .collect();
+
+        Ok(Rules {
             active_skills,
-            biome: read_from_dirs(&dirs, "biome.ron"),
-            chara: read_from_dirs(&dirs, "chara.ron"),
-            chara_gen: read_from_dirs(&dirs, "charagen.ron"),
-            class: read_from_dirs(&dirs, "class.ron"),
+            biome,
+            chara: read_from_dirs(&dirs, "chara.ron")?,
+            chara_gen: read_from_dirs(&dirs, "charagen.ron")?,
+            class: read_from_dirs(&dirs, "class.ron")?,
             creation,
-            combat: read_from_dirs(&dirs, "combat.ron"),
-            dungeon_gen: read_from_dirs(&dirs, "dungeon_gen.ron"),
-            effect: read_from_dirs(&dirs, "effect.ron"),
-            exp: read_from_dirs(&dirs, "exp.ron"),
-            faction: read_from_dirs(&dirs, "faction.ron"),
-            map_gen: read_from_dirs(&dirs, "map_gen.ron"),
-            item: read_from_dirs(&dirs, "item.ron"),
-            magic: read_from_dirs(&dirs, "magic.ron"),
-            material: read_from_dirs(&dirs, "material.ron"),
-            newgame: read_from_dirs(&dirs, "newgame.ron"),
-            npc: read_from_dirs(&dirs, "npc.ron"),
-            npc_ai: read_from_dirs(&dirs, "npc_ai.ron"),
-            params: read_from_dirs(&dirs, "params.ron"),
-            quest: read_from_dirs(&dirs, "quest.ron"),
-            race: read_from_dirs(&dirs, "race.ron"),
-            town: read_from_dirs(&dirs, "town.ron"),
-            world: read_from_dirs(&dirs, "world.ron"),
-        }
+            combat: read_from_dirs(&dirs, "combat.ron")?,
+            dungeon_gen: read_from_dirs(&dirs, "dungeon_gen.ron")?,
+            effect: read_from_dirs(&dirs, "effect.ron")?,
+            exp: read_from_dirs(&dirs, "exp.ron")?,
+            faction: read_from_dirs(&dirs, "faction.ron")?,
+            map_gen: read_from_dirs(&dirs, "map_gen.ron")?,
+            item: read_from_dirs(&dirs, "item.ron")?,
+            magic: read_from_dirs(&dirs, "magic.ron")?,
+            material: read_from_dirs(&dirs, "material.ron")?,
+            newgame: read_from_dirs(&dirs, "newgame.ron")?,
+            npc: read_from_dirs(&dirs, "npc.ron")?,
+            npc_ai: read_from_dirs(&dirs, "npc_ai.ron")?,
+            params: read_from_dirs(&dirs, "params.ron")?,
+            quest: read_from_dirs(&dirs, "quest.ron")?,
+            race: read_from_dirs(&dirs, "race.ron")?,
+            town: read_from_dirs(&dirs, "town.ron")?,
+            world: read_from_dirs(&dirs, "world.ron")?,
+            unknown_ids,
+        })
+    }
+
+    /// Check references to objects and other rules, and returns all broken references
+    pub fn validate(&self) -> Vec<RuleRefError> {
+        let mut errors = self.unknown_ids.clone();
+        errors.append(&mut validate::validate(self));
+        errors
     }
 }
 
-fn read_from_dirs<T, P>(dirs: &[P], name: &str) -> T
+/// Read a rule file from dirs. A file in a later dir replaces the previous one,
+/// and patch files are merged into the previous one (see `merge`).
+fn read_from_dirs<T, P>(dirs: &[P], name: &str) -> Result<T, RuleError>
 where
     T: for<'de> Deserialize<'de>,
     P: AsRef<Path>,
 {
+    let patch_name = format!("{}.patch.ron", name.trim_end_matches(".ron"));
     let mut rule: Option<T> = None;
+    // The source of the rule and patched files
+    let mut patched: Option<(merge::RonFile, Vec<PathBuf>)> = None;
+    let mut last_path: Option<PathBuf> = None;
 
     for dir in dirs.iter() {
         let dir = dir.as_ref();
         let file_path = dir.join(name);
-        let file = match fs::File::open(&file_path) {
-            Ok(o) => o,
-            Err(_) => {
-                continue;
-            }
-        };
-        info!("Rule file loading: \"{}\"", file_path.to_string_lossy());
-        match ron::de::from_reader(file) {
-            Ok(o) => {
-                rule = Some(o);
-            }
-            Err(e) => {
-                error!("{}", e);
-                exit_err();
-            }
+        if file_path.exists() {
+            rule = Some(read_from_file(&file_path)?);
+            patched = None;
+            last_path = Some(file_path);
         }
+
+        let patch_path = dir.join(&patch_name);
+        if !patch_path.exists() {
+            continue;
+        }
+        info!("Rule patch loading: \"{}\"", patch_path.to_string_lossy());
+        let (ron_file, patch_paths) = if let Some(patched) = patched.as_mut() {
+            patched
+        } else if let Some(last_path) = last_path.as_ref() {
+            patched.insert((read_ron_file(last_path)?, vec![last_path.clone()]))
+        } else {
+            return Err(RuleError::NoBaseForPatch {
+                name: name.into(),
+                patch: patch_path.to_string_lossy().into(),
+            });
+        };
+        ron_file.merge(read_ron_file(&patch_path)?);
+        patch_paths.push(patch_path);
     }
-    if let Some(rule) = rule {
-        rule
-    } else {
-        error!("rule file \"{}\" not found", name);
-        exit_err()
+
+    if let Some((ron_file, paths)) = patched {
+        let paths: Vec<_> = paths.iter().map(|p| p.to_string_lossy()).collect();
+        let o = ron::de::from_str(&ron_file.to_string()).map_err(|e| RuleError::Parse {
+            path: paths.join(" + "),
+            msg: e.to_string(),
+        })?;
+        rule = Some(o);
     }
+
+    rule.ok_or_else(|| RuleError::NotFound(name.into()))
+}
+
+fn read_ron_file(path: &Path) -> Result<merge::RonFile, RuleError> {
+    let s = fs::read_to_string(path).map_err(|e| RuleError::io(path, e))?;
+    merge::RonFile::parse(&s).map_err(|e| RuleError::parse(path, e))
 }
 
-fn read_from_file<T, P>(path: P) -> T
+fn read_from_file<T, P>(path: P) -> Result<T, RuleError>
 where
     T: for<'de> Deserialize<'de>,
     P: AsRef<Path>,
 {
-    info!("Rule file loading: \"{}\"", path.as_ref().to_string_lossy());
-    let file = match fs::File::open(path) {
-        Ok(o) => o,
-        Err(e) => {
-            error!("{}", e);
-            exit_err();
-        }
-    };
-    match ron::de::from_reader(file) {
-        Ok(o) => o,
-        Err(e) => {
-            error!("{}", e);
-            exit_err()
-        }
-    }
-}
-
-fn exit_err() -> ! {
-    std::process::exit(1)
+    let path = path.as_ref();
+    info!("Rule file loading: \"{}\"", path.to_string_lossy());
+    let file = fs::File::open(path).map_err(|e| RuleError::io(path, e))?;
+    ron::de::from_reader(file).map_err(|e| RuleError::parse(path, e))
 }
 
 static RULES_DIR: Lazy<Mutex<Option<PathBuf>>> = Lazy::new(|| Mutex::new(None));
 static ADDON_RULES_DIR: Lazy<Mutex<Option<PathBuf>>> = Lazy::new(|| Mutex::new(None));
 /// Global state rules holder
 pub static RULES: Lazy<Rules> = Lazy::new(|| {
-    Rules::load_from_dir(
+    let rules = Rules::load_from_dir(
         RULES_DIR.lock().unwrap().as_ref().unwrap(),
         ADDON_RULES_DIR
             .lock()
             .unwrap()
             .as_ref()
             .map(|path| path.as_ref()),
-    )
+    );
+    match rules {
+        Ok(rules) => rules,
+        Err(e) => {
+            error!("{}", e);
+            std::process::exit(1)
+        }
+    }
 });
 
+/// Load rules without setting the global state, and returns broken references
+pub fn check<P: AsRef<Path>>(
+    app_dir: P,
+    addon_dir: Option<P>,
+) -> Result<Vec<RuleRefError>, RuleError> {
+    let rules = Rules::load_from_dir(
+        &app_dir.as_ref().join("rules"),
+        addon_dir.as_ref().map(|path| path.as_ref()),
+    )?;
+    Ok(rules.validate())
+}
+
 /// Initialize Rules
 pub fn init<P: AsRef<Path>>(app_dirs: P, addon_dir: Option<P>) {
     *RULES_DIR.lock().unwrap() = Some(app_dirs.as_ref().join("rules"));
@@ -233,4 +292,74 @@ pub fn init<P: AsRef<Path>>(app_dirs: P, addon_dir: Option<P>) {
     }
 
     Lazy::force(&RULES);
+
+    for e in RULES.validate() {
+        warn!("{}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(PartialEq, Debug, Deserialize)]
+    struct TestRule {
+        n: u32,
+        #[serde(default)]
+        list: Vec<u32>,
+    }
+
+    fn write_rule(dir: &Path, name: &str, s: &str) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join(name), s).unwrap();
+    }
+
+    #[test]
+    fn read_from_dirs_test() {
+        let root =
+            std::env::temp_dir().join(format!("rusted-ruins-rules-test-{}", std::process::id()));
+        let base = root.join("base");
+        let addon_a = root.join("addon_a");
+        let addon_b = root.join("addon_b");
+        let dirs = [&base, &addon_a, &addon_b];
+
+        // Patches in later addons are merged later
+        write_rule(&base, "a.ron", "(n: 1, list: [1])");
+        write_rule(&addon_a, "a.patch.ron", "(n: 2, list: Append(2))");
+        write_rule(&addon_b, "a.patch.ron", "(n: 3)");
+        let rule: TestRule = read_from_dirs(&dirs, "a.ron").unwrap();
+        assert_eq!(
+            rule,
+            TestRule {
+                n: 3,
+                list: vec![1, 2]
+            }
+        );
+
+        // A rule file in a later addon replaces the patched rule
+        write_rule(&base, "b.ron", "(n: 1, list: [1])");
+        write_rule(&addon_a, "b.patch.ron", "(list: Append(2))");
+        write_rule(&addon_b, "b.ron", "(n: 4)");
+        let rule: TestRule = read_from_dirs(&dirs, "b.ron").unwrap();
+        assert_eq!(rule, TestRule { n: 4, list: vec![] });
+
+        // A patch is merged into the rule file in an earlier addon
+        write_rule(&addon_a, "c.ron", "(n: 5, list: [5])");
+        write_rule(&addon_b, "c.patch.ron", "(list: Remove)");
+        let rule: TestRule = read_from_dirs(&dirs, "c.ron").unwrap();
+        assert_eq!(rule, TestRule { n: 5, list: vec![] });
+
+        // A patch without the rule file
+        write_rule(&addon_b, "d.patch.ron", "(n: 6)");
+        let result: Result<TestRule, _> = read_from_dirs(&dirs, "d.ron");
+        assert!(matches!(
+            result,
+            Err(RuleError::NoBaseForPatch { ref name, .. }) if name == "d.ron"
+        ));
+
+        let result: Result<TestRule, _> = read_from_dirs(&dirs, "e.ron");
+        assert!(matches!(result, Err(RuleError::NotFound(_))));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
 }
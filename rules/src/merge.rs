@@ -0,0 +1,549 @@
+//! Merging patch files into rule files.
+//!
+//! Rule directories are read in order: the base rules directory first, then addon
+//! directories sorted by their names. In each directory, `<name>.ron` replaces the rule
+//! loaded so far, and `<name>.patch.ron` is merged into it. So addons can modify a part
+//! of a rule file without copying it, and later addons take precedence.
+//!
+//! A patch file is written in the same syntax as the rule file, and merged as below.
+//!
+//! * Struct fields and map entries are merged recursively. Fields and entries not in the
+//!   patch are kept, and new map entries are added.
+//! * Tuples and enum variants with the same name and length are merged element by element.
+//! * Other values, such as numbers, strings, lists and enum variants with other names,
+//!   override the current values.
+//! * `Remove` as a field or an entry value removes the field or the entry.
+//!   Removed fields get default values if the rule has them.
+//! * `Replace(value)` overrides the current value without merging.
+//! * `Append([..])` appends the items to the current list.
+//!
+//! ```text
+//! (
+//!     biomes: {
+//!         "swamp": (tile: "swamp", wall: "tree"), // Added, or merged if exists
+//!         "desert": Remove,
+//!     },
+//!     dungeon_biomes: Append(["swamp"]),
+//! )
+//! ```
+
+use std::fmt;
+
+const REMOVE: &str = "Remove";
+const REPLACE: &str = "Replace";
+const APPEND: &str = "Append";
+
+/// Parsed RON file that keeps enum variant names and struct names
+#[derive(Clone, PartialEq, Debug)]
+pub struct RonFile {
+    /// Extension attributes such as `#![enable(implicit_some)]`
+    attrs: Vec<String>,
+    value: Value,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Value {
+    /// Numbers, strings, chars and unit variants as written in the source
+    Atom(String),
+    Struct(Option<String>, Vec<(String, Value)>),
+    Tuple(Option<String>, Vec<Value>),
+    List(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+}
+
+impl RonFile {
+    pub fn parse(src: &str) -> Result<RonFile, String> {
+        let mut parser = Parser { src, pos: 0 };
+        let mut attrs = Vec::new();
+        parser.skip_ws()?;
+        while parser.src[parser.pos..].starts_with("#!") {
+            let start = parser.pos;
+            while parser.next() != Some(b']') {
+                if parser.peek().is_none() {
+                    return Err(parser.error("unclosed attribute"));
+                }
+            }
+            attrs.push(parser.src[start..parser.pos].to_owned());
+            parser.skip_ws()?;
+        }
+        let value = parser.value()?;
+        parser.skip_ws()?;
+        if parser.peek().is_some() {
+            return Err(parser.error("trailing characters"));
+        }
+        Ok(RonFile { attrs, value })
+    }
+
+    /// Merge a patch into this file
+    pub fn merge(&mut self, patch: RonFile) {
+        for attr in patch.attrs {
+            if !self.attrs.contains(&attr) {
+                self.attrs.push(attr);
+            }
+        }
+        merge_value(&mut self.value, patch.value);
+    }
+}
+
+impl fmt::Display for RonFile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for attr in &self.attrs {
+            writeln!(f, "{}", attr)?;
+        }
+        write!(f, "{}", self.value)
+    }
+}
+
+fn merge_value(base: &mut Value, patch: Value) {
+    match (base, patch) {
+        (base, Value::Tuple(Some(name), mut items)) if name == REPLACE && items.len() == 1 => {
+            *base = resolve(items.remove(0));
+        }
+        (Value::List(base_items), Value::Tuple(Some(name), mut items))
+            if name == APPEND && items.len() == 1 =>
+        {
+            match resolve(items.remove(0)) {
+                Value::List(items) => base_items.extend(items),
+                item => base_items.push(item),
+            }
+        }
+        (Value::Struct(base_name, base_fields), Value::Struct(name, fields))
+            if name.is_none() || name == *base_name =>
+        {
+            for (key, value) in fields {
+                let i = base_fields.iter().position(|(k, _)| *k == key);
+                match (i, value) {
+                    (Some(i), value) if is_remove(&value) => {
+                        base_fields.remove(i);
+                    }
+                    (None, value) if is_remove(&value) => (),
+                    (Some(i), value) => merge_value(&mut base_fields[i].1, value),
+                    (None, value) => base_fields.push((key, resolve(value))),
+                }
+            }
+        }
+        (Value::Tuple(base_name, base_items), Value::Tuple(name, items))
+            if name == *base_name && items.len() == base_items.len() =>
+        {
+            for (base_item, item) in base_items.iter_mut().zip(items) {
+                merge_value(base_item, item);
+            }
+        }
+        (Value::Map(base_entries), Value::Map(entries)) => {
+            for (key, value) in entries {
+                let i = base_entries.iter().position(|(k, _)| *k == key);
+                match (i, value) {
+                    (Some(i), value) if is_remove(&value) => {
+                        base_entries.remove(i);
+                    }
+                    (None, value) if is_remove(&value) => (),
+                    (Some(i), value) => merge_value(&mut base_entries[i].1, value),
+                    (None, value) => base_entries.push((key, resolve(value))),
+                }
+            }
+        }
+        (base, patch) => {
+            *base = resolve(patch);
+        }
+    }
+}
+
+/// Remove patch directives from a value that has nothing to be merged into
+fn resolve(value: Value) -> Value {
+    match value {
+        Value::Tuple(Some(name), mut items)
+            if (name == REPLACE || name == APPEND) && items.len() == 1 =>
+        {
+            resolve(items.remove(0))
+        }
+        Value::Struct(name, fields) => Value::Struct(
+            name,
+            fields
+                .into_iter()
+                .filter(|(_, v)| !is_remove(v))
+                .map(|(k, v)| (k, resolve(v)))
+                .collect(),
+        ),
+        Value::Tuple(name, items) => Value::Tuple(name, items.into_iter().map(resolve).collect()),
+        Value::List(items) => Value::List(items.into_iter().map(resolve).collect()),
+        Value::Map(entries) => Value::Map(
+            entries
+                .into_iter()
+                .filter(|(_, v)| !is_remove(v))
+                .map(|(k, v)| (k, resolve(v)))
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
+fn is_remove(value: &Value) -> bool {
+    matches!(value, Value::Atom(s) if s == REMOVE)
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Atom(s) => write!(f, "{}", s),
+            Value::Struct(name, fields) => {
+                write!(f, "{}(", name.as_deref().unwrap_or(""))?;
+                for (key, value) in fields {
+                    write!(f, "{}: {}, ", key, value)?;
+                }
+                write!(f, ")")
+            }
+            Value::Tuple(name, items) => {
+                write!(f, "{}(", name.as_deref().unwrap_or(""))?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                // A tuple with one element needs a trailing comma
+                if name.is_none() && items.len() == 1 {
+                    write!(f, ",")?;
+                }
+                write!(f, ")")
+            }
+            Value::List(items) => {
+                write!(f, "[")?;
+                for item in items {
+                    write!(f, "{}, ", item)?;
+                }
+                write!(f, "]")
+            }
+            Value::Map(entries) => {
+                write!(f, "{{")?;
+                for (key, value) in entries {
+                    write!(f, "{}: {}, ", key, value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.src.as_bytes().get(self.pos).copied()
+    }
+
+    fn peek_at(&self, n: usize) -> Option<u8> {
+        self.src.as_bytes().get(self.pos + n).copied()
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let c = self.peek();
+        if c.is_some() {
+            self.pos += 1;
+        }
+        c
+    }
+
+    fn error(&self, msg: &str) -> String {
+        let line = self.src[..self.pos].matches('\n').count() + 1;
+        format!("{} at line {}", msg, line)
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), String> {
+        self.skip_ws()?;
+        if self.next() == Some(c) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", c as char)))
+        }
+    }
+
+    /// Skip whitespaces and comments
+    fn skip_ws(&mut self) -> Result<(), String> {
+        loop {
+            match (self.peek(), self.peek_at(1)) {
+                (Some(c), _) if c.is_ascii_whitespace() => {
+                    self.pos += 1;
+                }
+                (Some(b'/'), Some(b'/')) => while !matches!(self.next(), Some(b'\n') | None) {},
+                (Some(b'/'), Some(b'*')) => {
+                    self.pos += 2;
+                    let mut depth = 1;
+                    while depth > 0 {
+                        match (self.next(), self.peek()) {
+                            (Some(b'/'), Some(b'*')) => {
+                                self.pos += 1;
+                                depth += 1;
+                            }
+                            (Some(b'*'), Some(b'/')) => {
+                                self.pos += 1;
+                                depth -= 1;
+                            }
+                            (None, _) => return Err(self.error("unclosed comment")),
+                            _ => (),
+                        }
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        self.skip_ws()?;
+        match self.peek() {
+            Some(b'(') => self.paren(None),
+            Some(b'[') => {
+                self.pos += 1;
+                Ok(Value::List(self.items(b']', Parser::value)?))
+            }
+            Some(b'{') => {
+                self.pos += 1;
+                let entries = self.items(b'}', |p| {
+                    let key = p.value()?;
+                    p.expect(b':')?;
+                    Ok((key, p.value()?))
+                })?;
+                Ok(Value::Map(entries))
+            }
+            Some(b'"') | Some(b'\'') => self.quoted(),
+            Some(b'r') if matches!(self.peek_at(1), Some(b'"') | Some(b'#')) => self.raw_string(),
+            Some(c) if c.is_ascii_alphabetic() || c == b'_' => {
+                let ident = self.ident();
+                self.skip_ws()?;
+                if self.peek() == Some(b'(') {
+                    self.paren(Some(ident))
+                } else {
+                    Ok(Value::Atom(ident))
+                }
+            }
+            Some(_) => {
+                let start = self.pos;
+                while self
+                    .peek()
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || b"+-._".contains(&c))
+                {
+                    self.pos += 1;
+                }
+                if start == self.pos {
+                    return Err(self.error("unexpected character"));
+                }
+                Ok(Value::Atom(self.src[start..self.pos].to_owned()))
+            }
+            None => Err(self.error("unexpected end of file")),
+        }
+    }
+
+    /// Parse a struct or a tuple
+    fn paren(&mut self, name: Option<String>) -> Result<Value, String> {
+        self.expect(b'(')?;
+        self.skip_ws()?;
+
+        // A struct starts with "field:"
+        let start = self.pos;
+        let ident = self.ident();
+        self.skip_ws()?;
+        let is_struct =
+            !ident.is_empty() && self.peek() == Some(b':') && self.peek_at(1) != Some(b':');
+        self.pos = start;
+
+        if is_struct {
+            let fields = self.items(b')', |p| {
+                p.skip_ws()?;
+                let key = p.ident();
+                if key.is_empty() {
+                    return Err(p.error("expected field name"));
+                }
+                p.expect(b':')?;
+                Ok((key, p.value()?))
+            })?;
+            Ok(Value::Struct(name, fields))
+        } else {
+            Ok(Value::Tuple(name, self.items(b')', Parser::value)?))
+        }
+    }
+
+    /// Parse comma separated items until the closing character
+    fn items<T, F>(&mut self, close: u8, mut f: F) -> Result<Vec<T>, String>
+    where
+        F: FnMut(&mut Parser<'a>) -> Result<T, String>,
+    {
+        let mut items = Vec::new();
+        loop {
+            self.skip_ws()?;
+            if self.peek() == Some(close) {
+                self.pos += 1;
+                return Ok(items);
+            }
+            items.push(f(self)?);
+            self.skip_ws()?;
+            match self.next() {
+                Some(b',') => (),
+                Some(c) if c == close => return Ok(items),
+                _ => return Err(self.error(&format!("expected ',' or '{}'", close as char))),
+            }
+        }
+    }
+
+    fn ident(&mut self) -> String {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == b'_')
+        {
+            self.pos += 1;
+        }
+        self.src[start..self.pos].to_owned()
+    }
+
+    /// Parse a string or a char
+    fn quoted(&mut self) -> Result<Value, String> {
+        let start = self.pos;
+        let quote = self.next();
+        loop {
+            match self.next() {
+                Some(b'\\') => {
+                    self.pos += 1;
+                }
+                c if c == quote => break,
+                None => return Err(self.error("unclosed string")),
+                _ => (),
+            }
+        }
+        Ok(Value::Atom(self.src[start..self.pos].to_owned()))
+    }
+
+    fn raw_string(&mut self) -> Result<Value, String> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut n_hash = 0;
+        while self.peek() == Some(b'#') {
+            self.pos += 1;
+            n_hash += 1;
+        }
+        self.expect(b'"')?;
+        let end = format!("\"{}", "#".repeat(n_hash));
+        if let Some(len) = self.src[self.pos..].find(&end) {
+            self.pos += len + end.len();
+            Ok(Value::Atom(self.src[start..self.pos].to_owned()))
+        } else {
+            Err(self.error("unclosed string"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn merged(base: &str, patch: &str) -> String {
+        let mut base = RonFile::parse(base).unwrap();
+        base.merge(RonFile::parse(patch).unwrap());
+        base.to_string()
+    }
+
+    #[test]
+    fn merge_test() {
+        let base = r#"
+            // Comment
+            #![enable(implicit_some)]
+            Rule(
+                n: 1,
+                list: [1, 2],
+                map: { "a": (x: 1, y: Some("s")), "b": (x: 2, y: None) },
+                kind: Slay(n: (1, 2)),
+                pair: (A, r"raw"),
+            )
+        "#;
+        let patch = r#"(
+            n: 2,
+            list: Append([3]),
+            map: { "a": (y: None), "b": Remove, "c": (x: 3, y: Remove) },
+            kind: Slay(n: (3, 4)),
+            pair: (B, r"raw"),
+            new_field: '/',
+        )"#;
+        assert_eq!(
+            merged(base, patch),
+            "#![enable(implicit_some)]\n\
+             Rule(n: 2, list: [1, 2, 3, ], map: {\"a\": (x: 1, y: None, ), \
+             \"c\": (x: 3, ), }, kind: Slay(n: (3, 4), ), pair: (B, r\"raw\"), \
+             new_field: '/', )"
+        );
+
+        assert_eq!(merged("[1, 2]", "[3]"), "[3, ]");
+        assert_eq!(merged("(a: [1])", "(a: Replace([2]))"), "(a: [2, ], )");
+        assert_eq!(merged("(k: A(x: 1))", "(k: B(y: 2))"), "(k: B(y: 2, ), )");
+        assert_eq!(merged("Some((1,))", "Some((2,))"), "Some((2,))");
+        assert!(RonFile::parse("(a: 1").is_err());
+        assert!(RonFile::parse("(a: \"s)").is_err());
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let e = RonFile::parse("#![enable(implicit_some)\n").unwrap_err();
+        assert!(e.starts_with("unclosed attribute"), "{}", e);
+        let e = RonFile::parse("(a: 1)\n(b: 2)").unwrap_err();
+        assert_eq!(e, "trailing characters at line 2");
+        let e = RonFile::parse("(a: 1) /* comment").unwrap_err();
+        assert!(e.starts_with("unclosed comment"), "{}", e);
+    }
+
+    #[test]
+    fn remove_test() {
+        // Struct fields
+        assert_eq!(merged("(a: 1, b: 2)", "(a: Remove)"), "(b: 2, )");
+        assert_eq!(merged("(a: 1)", "(b: Remove)"), "(a: 1, )");
+        assert_eq!(
+            merged("(s: (a: 1, b: 2))", "(s: (b: Remove))"),
+            "(s: (a: 1, ), )"
+        );
+
+        // Map entries
+        assert_eq!(
+            merged("{\"a\": 1, \"b\": 2}", "{\"a\": Remove}"),
+            "{\"b\": 2, }"
+        );
+        assert_eq!(merged("{\"a\": 1}", "{\"b\": Remove}"), "{\"a\": 1, }");
+        assert_eq!(
+            merged("{1: A, 2: B}", "{2: Remove, 3: C}"),
+            "{1: A, 3: C, }"
+        );
+
+        // Remove in a new value is dropped
+        assert_eq!(
+            merged("{}", "{\"a\": (x: 1, y: Remove)}"),
+            "{\"a\": (x: 1, ), }"
+        );
+    }
+
+    #[test]
+    fn replace_test() {
+        // Struct values are not merged
+        assert_eq!(
+            merged("(s: (a: 1, b: 2))", "(s: Replace((a: 3)))"),
+            "(s: (a: 3, ), )"
+        );
+        // Map values are not merged
+        assert_eq!(
+            merged("(m: {\"a\": 1, \"b\": 2})", "(m: Replace({\"c\": 3}))"),
+            "(m: {\"c\": 3, }, )"
+        );
+        // New fields
+        assert_eq!(merged("()", "(a: Replace([1]))"), "(a: [1, ], )");
+    }
+
+    #[test]
+    fn append_test() {
+        assert_eq!(merged("(a: [1])", "(a: Append(2))"), "(a: [1, 2, ], )");
+        assert_eq!(
+            merged("(a: [1])", "(a: Append((x: 2)))"),
+            "(a: [1, (x: 2, ), ], )"
+        );
+        assert_eq!(merged("(a: [])", "(a: Append([2, 3]))"), "(a: [2, 3, ], )");
+        // Appending to a value that is not a list overrides it
+        assert_eq!(merged("(a: 1)", "(a: Append([2]))"), "(a: [2, ], )");
+    }
+}
@@ -3,4 +3,18 @@
 pub struct Npc {
     /// Duration of npc recovering after map switching.
     pub map_switch_recover_minutes: u32,
+    /// Maximum number of party members except the player.
+    #[serde(default = "default_party_size")]
+    pub party_size: u32,
+    /// Money to hire a npc per its level.
+    #[serde(default = "default_hire_cost_per_level")]
+    pub hire_cost_per_level: i64,
+}
+
+fn default_party_size() -> u32 {
+    3
+}
+
+fn default_hire_cost_per_level() -> i64 {
+    100
 }
@@ -11,6 +11,10 @@ impl NpcAIs {
             .get(&kind)
             .unwrap_or_else(|| &self.0[&NpcAiKind::default()])
     }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&NpcAiKind, &NpcAi)> {
+        self.0.iter()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -26,6 +30,14 @@ pub struct NpcAi {
     /// Probability of trying to use active skill.
     #[serde(default)]
     pub active_skill_prob: f32,
+    /// AI kinds used during the hours (start hour, end hour, kind) instead of this.
+    /// The end hour is exclusive, and it can be smaller than the start hour for overnight.
+    #[serde(default)]
+    pub schedule: Vec<(u16, u16, NpcAiKind)>,
+    /// Behaviors in combat state. The available behavior with the highest score is taken.
+    /// If empty, approach_enemy_prob and active_skill_prob are used instead.
+    #[serde(default)]
+    pub combat_behaviors: Vec<CombatBehavior>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -34,3 +46,65 @@ pub enum MoveKind {
     Wander,
     Return,
 }
+
+/// A behavior candidate of combat state AI
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CombatBehavior {
+    pub kind: CombatBehaviorKind,
+    /// This behavior is available only when all conditions are satisfied.
+    #[serde(default)]
+    pub conditions: Vec<AiCondition>,
+    /// Base score to compare with other behaviors
+    #[serde(default = "default_score")]
+    pub score: f32,
+    /// Random value in [0, score_random) is added to the score
+    #[serde(default)]
+    pub score_random: f32,
+}
+
+fn default_score() -> f32 {
+    1.0
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum CombatBehaviorKind {
+    /// Move to the target, and attack it by melee attacks
+    Approach,
+    /// Keep the distance in the range from the target, and shoot it by ranged weapons
+    KeepDistance { min: u32, max: u32 },
+    /// Run away from the target
+    Flee,
+    /// Use an active skill whose effect has the target mode.
+    /// Enemy skills are used to the target, and ally skills to the most injured ally.
+    UseActiveSkill(TargetMode),
+    /// Use an item that has the effect kind, such as drinking a potion for RecoverHp
+    UseItem(AiItemEffect),
+}
+
+/// Effects of items used by AI
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum AiItemEffect {
+    RecoverHp,
+    RecoverSp,
+    RecoverMp,
+    /// Magic devices that attack enemies
+    Attack,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum AiCondition {
+    /// HP ratio of this character is lower than the value
+    HpBelow(f32),
+    /// HP ratio of this character is higher than or equal to the value
+    HpAbove(f32),
+    /// HP ratio of an ally in the detection range is lower than the value
+    AllyHpBelow(f32),
+    /// Distance to the target is shorter than the value
+    TargetNearer(u32),
+    /// Distance to the target is longer than or equal to the value
+    TargetFarther(u32),
+    /// This character equips a ranged weapon
+    HasRangedWeapon,
+    /// Satisfied by the probability
+    Probability(f32),
+}
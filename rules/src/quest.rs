@@ -1,8 +1,85 @@
 /// Rules for quest
+use common::gamedata::FactionId;
 use std::collections::HashMap;
 
 #[derive(Serialize, Deserialize)]
 pub struct Quest {
     /// The probability of choose npc for monster slaying quest
     pub slay_race_probability: HashMap<String, f32>,
+    /// The number of quests on a town quest board
+    #[serde(default = "default_n_town_quests")]
+    pub n_town_quests: u32,
+    /// Interval days of refreshing town quest boards
+    #[serde(default = "default_quest_refresh_days")]
+    pub quest_refresh_days: u32,
+    /// Reward money is multiplied by (1 + reward_level_factor * player level)
+    #[serde(default)]
+    pub reward_level_factor: f32,
+    /// Quest definitions
+    #[serde(default = "default_quests")]
+    pub quests: HashMap<String, QuestDef>,
+    /// Faction relation changes when a quest without definition is failed or abandoned
+    #[serde(default)]
+    pub default_failure_penalty: Vec<(FactionId, i16)>,
+}
+
+fn default_n_town_quests() -> u32 {
+    1
+}
+
+fn default_quest_refresh_days() -> u32 {
+    7
+}
+
+/// The monster slaying quest that was the only quest before quest definitions
+fn default_quests() -> HashMap<String, QuestDef> {
+    let def = QuestDef {
+        weight: 1.0,
+        kind: QuestKind::SlayMonsters { n: (10, 10) },
+        reward_money: 1000,
+        reward_money_per_unit: 0,
+        reward_items: Vec::new(),
+        deadline_days: None,
+        failure_penalty: Vec::new(),
+    };
+    vec![("slay_monsters".to_owned(), def)]
+        .into_iter()
+        .collect()
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QuestDef {
+    /// Weight for quest generation
+    pub weight: f32,
+    pub kind: QuestKind,
+    /// Base reward money
+    pub reward_money: i64,
+    /// Additional reward money for each monster, item, or floor of the objective
+    #[serde(default)]
+    pub reward_money_per_unit: i64,
+    /// Items given as reward
+    #[serde(default)]
+    pub reward_items: Vec<String>,
+    /// Days until the deadline. No deadline if None.
+    #[serde(default)]
+    pub deadline_days: Option<u32>,
+    /// Faction relation changes when the quest is failed or abandoned
+    #[serde(default)]
+    pub failure_penalty: Vec<(FactionId, i16)>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum QuestKind {
+    /// Slay monsters chosen by slay_race_probability
+    SlayMonsters { n: (u32, u32) },
+    /// Bring one of the items to a quest board
+    FetchItems { items: Vec<String>, n: (u32, u32) },
+    /// Deliver one of the items to a citizen of other town
+    Deliver { items: Vec<String> },
+    /// Escort a traveller to other town
+    Escort { traveller: String },
+    /// Reach the given floor of a dungeon in the region of the town
+    ExploreDungeon { floor: (u32, u32) },
+    /// Create one of the items
+    Craft { items: Vec<String>, n: (u32, u32) },
 }
@@ -10,7 +10,44 @@ pub struct Town {
     pub min_shop_items: u32,
     /// The maximum number of shop items
     pub max_shop_items: u32,
+    /// Interval days of restocking shop items
+    #[serde(default = "default_shop_restock_days")]
+    pub shop_restock_days: u32,
+    /// Selling price is multiplied by this value
+    #[serde(default = "default_selling_price_factor")]
+    pub selling_price_factor: f32,
+    /// Price discount per Charisma point
+    #[serde(default)]
+    pub discount_per_cha: f32,
+    /// Price discount per Negotiation skill level
+    #[serde(default)]
+    pub discount_per_negotiation: f32,
+    /// Price discount per relation point with the shopkeeper's faction
+    #[serde(default)]
+    pub discount_per_relation: f32,
+    /// The maximum discount. Negative discounts are also limited by this value
+    #[serde(default)]
+    pub max_discount: f32,
+    /// Price is multiplied by (1 + rarity_price_factor / (1 + the number of the same items
+    /// in stock at the town))
+    #[serde(default)]
+    pub rarity_price_factor: f32,
+    /// Selling price is divided by (1 + sold_price_factor * the number of the same items
+    /// sold at the town recently)
+    #[serde(default)]
+    pub sold_price_factor: f32,
+    /// Decrease of the number of recently sold items per day
+    #[serde(default)]
+    pub sold_count_recovery: u32,
     /// Shop kinds and its item selectors.
     #[serde_as(as = "HashMap<_, DisplayFromStr>")]
     pub shop_kinds: HashMap<String, ItemSelector>,
 }
+
+fn default_shop_restock_days() -> u32 {
+    7
+}
+
+fn default_selling_price_factor() -> f32 {
+    0.5
+}
@@ -0,0 +1,193 @@
+use crate::npc_ai::CombatBehaviorKind;
+use crate::Rules;
+use common::gamedata::{CreationKind, SkillKind};
+use common::gobj;
+use common::item_selector::ItemSelector;
+use common::objholder::*;
+use std::fmt;
+
+/// Broken reference in a rule file
+#[derive(Clone, Debug)]
+pub struct RuleRefError {
+    pub file: &'static str,
+    pub field: String,
+    pub msg: String,
+}
+
+impl RuleRefError {
+    pub(crate) fn new<S: Into<String>>(file: &'static str, field: S, msg: String) -> RuleRefError {
+        RuleRefError {
+            file,
+            field: field.into(),
+            msg,
+        }
+    }
+}
+
+impl fmt::Display for RuleRefError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.file, self.field, self.msg)
+    }
+}
+
+struct Validator<'a> {
+    rules: &'a Rules,
+    file: &'static str,
+    errors: Vec<RuleRefError>,
+}
+
+impl<'a> Validator<'a> {
+    fn error(&mut self, field: String, msg: String) {
+        self.errors.push(RuleRefError::new(self.file, field, msg));
+    }
+
+    fn obj_id<T: ObjectIndex>(&mut self, field: String, kind: &str, id: &str) {
+        if T::search_idx(id, gobj::get_objholder()).is_none() {
+            self.error(field, format!("unknown {} \"{}\"", kind, id));
+        }
+    }
+
+    fn item_id(&mut self, field: String, id: &str) {
+        self.obj_id::<ItemIdx>(field, "item", id);
+    }
+
+    fn chara_template_id(&mut self, field: String, id: &str) {
+        self.obj_id::<CharaTemplateIdx>(field, "character template", id);
+    }
+
+    fn item_selector(&mut self, field: String, selector: &ItemSelector) {
+        for id in selector.ids() {
+            self.item_id(field.clone(), id);
+        }
+        for group in selector.groups() {
+            if !gobj::get_objholder().item.iter().any(|o| o.group == *group) {
+                self.error(field.clone(), format!("no item in group \"{}\"", group));
+            }
+        }
+    }
+
+    fn race(&mut self, field: String, race: &str) {
+        if !self.rules.race.contains_key(race) {
+            self.error(field, format!("unknown race \"{}\"", race));
+        }
+    }
+}
+
+pub(crate) fn validate(rules: &Rules) -> Vec<RuleRefError> {
+    let mut v = Validator {
+        rules,
+        file: "",
+        errors: Vec::new(),
+    };
+
+    v.file = "class.ron";
+    for (class, c) in rules.class.iter() {
+        for (i, (_, selector, _)) in c.equips.iter().enumerate() {
+            v.item_selector(format!("{:?}.equips[{}]", class, i), selector);
+        }
+    }
+
+    // Recipes are defined in creation.ron and recipe directories
+    v.file = "recipe";
+    for kind in CreationKind::all() {
+        for recipe in rules.creation.recipes(kind) {
+            let kind = SkillKind::from(kind).id();
+            let field = format!("{}[\"{}\"]", kind, recipe.product);
+            v.item_id(format!("{}.product", field), &recipe.product);
+            for (ingredient, _) in &recipe.ingredients {
+                if !ingredient.starts_with("group/") {
+                    v.item_id(format!("{}.ingredients", field), ingredient);
+                }
+            }
+        }
+    }
+
+    v.file = "dungeon_gen.ron";
+    for (kind, params) in &rules.dungeon_gen {
+        for (i, [tile, wall]) in params.terrain.iter().enumerate() {
+            let field = format!("{:?}.terrain[{}]", kind, i);
+            v.obj_id::<TileIdx>(field.clone(), "tile", tile);
+            v.obj_id::<WallIdx>(field, "wall", wall);
+        }
+        for (i, (wall, _)) in params.sub_walls.iter().enumerate() {
+            v.obj_id::<WallIdx>(format!("{:?}.sub_walls[{}]", kind, i), "wall", wall);
+        }
+        for (i, (map_gen, _)) in params.map_gen.iter().enumerate() {
+            if !rules.map_gen.map_gen_params.contains_key(map_gen) {
+                v.error(
+                    format!("{:?}.map_gen[{}]", kind, i),
+                    format!("unknown map generation parameter \"{}\"", map_gen),
+                );
+            }
+        }
+        for race in params.npc_race_probability.keys() {
+            v.race(format!("{:?}.npc_race_probability", kind), race);
+        }
+        if !rules
+            .faction
+            .factions
+            .contains_key(&params.default_faction_id)
+        {
+            v.error(
+                format!("{:?}.default_faction_id", kind),
+                format!("unknown faction \"{}\"", params.default_faction_id.as_str()),
+            );
+        }
+    }
+
+    v.file = "newgame.ron";
+    for (class, id) in &rules.newgame.chara_template_table {
+        v.chara_template_id(format!("chara_template_table[{:?}]", class), id);
+    }
+
+    v.file = "npc_ai.ron";
+    for (kind, ai) in rules.npc_ai.iter() {
+        for (i, (_, _, schedule_kind)) in ai.schedule.iter().enumerate() {
+            if !rules.npc_ai.iter().any(|(k, _)| k == schedule_kind) {
+                v.error(
+                    format!("{}.schedule[{}]", kind.as_str(), i),
+                    format!("unknown npc ai kind \"{}\"", schedule_kind.as_str()),
+                );
+            }
+        }
+        for (i, behavior) in ai.combat_behaviors.iter().enumerate() {
+            if let CombatBehaviorKind::KeepDistance { min, max } = behavior.kind {
+                if min > max {
+                    v.error(
+                        format!("{}.combat_behaviors[{}]", kind.as_str(), i),
+                        format!("min distance {} is larger than max {}", min, max),
+                    );
+                }
+            }
+        }
+    }
+
+    v.file = "quest.ron";
+    for race in rules.quest.slay_race_probability.keys() {
+        v.race("slay_race_probability".into(), race);
+    }
+    for (name, quest) in &rules.quest.quests {
+        use crate::quest::QuestKind;
+        let field = format!("quests[\"{}\"].kind", name);
+        match &quest.kind {
+            QuestKind::FetchItems { items, .. }
+            | QuestKind::Deliver { items }
+            | QuestKind::Craft { items, .. } => {
+                for id in items {
+                    v.item_id(field.clone(), id);
+                }
+            }
+            QuestKind::Escort { traveller } => {
+                v.chara_template_id(field, traveller);
+            }
+            _ => (),
+        }
+    }
+
+    v.file = "town.ron";
+    for (shop_kind, selector) in &rules.town.shop_kinds {
+        v.item_selector(format!("shop_kinds[\"{}\"]", shop_kind), selector);
+    }
+
+    v.errors
+}
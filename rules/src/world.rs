@@ -1,6 +1,31 @@
+use common::gamedata::FactionId;
+
 /// Rules for game world
 #[derive(Serialize, Deserialize)]
 pub struct World {
     /// Restart map path
     pub restart_path: String,
+    /// Events applied to the world periodically
+    #[serde(default)]
+    pub periodic_events: Vec<PeriodicEvent>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PeriodicEvent {
+    pub interval: PeriodicInterval,
+    pub kind: PeriodicEventKind,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum PeriodicInterval {
+    Hours(u32),
+    Days(u32),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum PeriodicEventKind {
+    /// Execute the script with NewHour or NewDay event
+    Script { id: String },
+    /// Change the relation of the faction to the player
+    FactionRelation { faction: FactionId, value: i16 },
 }
@@ -1,15 +1,81 @@
-use crate::config::Config;
+use crate::config::{Config, HeadlessConfig};
 use clap::{App, Arg, ArgMatches};
 
+const DEFAULT_HEADLESS_TURNS: u32 = 100;
+
 fn get_matches() -> ArgMatches<'static> {
-    App::new("Rusted Ruins")
+    let app = App::new("Rusted Ruins")
         .version(env!("CARGO_PKG_VERSION"))
         .arg(
             Arg::with_name("fix-rand")
                 .long("fix-rand")
                 .help("Fixes the state of RNG when game start"),
         )
-        .get_matches()
+        .arg(
+            Arg::with_name("mute")
+                .long("mute")
+                .help("Disables sounds and music"),
+        )
+        .arg(
+            Arg::with_name("check-rules")
+                .long("check-rules")
+                .help("Checks rule files and references in them, and exits"),
+        )
+        .arg(
+            Arg::with_name("record")
+                .long("record")
+                .value_name("DIR")
+                .help("Records inputs to the replay directory when game start"),
+        )
+        .arg(
+            Arg::with_name("replay")
+                .long("replay")
+                .value_name("DIR")
+                .conflicts_with("record")
+                .help("Replays recorded inputs in the replay directory without window"),
+        )
+        .arg(
+            Arg::with_name("headless")
+                .long("headless")
+                .conflicts_with_all(&["record", "replay"])
+                .help("Runs simulation without window"),
+        )
+        .arg(
+            Arg::with_name("turns")
+                .long("turns")
+                .value_name("N")
+                .requires("headless")
+                .help("The number of player turns to simulate in headless mode"),
+        )
+        .arg(
+            Arg::with_name("load")
+                .long("load")
+                .value_name("DIR")
+                .requires("headless")
+                .help("Save directory to load in headless mode"),
+        )
+        .arg(
+            Arg::with_name("class")
+                .long("class")
+                .value_name("CLASS")
+                .requires("headless")
+                .conflicts_with("load")
+                .help("Character class of new game in headless mode"),
+        )
+        .arg(
+            Arg::with_name("commands")
+                .long("commands")
+                .value_name("FILE")
+                .requires("headless")
+                .help("Commands for the player in headless mode"),
+        );
+
+    // Arguments given to the test harness are not for the game
+    if cfg!(test) {
+        app.get_matches_from(["rusted-ruins"])
+    } else {
+        app.get_matches()
+    }
 }
 
 pub fn modify_config_by_args(mut config: Config) -> Config {
@@ -19,5 +85,41 @@ pub fn modify_config_by_args(mut config: Config) -> Config {
         config.fix_rand = true;
     }
 
+    if matches.is_present("mute") {
+        config.mute = true;
+    }
+
+    if matches.is_present("check-rules") {
+        config.check_rules = true;
+    }
+
+    if let Some(path) = matches.value_of("record") {
+        config.record = Some(path.into());
+    }
+
+    if let Some(path) = matches.value_of("replay") {
+        config.replay = Some(path.into());
+    }
+
+    if matches.is_present("headless") {
+        let turns = if let Some(turns) = matches.value_of("turns") {
+            match turns.parse() {
+                Ok(turns) => turns,
+                Err(e) => {
+                    error!("Invalid number of turns \"{}\": {}", turns, e);
+                    std::process::exit(1);
+                }
+            }
+        } else {
+            DEFAULT_HEADLESS_TURNS
+        };
+        config.headless = Some(HeadlessConfig {
+            turns,
+            load: matches.value_of("load").map(|path| path.into()),
+            class: matches.value_of("class").map(|class| class.to_owned()),
+            commands: matches.value_of("commands").map(|path| path.into()),
+        });
+    }
+
     config
 }
@@ -252,4 +252,32 @@ pub struct Config {
     pub fix_rand: bool,
     pub enable_joystick: bool,
     pub music_volume: i32,
+    /// Disable sounds and music
+    #[serde(default)]
+    pub mute: bool,
+    /// Check rule files and exit
+    #[serde(skip)]
+    pub check_rules: bool,
+    /// Directory to record player's inputs
+    #[serde(skip)]
+    pub record: Option<PathBuf>,
+    /// Directory of recorded inputs to replay
+    #[serde(skip)]
+    pub replay: Option<PathBuf>,
+    /// Run simulation without window
+    #[serde(skip)]
+    pub headless: Option<HeadlessConfig>,
+}
+
+/// Settings for headless simulation given by command line arguments
+#[derive(Debug)]
+pub struct HeadlessConfig {
+    /// The number of player turns to advance
+    pub turns: u32,
+    /// Save directory to load. If None, start new game.
+    pub load: Option<PathBuf>,
+    /// Character class for new game
+    pub class: Option<String>,
+    /// Commands file in JSON Lines format. Auto player acts after all commands are used.
+    pub commands: Option<PathBuf>,
 }
@@ -14,6 +14,7 @@ pub struct ScreenConfig {
     pub shortcut_list: CfgRect,
     pub hp_indicator: CfgRect,
     pub sp_indicator: CfgRect,
+    pub mp_indicator: CfgRect,
     pub floor_info: CfgRect,
     pub date_info: CfgRect,
     pub time_info: CfgRect,
@@ -61,6 +62,7 @@ pub struct UiConfig {
     pub game_info_window: GameInfoWindowConfig,
     pub skill_window: SkillWindowConfig,
     pub quest_window: QuestWindowConfig,
+    pub build_window: BuildWindowConfig,
     pub label_widget: LabelWidgetConfig,
     pub list_widget: ListWidgetConfig,
     pub time_info: TimeInfoConfig,
@@ -87,6 +89,7 @@ pub struct UiColorConfig {
     pub gauge_border_light: CfgColor,
     pub gauge_hp: CfgColor,
     pub gauge_sp: CfgColor,
+    pub gauge_mp: CfgColor,
     pub gauge_work: CfgColor,
     pub list_border: CfgColor,
     pub log_font: CfgColor,
@@ -265,6 +268,7 @@ pub struct StatusWindowConfig {
     pub name_label_rect: CfgRect,
     pub hp_label_rect: CfgRect,
     pub sp_label_rect: CfgRect,
+    pub mp_label_rect: CfgRect,
     pub faction_label_rect: CfgRect,
     pub str_label_rect: CfgRect,
     pub vit_label_rect: CfgRect,
@@ -300,6 +304,13 @@ pub struct QuestWindowConfig {
     pub n_row: u32,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct BuildWindowConfig {
+    pub rect: CfgRect,
+    pub n_row: u32,
+    pub column_pos: Vec<i32>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct LabelWidgetConfig {
     pub h: i32,
@@ -109,6 +109,20 @@ impl<'a, 'b, 't, 'sdl> Context<'a, 'b, 't, 'sdl> {
         }
     }
 
+    /// Render an icon translucently. The bottom of the icon is aligned to dest.
+    pub fn render_icon_translucent(&mut self, idx: IconIdx, dest: Rect, alpha: u8) {
+        let (tex, src) = self.sv.texture_holder.get_icon_mut(idx);
+        let dest = Rect::new(
+            dest.x + (dest.w - src.w) / 2,
+            dest.y + dest.h - src.h,
+            src.width(),
+            src.height(),
+        );
+        tex.set_alpha_mod(alpha);
+        try_sdl!(self.canvas.copy(tex, src, dest));
+        tex.set_alpha_mod(255);
+    }
+
     pub fn draw_rect<R: Into<Rect>, T: Into<Color>>(&mut self, rect: R, color: T) {
         self.canvas.set_draw_color(color.into());
         try_sdl!(self.canvas.draw_rect(rect.into()))
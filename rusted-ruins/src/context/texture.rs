@@ -84,7 +84,7 @@ impl<'a> TextureCreatorW<'a> {
 
 // Implement icon idx
 macro_rules! impl_iconidx {
-    ($({$a:ident, $idx:ident}),*) => {
+    ($({$a:ident, $idx:ident, $mem:ident}),*) => {
         #[derive(Clone, Copy, PartialEq, Eq, Debug)]
         pub enum IconIdx {
             $(
@@ -104,6 +104,18 @@ macro_rules! impl_iconidx {
                     )*
                 }
             }
+
+            pub fn get_icon_mut(&mut self, idx: IconIdx) -> (&mut Texture<'a>, Rect) {
+                match idx {
+                    $(
+                        IconIdx::$a { idx, i_pattern } => {
+                            let t = &mut self.$mem[idx.as_usize()];
+                            let r = gobj::get_obj(idx).img_rect_pattern(i_pattern);
+                            return (t, Rect::from(r));
+                        }
+                    )*
+                }
+            }
         }
 
         $(
@@ -115,6 +127,9 @@ macro_rules! impl_iconidx {
 }
 
 impl_iconidx! {
-    {Item, ItemIdx},
-    {UiImg, UiImgIdx}
+    {Deco, DecoIdx, deco},
+    {Item, ItemIdx, item},
+    {Tile, TileIdx, tile},
+    {UiImg, UiImgIdx, ui_img},
+    {Wall, WallIdx, wall}
 }
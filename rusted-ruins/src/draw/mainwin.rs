@@ -16,6 +16,7 @@ use sdl2::render::Texture;
 use sdl2::render::WindowCanvas;
 
 const CHARA_DRAW_OFFSET: i32 = 16;
+const BUILD_PREVIEW_ALPHA: u8 = 128;
 
 #[derive(Clone, Debug)]
 pub struct TargetModeDrawInfo {
@@ -51,6 +52,7 @@ impl MainWinDrawer {
         centering_tile: Option<Vec2d>,
         hover_tile: Option<Vec2d>,
         target_mode: Option<&TargetModeDrawInfo>,
+        build_preview: Option<(Vec2d, BuildObj)>,
     ) {
         super::frame::next_frame();
         let mut player_move_dir = None;
@@ -94,6 +96,10 @@ impl MainWinDrawer {
             self.draw_target_mode(context, game, target_mode);
         }
 
+        if let Some((pos, obj)) = build_preview {
+            self.draw_build_preview(context, pos, obj);
+        }
+
         if let Some(t) = hover_tile {
             self.draw_tile_cursor(context, t);
         }
@@ -424,6 +430,15 @@ impl MainWinDrawer {
         }
     }
 
+    fn draw_build_preview(&self, context: &mut Context, pos: Vec2d, obj: BuildObj) {
+        let icon = match obj {
+            BuildObj::Wall(idx) => IconIdx::from(idx),
+            BuildObj::Tile(idx) => IconIdx::from(idx),
+            BuildObj::Deco(idx) => IconIdx::from(idx),
+        };
+        context.render_icon_translucent(icon, self.tile_rect(pos, 0, 0), BUILD_PREVIEW_ALPHA);
+    }
+
     fn draw_tile_cursor(&self, context: &mut Context, ct: Vec2d) {
         let idx: UiImgIdx = gobj::id_to_idx_checked("!tile-cursor")
             .expect("UIImg object \"!tile-cursor\" not found");
@@ -25,6 +25,8 @@ pub struct EventHandler {
     waiting_dir_release: WaitingDirRelease,
     mouse_state: Option<MouseState>,
     key_state: KeyState,
+    /// Recorded commands used instead of inputs in replay mode
+    replay: Option<VecDeque<Command>>,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -101,9 +103,23 @@ impl EventHandler {
             waiting_dir_release: WaitingDirRelease::No,
             mouse_state: None,
             key_state: KeyState::default(),
+            replay: None,
         }
     }
 
+    /// Start replay mode. Given commands are returned by get_command() in order.
+    pub fn start_replay(&mut self, commands: VecDeque<Command>) {
+        self.replay = Some(commands);
+    }
+
+    /// Returns true if all recorded commands are consumed in replay mode
+    pub fn replay_finished(&self) -> bool {
+        self.replay
+            .as_ref()
+            .map(|commands| commands.is_empty())
+            .unwrap_or(false)
+    }
+
     pub fn process_event(&mut self, event: Event) -> bool {
         match event {
             Event::Quit { .. } => {
@@ -244,6 +260,10 @@ impl EventHandler {
     }
 
     pub fn get_command(&mut self, mode: InputMode) -> Option<Command> {
+        if let Some(commands) = self.replay.as_mut() {
+            return commands.pop_front();
+        }
+
         // If input mode switched normal, cursor shouldn't move until direction key released once
         if mode == InputMode::Dialog && self.prev_input_mode == InputMode::Normal {
             self.waiting_dir_release = WaitingDirRelease::Waiting;
@@ -14,6 +14,12 @@ pub fn get_item<T: Into<ItemMoveNum>>(
         ItemMoveNum::Partial(n) => n,
         ItemMoveNum::All => src_list.get_number(item_location.1),
     };
+    let event = ScriptEvent::PickUpItem {
+        cid,
+        item: obj.id.clone(),
+        n,
+    };
+    crate::game::script_hook::push_event(gd, event);
 
     // If item is gold and dest is player, increases player money.
     if obj.id == "!gold" {
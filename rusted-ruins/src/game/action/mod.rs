@@ -80,6 +80,7 @@ pub fn melee_attack(game: &mut Game, cid: CharaId, target: CharaId) {
                 anim_img: "!damage-blunt".into(),
                 anim_img_shot: String::new(),
                 sound: "punch".into(),
+                ..Effect::default()
             };
             (effect, SkillKind::BareHands, 1)
         };
@@ -97,6 +98,7 @@ pub fn melee_attack(game: &mut Game, cid: CharaId, target: CharaId) {
     let target_level = game.gd.chara.get(target).level;
     let attacker = game.gd.chara.get_mut(cid);
     attacker.add_attack_exp(skill_kind, target_level);
+    crate::game::party::share_attack_exp(&mut game.gd, cid, skill_kind, target_level);
 }
 
 /// Shoot target
@@ -125,11 +127,12 @@ pub fn shoot_target(game: &mut Game, cid: CharaId, target: CharaId) -> bool {
     let target_level = game.gd.chara.get(target).level;
     let attacker = game.gd.chara.get_mut(cid);
     attacker.add_attack_exp(skill_kind, target_level);
+    crate::game::party::share_attack_exp(&mut game.gd, cid, skill_kind, target_level);
 
     true
 }
 
-fn get_skill_kind_from_weapon(item: &Item) -> SkillKind {
+pub fn get_skill_kind_from_weapon(item: &Item) -> SkillKind {
     let weapon_obj = gobj::get_obj(item.idx);
     match weapon_obj.kind {
         ItemKind::Weapon(kind) => kind.into(),
@@ -161,6 +164,7 @@ pub fn throw_item(game: &mut Game, il: ItemLocation, cid: CharaId, target: Targe
     };
     let attacker = game.gd.chara.get_mut(cid);
     attacker.add_attack_exp(SkillKind::Throwing, target_level);
+    crate::game::party::share_attack_exp(&mut game.gd, cid, SkillKind::Throwing, target_level);
 }
 
 /// Drink one item
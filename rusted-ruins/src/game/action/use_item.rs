@@ -52,6 +52,7 @@ fn use_deed(game: &mut Game) -> bool {
     let mut site = Site::new(1, None);
     site.content = SiteContent::Player {
         kind: PlayerBaseKind::Normal,
+        stash: ItemList::default(),
     };
     let rid = mapid.rid();
     let sid = gd.add_site(site, SiteKind::Player, rid, Some(pos)).unwrap();
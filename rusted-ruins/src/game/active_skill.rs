@@ -1,16 +1,17 @@
 use super::effect::do_effect;
 use super::extrait::*;
+use super::target::Target;
 use super::Game;
 use crate::text::ToText;
 use common::gamedata::*;
 use rules::RULES;
 
 /// Return true if success.
-pub fn use_active_skill(
+pub fn use_active_skill<T: Into<Target>>(
     game: &mut Game,
     active_skill_id: &ActiveSkillId,
     cid: CharaId,
-    target: CharaId,
+    target: T,
 ) -> bool {
     let active_skill = if let Some(active_skill) = RULES.active_skills.get(active_skill_id) {
         active_skill
@@ -23,9 +24,16 @@ pub fn use_active_skill(
     if !chara.active_skill_available(active_skill) {
         return false;
     }
-
-    let power =
-        crate::game::active_skill::calc_power(&game.gd, active_skill, cid) * active_skill.power;
+    let power = if let Some(power) = calc_power(&game.gd, active_skill, cid) {
+        power * active_skill.power
+    } else {
+        warn!(
+            "unsupported power calculation method for active_skill \"{}\"",
+            active_skill_id
+        );
+        return false;
+    };
+    game.gd.chara.get_mut(cid).mp -= active_skill.cost_mp as i32;
     let hit_power = active_skill.hit_power;
 
     let chara = game.gd.chara.get(cid);
@@ -57,15 +65,16 @@ pub fn use_active_skill(
     true
 }
 
-pub fn calc_power(gd: &GameData, active_skill: &'static ActiveSkill, cid: CharaId) -> f32 {
+/// Returns None if the power calculation method is not supported.
+pub fn calc_power(gd: &GameData, active_skill: &'static ActiveSkill, cid: CharaId) -> Option<f32> {
     match active_skill.power_calc {
-        PowerCalcMethod::Num(n) => n,
+        PowerCalcMethod::Num(n) => Some(n),
         PowerCalcMethod::Magic => {
             let chara = gd.chara.get(cid);
             let skill_level = chara.skill_level(SkillKind::MagicDevice) as f32;
             let int = chara.attr.int as f32;
-            skill_level * int
+            Some(skill_level * int)
         }
-        _ => todo!(),
+        PowerCalcMethod::Custom(_) => None,
     }
 }
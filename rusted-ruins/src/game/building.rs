@@ -1,16 +1,72 @@
+use super::extrait::*;
 use super::Game;
+use crate::text::obj_txt;
 use common::gamedata::*;
 use common::gobj;
 use common::obj::*;
 use common::objholder::*;
 use geom::*;
+use rules::RULES;
 
-pub fn start_build(game: &mut Game, pos: Vec2d, builder: CharaId) {
-    let wall_id = "wooden-wall-01";
-    let wall_idx: WallIdx = gobj::id_to_idx(wall_id);
-    let wall_obj = gobj::get_obj(wall_idx);
+/// Get all objects that have build requirements
+pub fn build_candidates() -> Vec<BuildObj> {
+    let objholder = gobj::get_objholder();
+    let walls = objholder
+        .wall
+        .iter()
+        .enumerate()
+        .filter(|(_, obj)| obj.materials.is_some())
+        .map(|(i, _)| BuildObj::Wall(WallIdx::from_usize(i)));
+    let tiles = objholder
+        .tile
+        .iter()
+        .enumerate()
+        .filter(|(_, obj)| obj.materials.is_some())
+        .map(|(i, _)| BuildObj::Tile(TileIdx::from_usize(i)));
+    let decos = objholder
+        .deco
+        .iter()
+        .enumerate()
+        .filter(|(_, obj)| obj.materials.is_some())
+        .map(|(i, _)| BuildObj::Deco(DecoIdx::from_usize(i)));
+    walls.chain(tiles).chain(decos).collect()
+}
+
+/// Get the id, needed skill level and materials of the object
+pub fn build_requirements(obj: BuildObj) -> (&'static str, u32, &'static [(String, u32)]) {
+    let (id, build_skill, materials) = match obj {
+        BuildObj::Wall(idx) => {
+            let o = gobj::get_obj(idx);
+            (&o.id, o.build_skill, &o.materials)
+        }
+        BuildObj::Tile(idx) => {
+            let o = gobj::get_obj(idx);
+            (&o.id, o.build_skill, &o.materials)
+        }
+        BuildObj::Deco(idx) => {
+            let o = gobj::get_obj(idx);
+            (&o.id, o.build_skill, &o.materials)
+        }
+    };
+    (
+        id,
+        build_skill.unwrap_or(0),
+        materials.as_deref().unwrap_or(&[]),
+    )
+}
 
-    if !is_buildable(&game.gd, pos) {
+pub fn start_build(game: &mut Game, pos: Vec2d, builder: CharaId, obj: BuildObj) {
+    let (id, build_skill, materials) = build_requirements(obj);
+
+    if !is_buildable(&game.gd, pos, obj) {
+        game_log_i!("building-not-buildable-tile");
+        return;
+    }
+
+    let chara = game.gd.chara.get(builder);
+    if chara.skill_level(SkillKind::Construction) < build_skill {
+        let item = obj_txt(id);
+        game_log_i!("building-lack-of-skill"; item=item, skill_level=build_skill);
         return;
     }
 
@@ -18,15 +74,13 @@ pub fn start_build(game: &mut Game, pos: Vec2d, builder: CharaId) {
         .gd
         .get_item_list_mut(ItemListLocation::Chara { cid: builder });
 
-    let materials = wall_obj.materials.as_ref().unwrap();
-
     // Check player has needed materials
     for &(ref item_id, n) in materials {
         let item_idx: ItemIdx = gobj::id_to_idx(item_id);
         let has = item_list.count(item_idx);
         if has < n {
             let needed = n - has;
-            let item = crate::text::obj_txt(item_id);
+            let item = obj_txt(item_id);
             game_log_i!("building-shortage-material"; item=item, n=needed);
             return;
         }
@@ -38,29 +92,160 @@ pub fn start_build(game: &mut Game, pos: Vec2d, builder: CharaId) {
         item_list.consume(item_idx, n, |_, _| {}, false);
     }
 
-    finish_build(game, pos, wall_idx);
+    let needed_turn = RULES.creation.build_turn;
+    game.gd
+        .chara
+        .get_mut(builder)
+        .add_status(CharaStatus::Work {
+            turn_left: needed_turn,
+            needed_turn,
+            work: Work::Build { pos, obj },
+        });
+    game.anim_queue.push_work(1.0);
+    let item = obj_txt(id);
+    game_log_i!("building-start"; chara=game.gd.chara.get(builder), item=item);
 }
 
-pub fn finish_build(game: &mut Game, pos: Vec2d, wall_idx: WallIdx) {
-    let map = game.gd.get_current_map_mut();
-    map.set_wall(pos, wall_idx);
+pub fn finish_build(gd: &mut GameData, cid: CharaId, pos: Vec2d, obj: BuildObj) {
+    let (id, build_skill, materials) = build_requirements(obj);
+
+    // The tile may be occupied during building
+    if !is_buildable(gd, pos, obj) {
+        game_log_i!("building-not-buildable-tile");
+        refund_materials(gd, cid, materials, 1.0);
+        return;
+    }
+
+    let map = gd.get_current_map_mut();
+    match obj {
+        BuildObj::Wall(idx) => map.set_wall(pos, idx),
+        BuildObj::Tile(idx) => map.set_tile(pos, idx, None),
+        BuildObj::Deco(idx) => map.tile[pos].deco = Some(idx),
+    }
+    if !matches!(obj, BuildObj::Tile(_)) && !map.player_built.contains(&pos) {
+        map.player_built.push(pos);
+    }
+    gd.chara.get_mut(cid).skills.add_exp(
+        SkillKind::Construction,
+        RULES.exp.creation_base_exp,
+        build_skill,
+    );
+    let item = obj_txt(id);
+    game_log_i!("building-finish"; chara=gd.chara.get(cid), item=item);
     audio::play_sound("finish-build");
 }
 
-fn is_buildable(gd: &GameData, pos: Vec2d) -> bool {
+/// Get the object that can be deconstructed at the tile.
+/// Walls are prior to decos. Tiles cannot be deconstructed.
+/// Only objects built by the player or on the player's base can be deconstructed.
+pub fn deconstructible_obj(gd: &GameData, pos: Vec2d) -> Option<BuildObj> {
+    let map = gd.get_current_map();
+    if !map.is_inside(pos) {
+        return None;
+    }
+    let mid = gd.get_current_mapid();
+    let on_player_base = !mid.is_region_map() && mid.sid().kind == SiteKind::Player;
+    if !on_player_base && !map.player_built.contains(&pos) {
+        return None;
+    }
+    let tile = &map.tile[pos];
+
+    if let Some(idx) = tile.wall.idx() {
+        return gobj::get_obj(idx)
+            .materials
+            .as_ref()
+            .map(|_| BuildObj::Wall(idx));
+    }
+    if let Some(idx) = tile.deco {
+        return gobj::get_obj(idx)
+            .materials
+            .as_ref()
+            .map(|_| BuildObj::Deco(idx));
+    }
+    None
+}
+
+pub fn start_deconstruct(game: &mut Game, pos: Vec2d, builder: CharaId) {
+    let obj = if let Some(obj) = deconstructible_obj(&game.gd, pos) {
+        obj
+    } else {
+        return;
+    };
+    let (id, _, _) = build_requirements(obj);
+
+    let needed_turn = RULES.creation.deconstruct_turn;
+    game.gd
+        .chara
+        .get_mut(builder)
+        .add_status(CharaStatus::Work {
+            turn_left: needed_turn,
+            needed_turn,
+            work: Work::Deconstruct { pos, obj },
+        });
+    game.anim_queue.push_work(1.0);
+    let item = obj_txt(id);
+    game_log_i!("deconstruct-start"; chara=game.gd.chara.get(builder), item=item);
+}
+
+pub fn finish_deconstruct(gd: &mut GameData, cid: CharaId, pos: Vec2d, obj: BuildObj) {
+    // The object may be changed during deconstruction
+    if deconstructible_obj(gd, pos) != Some(obj) {
+        return;
+    }
+    let (id, _, materials) = build_requirements(obj);
+
+    let map = gd.get_current_map_mut();
+    match obj {
+        BuildObj::Wall(_) => map.erase_wall(pos),
+        BuildObj::Deco(_) => map.tile[pos].deco = None,
+        BuildObj::Tile(_) => unreachable!(),
+    }
+    // A deco may remain under a deconstructed wall
+    if map.tile[pos].wall.is_empty() && map.tile[pos].deco.is_none() {
+        map.player_built.retain(|p| *p != pos);
+    }
+    let item = obj_txt(id);
+    game_log_i!("deconstruct-finish"; chara=gd.chara.get(cid), item=item);
+    refund_materials(gd, cid, materials, RULES.creation.deconstruct_refund_rate);
+}
+
+fn refund_materials(gd: &mut GameData, cid: CharaId, materials: &[(String, u32)], rate: f32) {
+    let item_list = gd.get_item_list_mut(ItemListLocation::Chara { cid });
+    for &(ref item_id, n) in materials {
+        let n = (n as f32 * rate) as u32;
+        if n == 0 {
+            continue;
+        }
+        let item = crate::game::item::gen::gen_item_from_id(item_id, 0);
+        item_list.append(item, n);
+    }
+    gd.chara.get_mut(cid).update();
+}
+
+fn is_buildable(gd: &GameData, pos: Vec2d, obj: BuildObj) -> bool {
     let map = gd.get_current_map();
 
     if !map.is_inside(pos) {
         return false;
     }
 
-    if map.tile[pos].wall.is_empty() {
-        let tile = gobj::get_obj(map.tile[pos].main_tile());
-        match tile.kind {
-            TileKind::Ground => true,
-            TileKind::Water => false,
+    let tile = &map.tile[pos];
+    if !tile.wall.is_empty() {
+        return false;
+    }
+
+    match obj {
+        BuildObj::Wall(_) => {
+            if tile.chara.is_some() {
+                return false;
+            }
+            let tile = gobj::get_obj(tile.main_tile());
+            match tile.kind {
+                TileKind::Ground => true,
+                TileKind::Water => false,
+            }
         }
-    } else {
-        false
+        BuildObj::Tile(idx) => tile.main_tile() != idx,
+        BuildObj::Deco(_) => tile.deco.is_none(),
     }
 }
@@ -49,6 +49,7 @@ pub fn create_chara(
 
     chara.update();
     chara.hp = chara.attr.max_hp;
+    chara.mp = chara.attr.max_mp;
     chara.reset_wait_time();
     chara
 }
@@ -37,6 +37,12 @@ impl Chara {
         self.add_skill_exp(kind, RULES.exp.attack, target_level);
     }
 
+    /// Add exp shared from other party members' attacks.
+    fn add_shared_attack_exp(&mut self, kind: SkillKind, target_level: u32) {
+        let exp = RULES.exp.attack as f32 * RULES.exp.party_share;
+        self.add_skill_exp(kind, exp as u32, target_level);
+    }
+
     /// Add exp when damaged.
     fn add_damage_exp(&mut self, damage: i32, attacker_level: u32) {
         let rel_damage = damage as f32 / self.attr.max_hp as f32;
@@ -111,6 +117,11 @@ impl Chara {
         self.hp = std::cmp::min(self.hp + value, self.attr.max_hp);
     }
 
+    /// Recover MP of this character
+    fn recover_mp(&mut self, value: i32) {
+        self.mp = std::cmp::min(self.mp + value, self.attr.max_mp);
+    }
+
     /// Update character parameters by its status
     fn update(&mut self) {
         update::update_encumbrance_status(self);
@@ -155,6 +166,13 @@ impl Chara {
             adj += bonus.1;
         }
 
+        let def = kind.def();
+        if let Some(attr) = def.related_attr {
+            if lv > 0 {
+                adj += (self.attr.get(attr) as f32 * def.attr_bonus) as i32;
+            }
+        }
+
         let adj = adj + (lv as f32 * adj_factor) as i32;
 
         (lv, adj)
@@ -162,6 +180,6 @@ impl Chara {
 
     /// active skill available or not.
     fn active_skill_available(&self, active_skill: &ActiveSkill) -> bool {
-        self.sp > active_skill.cost_sp as f32
+        self.sp > active_skill.cost_sp as f32 && self.mp >= active_skill.cost_mp as i32
     }
 }
@@ -1,9 +1,9 @@
 use super::Game;
 use crate::game::damage::*;
 use crate::game::extrait::*;
+use crate::game::view::for_each_visible_tile;
 use crate::text::ToText;
 use common::gamedata::*;
-use geom::MDistRangeIter;
 use rng::{get_rng, roll_dice, Rng};
 use rules::RULES;
 
@@ -86,6 +86,19 @@ pub fn preturn(game: &mut Game, cid: CharaId) -> bool {
         }
     }
 
+    let chara = game.gd.chara.get_mut(cid);
+    if chara.mp < chara.attr.max_mp {
+        // MP regeneration
+        if get_rng().gen_bool(RULES.chara.mp_regeneration_probability.into()) {
+            let a = std::cmp::max(
+                (chara.attr.wil as f32 * RULES.chara.mp_regeneration_factor) as i32,
+                1,
+            );
+            let v = roll_dice(1, a);
+            chara.recover_mp(v);
+        }
+    }
+
     can_act(game.gd.chara.get_mut(cid))
 }
 
@@ -121,12 +134,16 @@ fn awake_other_npc(game: &mut Game, cid: CharaId) {
     let detection_range = RULES.combat.detection_range;
     let detection_factor = RULES.combat.detection_factor;
 
-    for (distance, pos) in MDistRangeIter::new(center, detection_range) {
-        let map = game.gd.get_current_map();
-        if !map.is_inside(pos) {
-            continue;
-        }
+    // Only characters in sight can detect
+    let mut visible_tiles = Vec::new();
+    for_each_visible_tile(game.gd.get_current_map(), center, detection_range, |pos| {
+        visible_tiles.push(pos)
+    });
+    visible_tiles.sort();
+    visible_tiles.dedup();
 
+    for pos in visible_tiles {
+        let map = game.gd.get_current_map();
         let other_cid = if let Some(other_cid) = map.tile[pos].chara {
             other_cid
         } else {
@@ -142,7 +159,8 @@ fn awake_other_npc(game: &mut Game, cid: CharaId) {
         }
 
         let conceal = game.gd.chara.get(other_cid).skill_level(SkillKind::Conceal);
-        let distance_factor = 1.0 - (distance as f32 / detection_range as f32);
+        let distance = center.distance2(pos).sqrt();
+        let distance_factor = 1.0 - (distance / detection_range as f32);
         let p = (detection as f32 / conceal as f32) * distance_factor * detection_factor;
 
         if p >= 1.0 || rng::gen_bool(p) {
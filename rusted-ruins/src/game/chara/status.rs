@@ -139,6 +139,12 @@ impl CharaStatusExt for CharaStatus {
                 Work::Harvest { item_idx, il } => {
                     crate::game::action::harvest::finish_harvest(gd, cid, item_idx, il);
                 }
+                Work::Build { pos, obj } => {
+                    crate::game::building::finish_build(gd, cid, pos, obj);
+                }
+                Work::Deconstruct { pos, obj } => {
+                    crate::game::building::finish_deconstruct(gd, cid, pos, obj);
+                }
             }
         }
     }
@@ -17,6 +17,8 @@ pub fn update_attributes(chara: &mut Chara) {
     chara.attr.int = base_attr.int as u16;
     chara.attr.wil = base_attr.wil as u16;
     chara.attr.cha = base_attr.cha as u16;
+    chara.attr.max_mp = calc_max_mp(chara);
+    chara.mp = std::cmp::min(chara.mp, chara.attr.max_mp);
 
     // Speed
     let mut factor = 1.0;
@@ -47,6 +49,10 @@ fn calc_max_hp(chara: &mut Chara, ct: &CharaTemplateObject) -> i32 {
     (chara.skill_level(SkillKind::Endurance) as i32 + 8) * ct.base_attr.base_hp / 8
 }
 
+fn calc_max_mp(chara: &Chara) -> i32 {
+    ((chara.attr.int as f32 + chara.attr.wil as f32 / 2.0) * RULES.chara.max_mp_factor) as i32
+}
+
 pub fn update_encumbrance_status(chara: &mut Chara) {
     let cap = calc_carrying_capacity(chara);
     let total_weight = chara.item_list.sum_weight() as f32;
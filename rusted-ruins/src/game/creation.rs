@@ -92,6 +92,7 @@ pub fn finish_creation(
     };
     let il = gd.get_item_list_mut(ill);
     il.append(item, 1);
+    crate::game::quest::count_crafted_item(gd, idx);
 
     let product = obj_txt(&recipe.product);
     let player = gd.chara.get_mut(CharaId::Player);
@@ -124,14 +125,10 @@ pub fn learn_recipe(gd: &mut GameData, il: ItemLocation) -> LearnRecipeResult {
     const RECIPE_KIND_PREFIX: &str = "recipe_kind:";
 
     if title.starts_with(RECIPE_KIND_PREFIX) {
-        let creation_kind = match title.trim_start_matches(RECIPE_KIND_PREFIX) {
-            "art" => CreationKind::Art,
-            "construction" => CreationKind::Construction,
-            "cooking" => CreationKind::Cooking,
-            "craft" => CreationKind::Craft,
-            "pharmacy" => CreationKind::Pharmacy,
-            "smith" => CreationKind::Smith,
-            _ => {
+        let creation_kind: CreationKind = match title.trim_start_matches(RECIPE_KIND_PREFIX).parse()
+        {
+            Ok(creation_kind) => creation_kind,
+            Err(_) => {
                 return LearnRecipeResult::NotRecipeBook;
             }
         };
@@ -220,10 +217,10 @@ pub fn available_material(
 
 /// Recipes that difficulty is zero are available from game start.
 pub fn add_initial_recipes(gd: &mut GameData) {
-    for creation_kind in CreationKind::ALL {
-        for recipe in RULES.creation.recipes(*creation_kind) {
+    for creation_kind in CreationKind::all() {
+        for recipe in RULES.creation.recipes(creation_kind) {
             if recipe.difficulty == 0 {
-                gd.learned_recipes.add(*creation_kind, &recipe.product);
+                gd.learned_recipes.add(creation_kind, &recipe.product);
             }
         }
     }
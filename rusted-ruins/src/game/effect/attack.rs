@@ -256,5 +256,6 @@ pub fn weapon_to_effect(item: &Item) -> Effect {
         anim_img,
         anim_img_shot,
         sound,
+        ..Effect::default()
     }
 }
@@ -36,6 +36,11 @@ pub fn do_effect<T: Into<Target>>(
                     self::recover::recover_hp(game, *cid, power);
                 }
             }
+            EffectKind::RecoverMp => {
+                for cid in &cids {
+                    self::recover::recover_mp(game, *cid, power);
+                }
+            }
             EffectKind::Melee { element } => {
                 for cid in &cids {
                     self::attack::melee_attack(
@@ -128,7 +133,7 @@ pub fn do_effect<T: Into<Target>>(
             }
         }
         EffectAnimKind::Shot => {
-            if !effect.anim_img_shot.is_empty() {
+            if !effect.anim_img_shot.is_empty() && !tiles.is_empty() {
                 let start = game
                     .gd
                     .chara_pos(cause.unwrap())
@@ -174,25 +179,66 @@ fn get_tiles(game: &Game, effect: &Effect, target: Target, cause: Option<CharaId
         }
     };
     let map = game.gd.get_current_map();
-    if let Some(shape) = to_shape(effect, target, cause) {
-        shape
-            .iter()
-            .into_iter()
-            .filter(|pos| map.is_inside(*pos))
-            .collect()
+    if let Some(shape) = to_shape(effect, target, cause, map) {
+        let tiles = shape.iter().into_iter().filter(|pos| map.is_inside(*pos));
+        if let Shape::Line { .. } = shape {
+            // Beams are stopped by the first wall
+            let mut blocked = false;
+            tiles
+                .take_while(|pos| {
+                    let take = !blocked;
+                    blocked = !map.tile[*pos].wall.is_empty();
+                    take
+                })
+                .collect()
+        } else if let Shape::All { .. } = shape {
+            // The caster is not affected by its own effect on the whole map
+            tiles.filter(|pos| Some(*pos) != cause).collect()
+        } else {
+            tiles.collect()
+        }
     } else {
         vec![]
     }
 }
 
-fn to_shape(effect: &Effect, target: Vec2d, _cause: Option<Vec2d>) -> Option<Shape> {
+fn to_shape(effect: &Effect, target: Vec2d, cause: Option<Vec2d>, map: &Map) -> Option<Shape> {
     match effect.shape {
         ShapeKind::OneTile => Some(Shape::OneTile { pos: target }),
-        ShapeKind::Line => unimplemented!(),
+        ShapeKind::Line => {
+            let start = cause?;
+            let d = target - start;
+            let len = std::cmp::max(d.0.abs(), d.1.abs());
+            if len == 0 {
+                return None;
+            }
+            // Extend the line to the effect size. If size is 0, the line ends at the target.
+            let end = if effect.size == 0 {
+                target
+            } else {
+                let ratio = effect.size as f32 / len as f32;
+                start
+                    + Vec2d(
+                        (d.0 as f32 * ratio).round() as i32,
+                        (d.1 as f32 * ratio).round() as i32,
+                    )
+            };
+            Some(Shape::Line { start, end })
+        }
         ShapeKind::Circle => Some(Shape::Circle {
             center: target,
             radius: effect.size,
         }),
+        ShapeKind::Sector => Some(Shape::Sector {
+            center: cause?,
+            target,
+            radius: effect.size,
+            angle: effect.sector_angle,
+        }),
+        ShapeKind::All => Some(Shape::All {
+            top_left: Vec2d(0, 0),
+            bottom_right: Vec2d(map.w as i32 - 1, map.h as i32 - 1),
+        }),
     }
 }
 
@@ -11,3 +11,10 @@ pub fn recover_hp(game: &mut Game, cid: CharaId, power: f32) {
     chara.heal(value);
     crate::chara_log::get_log_mut().push_damage(cid, pos, -value);
 }
+
+pub fn recover_mp(game: &mut Game, cid: CharaId, power: f32) {
+    let value = (RULES.effect.recover_mp_factor * power) as i32;
+    let chara = game.gd.chara.get_mut(cid);
+    chara.recover_mp(value);
+    game_log!("recover-mp"; chara=chara, value=value);
+}
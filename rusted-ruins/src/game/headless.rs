@@ -0,0 +1,423 @@
+//! Run the game without window, sound and animation.
+//! Used for simulations on machines without display.
+
+use super::newgame::NewGameBuilder;
+use super::{Command, DialogOpenRequest, DoPlayerAction, Game, GameState, InfoGetter};
+use crate::game::script_exec::AdvanceScriptResult;
+use common::gamedata::*;
+use geom::Direction;
+use script::{ScriptEngine, TalkText};
+use std::path::Path;
+
+/// Limit of script advancing to avoid infinite loop in talk
+const MAX_TALK_ADVANCE: u32 = 100;
+
+pub struct HeadlessRunner<'s> {
+    game: Game<'s>,
+    /// The number of player turns advanced
+    turns: u32,
+    player_dead: bool,
+}
+
+/// Result of simulation
+#[derive(PartialEq, Eq, Debug, Serialize)]
+pub struct HeadlessSummary {
+    pub turns: u32,
+    pub player_dead: bool,
+    pub level: u32,
+    pub hp: i32,
+    pub max_hp: i32,
+    pub mp: i32,
+    pub max_mp: i32,
+    pub money: i64,
+    pub elapsed_secs: u64,
+}
+
+impl<'s> HeadlessRunner<'s> {
+    /// Start new game with given player name and class.
+    /// If fix_rand is true, the simulation result is reproducible.
+    pub fn new_game(
+        se: ScriptEngine<'s>,
+        player_name: &str,
+        class: CharaClass,
+        fix_rand: bool,
+    ) -> Self {
+        let mut builder = NewGameBuilder::new();
+        builder.set_player_name(player_name);
+        builder.set_chara_class(class);
+        builder.set_fix_rand(fix_rand);
+        let gd = builder.build();
+
+        let mut runner = Self::from_gd(se, gd);
+        runner.game.start_new_game();
+        runner.process_requests();
+        runner
+    }
+
+    /// Load game from the given save directory.
+    /// fix_rand is used if the save data has no RNG state.
+    pub fn load<P: AsRef<Path>>(
+        se: ScriptEngine<'s>,
+        path: P,
+        fix_rand: bool,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let gd = GameData::load(&path, fix_rand)?;
+        let mut runner = Self::from_gd(se, gd);
+        runner.game.set_save_dir(path.as_ref().to_owned());
+        Ok(runner)
+    }
+
+    fn from_gd(se: ScriptEngine<'s>, gd: GameData) -> Self {
+        let mut game = Game::new(gd, se);
+        game.update_before_player_turn();
+        HeadlessRunner {
+            game,
+            turns: 0,
+            player_dead: false,
+        }
+    }
+
+    /// Advance n player turns.
+    /// The player's actions are taken from commands, and decided by auto player after that.
+    /// Returns the number of advanced turns.
+    pub fn advance_turns<I: Iterator<Item = Command>>(&mut self, n: u32, commands: I) -> u32 {
+        advance_turns(self, n, commands)
+    }
+
+    /// Execute a command for the player.
+    /// Commands for opening windows are ignored.
+    pub fn exec_command(&mut self, command: &Command) {
+        if self.game.get_state() != GameState::PlayerTurn {
+            return;
+        }
+        let mut pa = DoPlayerAction::new(&mut self.game);
+
+        match *command {
+            Command::Move { dir } => {
+                pa.try_move(dir);
+            }
+            Command::MoveTo { dest } => {
+                pa.move_to(dest);
+            }
+            Command::Shoot { target } => {
+                pa.shoot(target);
+            }
+            Command::UseTool { target } => {
+                pa.use_tool(target);
+            }
+            Command::Enter => {
+                if pa.gd().on_map_entrance() {
+                    pa.goto_next_floor(Direction::none(), true);
+                }
+            }
+            Command::ActionShortcut(n) => {
+                pa.exec_shortcut(n);
+            }
+            _ => {
+                warn!("command {:?} is ignored in headless mode", command);
+            }
+        }
+        self.process_requests();
+    }
+
+    /// Let auto player act
+    pub fn exec_auto_player(&mut self) {
+        if self.game.get_state() != GameState::PlayerTurn {
+            return;
+        }
+        super::npc::process_auto_player_turn(&mut self.game);
+        self.game.finish_player_turn();
+        self.process_requests();
+    }
+
+    pub fn summary(&self) -> HeadlessSummary {
+        let gd = &self.game.gd;
+        let player = gd.chara.get(CharaId::Player);
+        HeadlessSummary {
+            turns: self.turns,
+            player_dead: self.player_dead,
+            level: player.level,
+            hp: player.hp,
+            max_hp: player.attr.max_hp,
+            mp: player.mp,
+            max_mp: player.attr.max_mp,
+            money: gd.player.money(),
+            elapsed_secs: gd.time.current_time().as_secs(),
+        }
+    }
+
+    /// Skip animations and process requests to UI
+    fn process_requests(&mut self) {
+        while self.game.pop_animation().is_some() {}
+        while self.game.pop_ui_request().is_some() {}
+
+        while let Some(req) = self.game.pop_dialog_open_request() {
+            match req {
+                DialogOpenRequest::YesNo { mut callback, .. } => {
+                    callback(&mut DoPlayerAction::new(&mut self.game), true);
+                }
+                DialogOpenRequest::Talk { talk_text, .. } => {
+                    self.advance_talk(talk_text);
+                }
+                DialogOpenRequest::GameOver => {
+                    self.player_dead = true;
+                }
+                _ => (),
+            }
+            while self.game.pop_animation().is_some() {}
+            while self.game.pop_ui_request().is_some() {}
+        }
+    }
+
+    /// Advance talk until the end. The last choice is chosen because it is usually for leaving.
+    /// Dialogs requested by the script such as shops are skipped.
+    fn advance_talk(&mut self, talk_text: TalkText) {
+        let last_choice =
+            |talk_text: &TalkText| talk_text.choices.len().checked_sub(1).map(|n| n as u32);
+        let mut choice = last_choice(&talk_text);
+
+        for _ in 0..MAX_TALK_ADVANCE {
+            match self.game.advance_script(choice) {
+                AdvanceScriptResult::UpdateTalkText(talk_text) => {
+                    choice = last_choice(&talk_text);
+                }
+                AdvanceScriptResult::Continue => {
+                    let _ = self.game.pop_dialog_open_request();
+                    choice = None;
+                }
+                AdvanceScriptResult::Quit => {
+                    return;
+                }
+            }
+        }
+        warn!("talk did not finish in headless mode");
+    }
+}
+
+/// Steps of simulation used by advance_turns()
+trait TurnStepper {
+    /// The number of player turns advanced
+    fn turns(&self) -> u32;
+
+    fn player_dead(&self) -> bool;
+
+    fn exec_command(&mut self, command: &Command);
+
+    fn exec_auto_player(&mut self);
+
+    /// Process turns of other characters until the next player turn
+    fn advance_to_player_turn(&mut self);
+}
+
+impl<'s> TurnStepper for HeadlessRunner<'s> {
+    fn turns(&self) -> u32 {
+        self.turns
+    }
+
+    fn player_dead(&self) -> bool {
+        self.player_dead
+    }
+
+    fn exec_command(&mut self, command: &Command) {
+        HeadlessRunner::exec_command(self, command);
+    }
+
+    fn exec_auto_player(&mut self) {
+        HeadlessRunner::exec_auto_player(self);
+    }
+
+    fn advance_to_player_turn(&mut self) {
+        if self.game.get_state() != GameState::WaitingForNextTurn {
+            return;
+        }
+        self.turns += 1;
+
+        while self.game.get_state() == GameState::WaitingForNextTurn && !self.player_dead {
+            self.game.advance_turn();
+            self.process_requests();
+        }
+    }
+}
+
+/// Feed commands to the player, and let auto player act after the commands run out.
+/// Commands not finishing the player turn do not count as advanced turns.
+fn advance_turns<S: TurnStepper, I: Iterator<Item = Command>>(
+    stepper: &mut S,
+    n: u32,
+    mut commands: I,
+) -> u32 {
+    let start_turns = stepper.turns();
+
+    while stepper.turns() - start_turns < n && !stepper.player_dead() {
+        if let Some(command) = commands.next() {
+            stepper.exec_command(&command);
+        } else {
+            stepper.exec_auto_player();
+        }
+        stepper.advance_to_player_turn();
+    }
+
+    stepper.turns() - start_turns
+}
+
+/// Run headless simulation by command line arguments, and print the summary as JSON.
+pub fn run(se: ScriptEngine, config: &crate::config::HeadlessConfig) {
+    use rules::RULES;
+
+    let mut runner = if let Some(path) = config.load.as_ref() {
+        match HeadlessRunner::load(se, path, crate::config::CONFIG.fix_rand) {
+            Ok(runner) => runner,
+            Err(e) => {
+                error!("Failed to load \"{}\": {}", path.display(), e);
+                return;
+            }
+        }
+    } else {
+        let class_choices = &RULES.newgame.class_choices;
+        let class = if let Some(class) = config.class.as_ref() {
+            if let Some(class) = class_choices.iter().find(|c| c.as_str() == class) {
+                *class
+            } else {
+                error!("Unknown character class \"{}\"", class);
+                return;
+            }
+        } else {
+            class_choices[0]
+        };
+        HeadlessRunner::new_game(se, "headless", class, crate::config::CONFIG.fix_rand)
+    };
+
+    let commands = if let Some(path) = config.commands.as_ref() {
+        match crate::replay::read_commands(path) {
+            Ok(commands) => commands,
+            Err(e) => {
+                error!("Failed to read commands \"{}\": {}", path.display(), e);
+                return;
+            }
+        }
+    } else {
+        Default::default()
+    };
+
+    runner.advance_turns(config.turns, commands.into_iter());
+
+    match serde_json::to_string(&runner.summary()) {
+        Ok(s) => println!("{}", s),
+        Err(e) => error!("{}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records the steps instead of running the game
+    #[derive(Default)]
+    struct MockStepper {
+        turns: u32,
+        /// The player dies at this turn
+        dead_at: Option<u32>,
+        player_turn_finished: bool,
+        log: Vec<Option<Command>>,
+    }
+
+    impl TurnStepper for MockStepper {
+        fn turns(&self) -> u32 {
+            self.turns
+        }
+
+        fn player_dead(&self) -> bool {
+            self.dead_at.is_some_and(|t| self.turns >= t)
+        }
+
+        fn exec_command(&mut self, command: &Command) {
+            // Commands for windows do not finish the player turn in headless mode
+            self.player_turn_finished = !matches!(command, Command::OpenStatusWin);
+            self.log.push(Some(command.clone()));
+        }
+
+        fn exec_auto_player(&mut self) {
+            self.player_turn_finished = true;
+            self.log.push(None);
+        }
+
+        fn advance_to_player_turn(&mut self) {
+            if self.player_turn_finished {
+                self.turns += 1;
+                self.player_turn_finished = false;
+            }
+        }
+    }
+
+    #[test]
+    fn advance_turns_test() {
+        let move_north = Command::Move { dir: Direction::N };
+        let commands = vec![move_north.clone(), Command::OpenStatusWin, Command::Enter];
+
+        let mut stepper = MockStepper::default();
+        assert_eq!(advance_turns(&mut stepper, 4, commands.into_iter()), 4);
+        assert_eq!(
+            stepper.log,
+            vec![
+                Some(move_north.clone()),
+                Some(Command::OpenStatusWin),
+                Some(Command::Enter),
+                None,
+                None,
+            ]
+        );
+
+        // Remaining commands are used in the next call
+        let mut commands = vec![Command::Enter, move_north.clone()].into_iter();
+        let mut stepper = MockStepper::default();
+        assert_eq!(advance_turns(&mut stepper, 1, &mut commands), 1);
+        assert_eq!(advance_turns(&mut stepper, 1, &mut commands), 1);
+        assert_eq!(advance_turns(&mut stepper, 1, &mut commands), 1);
+        assert_eq!(
+            stepper.log,
+            vec![Some(Command::Enter), Some(move_north), None]
+        );
+    }
+
+    #[test]
+    fn advance_turns_player_dead_test() {
+        let mut stepper = MockStepper {
+            dead_at: Some(3),
+            ..MockStepper::default()
+        };
+        assert_eq!(advance_turns(&mut stepper, 10, std::iter::empty()), 3);
+        assert_eq!(stepper.log, vec![None; 3]);
+        assert_eq!(advance_turns(&mut stepper, 10, std::iter::empty()), 0);
+    }
+
+    #[test]
+    #[ignore = "requires paks and rules in the assets directory"]
+    fn headless_reproducibility_test() {
+        const N_TURNS: u32 = 100;
+
+        if std::env::var_os("RUSTED_RUINS_ASSETS_DIR").is_none() {
+            std::env::set_var(
+                "RUSTED_RUINS_ASSETS_DIR",
+                concat!(env!("CARGO_MANIFEST_DIR"), "/../assets"),
+            );
+        }
+        crate::init_lazy();
+        crate::init_obj();
+        crate::init_rules();
+        crate::game::script_methods::init();
+        let _audio_context = audio::init_with_backend(Box::new(audio::NullBackend));
+
+        let simulate = || {
+            script::enter(|se| {
+                let class = rules::RULES.newgame.class_choices[0];
+                let mut runner = HeadlessRunner::new_game(se, "headless", class, true);
+                runner.advance_turns(N_TURNS, std::iter::empty());
+                runner.summary()
+            })
+        };
+
+        let summary = simulate();
+        assert!(summary.turns == N_TURNS || summary.player_dead);
+        assert_eq!(summary, simulate());
+    }
+}
@@ -0,0 +1,113 @@
+//! Functions for container items
+
+use crate::game::extrait::*;
+use common::gamedata::*;
+
+/// Get the capacity and the weight rate of given container item
+pub fn container_attr(item: &Item) -> Option<(u32, u32)> {
+    item.obj().attrs.iter().find_map(|attr| match *attr {
+        ItemObjAttr::Container {
+            capacity,
+            weight_rate,
+        } => Some((capacity, weight_rate)),
+        _ => None,
+    })
+}
+
+/// Weight of the contents added to the container weight
+pub fn contents_weight(item: &Item) -> u32 {
+    let contents = if let Some(contents) = item.container() {
+        contents
+    } else {
+        return 0;
+    };
+    let weight_rate = container_attr(item).map_or(100, |(_, weight_rate)| weight_rate);
+    (contents.sum_weight() as u64 * weight_rate as u64 / 100) as u32
+}
+
+/// Put items into the container.
+/// Returns the location of the container after moving, because moving may shift it.
+/// Returns None if the container does not exist.
+pub fn put_in(
+    gd: &mut GameData,
+    container: ItemLocation,
+    il: ItemLocation,
+) -> Option<ItemLocation> {
+    if !gd.container_exists(container) {
+        warn!("container at {:?} does not exist", container);
+        return None;
+    }
+    let (item, n) = gd.get_item(il);
+    let container_item = gd.get_item(container).0;
+
+    if item.kind == ItemKind::Container {
+        game_log_i!("container-nested"; item=item);
+        return Some(container);
+    }
+    let capacity = container_attr(container_item).map_or(0, |(capacity, _)| capacity);
+    let contents_w = container_item
+        .container()
+        .map_or(0, |list| list.sum_weight());
+    if contents_w + item.w() * n > capacity {
+        game_log_i!("container-full"; container=container_item);
+        return Some(container);
+    }
+
+    game_log_i!("container-put-in"; chara=gd.chara.get(CharaId::Player), item=item, container=container_item);
+    let container = gd.move_item_to_container(il, container, n);
+    gd.chara.get_mut(CharaId::Player).update();
+    Some(container)
+}
+
+/// Take items out of the container to the player's inventory.
+/// Returns the location of the container after moving, because moving may shift it.
+/// Returns None if the container does not exist.
+pub fn take_out(
+    gd: &mut GameData,
+    container: ItemLocation,
+    il: ItemLocation,
+) -> Option<ItemLocation> {
+    if !gd.container_exists(container) {
+        warn!("container at {:?} does not exist", container);
+        return None;
+    }
+    let n = gd.get_item(il).1;
+    game_log_i!("container-take-out"; chara=gd.chara.get(CharaId::Player), item=gd.get_item(il).0, container=gd.get_item(container).0);
+    let item = gd.remove_item_and_get(il, n);
+
+    let dest = gd.get_item_list_mut(ItemListLocation::PLAYER);
+    let len = dest.len();
+    let i = dest.append(item, n) as u32;
+    let inserted = dest.len() > len;
+    gd.chara.get_mut(CharaId::Player).update();
+
+    if container.0 == ItemListLocation::PLAYER && inserted && i <= container.1 {
+        Some((container.0, container.1 + 1))
+    } else {
+        Some(container)
+    }
+}
+
+/// Get the site id of the player's base if the player is in it
+pub fn current_stash(gd: &GameData) -> Option<SiteId> {
+    let mid = gd.get_current_mapid();
+    if mid.is_region_map() {
+        return None;
+    }
+    let sid = mid.sid();
+    gd.region.get_site(sid).stash().map(|_| sid)
+}
+
+/// Put items into the stash at the player's base
+pub fn put_in_stash(gd: &mut GameData, sid: SiteId, il: ItemLocation) {
+    game_log_i!("stash-put-in"; chara=gd.chara.get(CharaId::Player), item=gd.get_item(il).0);
+    gd.move_item(il, ItemListLocation::Stash { sid }, ItemMoveNum::All);
+    gd.chara.get_mut(CharaId::Player).update();
+}
+
+/// Take items out of the stash to the player's inventory
+pub fn take_out_from_stash(gd: &mut GameData, il: ItemLocation) {
+    game_log_i!("stash-take-out"; chara=gd.chara.get(CharaId::Player), item=gd.get_item(il).0);
+    gd.move_item(il, ItemListLocation::PLAYER, ItemMoveNum::All);
+    gd.chara.get_mut(CharaId::Player).update();
+}
@@ -203,13 +203,13 @@ fn gen_readable_item(item: &mut Item, item_obj: &ItemObject) {
 /// Generate a skill learning item
 fn gen_skill_lerning_item(item: &mut Item, _item_obj: &ItemObject) {
     let skill_kind: SkillKind = if rng::gen_range(0..3) == 0 {
-        CreationKind::ALL
+        CreationKind::all()
             .choose(&mut rng::GameRng)
             .copied()
             .unwrap()
             .into()
     } else {
-        WeaponKind::ALL
+        WeaponKind::all()
             .choose(&mut rng::GameRng)
             .copied()
             .unwrap()
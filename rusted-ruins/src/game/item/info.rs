@@ -1,4 +1,4 @@
-use crate::game::extrait::ItemExt;
+use crate::game::extrait::{ItemExt, ItemListExt};
 use crate::text::ToText;
 use common::gamedata::*;
 
@@ -62,7 +62,16 @@ impl ItemInfoText {
                 desc_text.push((UI_IMG_ID_ITEM_INFO, t));
             }
             ItemKind::Tool => {}
-            ItemKind::Container => {}
+            ItemKind::Container => {
+                if let Some((capacity, _)) = super::container::container_attr(item) {
+                    let w = item.container().map_or(0, |list| list.sum_weight());
+                    let w = format!("{:.1}", w as f32 / 1000.0);
+                    let capacity = format!("{:.1}", capacity as f32 / 1000.0);
+                    let t = misc_txt_format!(
+                        "item_info_text-container"; w=w, capacity=capacity);
+                    desc_text.push((UI_IMG_ID_ITEM_INFO, t));
+                }
+            }
             ItemKind::Special => {}
             ItemKind::Readable => {}
             ItemKind::Material => {}
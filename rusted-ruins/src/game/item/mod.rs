@@ -1,3 +1,4 @@
+pub mod container;
 pub mod filter;
 pub mod gen;
 pub mod info;
@@ -119,19 +120,15 @@ impl Item {
         (item_obj.basic_price as f32 * factor) as i64
     }
 
-    /// Calculate item selling price
-    fn selling_price(&self) -> i64 {
-        self.price() / 2
-    }
-
     fn w(&self) -> u32 {
         let item_obj = gobj::get_obj(self.idx);
 
-        if let Some((_, material)) = self.material() {
+        let w = if let Some((_, material)) = self.material() {
             (item_obj.w as f32 * material.w) as u32
         } else {
             item_obj.w
-        }
+        };
+        w + container::contents_weight(self)
     }
 
     fn charge(&self) -> Option<u32> {
@@ -240,7 +237,7 @@ impl ItemList {
         self.iter()
             .map(|(item, n)| {
                 let obj = item.obj();
-                obj.w * n
+                (obj.w + container::contents_weight(item)) * n
             })
             .sum()
     }
@@ -1,5 +1,6 @@
 pub mod builder;
 pub mod from_template;
+pub mod route;
 pub mod search;
 pub mod tile_info;
 mod update;
@@ -109,21 +110,39 @@ pub fn switch_map(game: &mut Game, destination: Destination) {
     gd.get_current_map_mut()
         .locate_chara(CharaId::Player, new_player_pos);
 
-    // Remove temp site
-    if !old_mid.is_region_map() {
+    // Temp site is removed if new site is not the same as old site.
+    let remove_old_site = !old_mid.is_region_map()
+        && old_mid.sid().kind == SiteKind::Temp
+        && (new_mid.is_region_map() || new_mid.sid() != old_mid.sid());
+
+    if remove_old_site {
+        // Party members may be waiting on other floors of the site
         let sid = old_mid.sid();
-        if sid.kind == SiteKind::Temp {
-            // If new site is not the same as old site.
-            if new_mid.is_region_map() || new_mid.sid() != sid {
-                gd.region.remove_site(sid);
-            }
+        for floor in 0..gd.region.get_site(sid).floor_num() {
+            gd.region
+                .preload_map(MapId::SiteMap { sid, floor }, save_dir.join("maps"));
         }
     }
 
+    super::party::move_party_members(gd, old_mid, remove_old_site);
+
+    // Remove temp site
+    if remove_old_site {
+        gd.region.remove_site(old_mid.sid());
+    }
+
     crate::audio::play_sound("floor-change");
     crate::audio::play_music(&gd.get_current_map().music);
     update::update_map(game);
     super::view::update_view_map(game);
+
+    let gd = &mut game.gd;
+    super::quest::check_arrival(gd, new_mid);
+    super::script_hook::push_event(gd, ScriptEvent::EnterMap { mid: new_mid });
+    if !new_mid.is_region_map() && (old_mid.is_region_map() || old_mid.sid() != new_mid.sid()) {
+        let sid = new_mid.sid();
+        super::script_hook::push_event(gd, ScriptEvent::EnterSite { sid });
+    }
 }
 
 /// Convert Destination to map id.
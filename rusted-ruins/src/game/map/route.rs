@@ -0,0 +1,45 @@
+//! Route search on the current map
+
+use super::MapExt;
+use crate::game::InfoGetter;
+use common::gamedata::*;
+use geom::*;
+use std::cell::RefCell;
+
+thread_local!(static ASTAR: RefCell<AStar> = RefCell::new(AStar::new()));
+
+/// Additional cost to pass through a tile occupied by other character
+const CHARA_OCCUPIED_COST: u32 = 4;
+
+/// Search the lowest cost route from the character to dest on the current map
+pub fn route_for_chara(
+    gd: &GameData,
+    cid: CharaId,
+    dest: Vec2d,
+    max_step: u32,
+) -> Option<Vec<Vec2d>> {
+    let start = gd.chara_pos(cid)?;
+    let map = gd.get_current_map();
+    let chara = gd.chara.get(cid);
+
+    ASTAR.with(|astar| {
+        astar
+            .borrow_mut()
+            .route(map.w, map.h, start, dest, max_step, |pos| {
+                if !map.is_passable(chara, pos) {
+                    None
+                } else if map.tile[pos].chara.is_some() {
+                    Some(1 + CHARA_OCCUPIED_COST)
+                } else {
+                    Some(1)
+                }
+            })
+    })
+}
+
+/// Returns the direction of the first step of the route to dest
+pub fn dir_to_dest(gd: &GameData, cid: CharaId, dest: Vec2d, max_step: u32) -> Option<Direction> {
+    let route = route_for_chara(gd, cid, dest, max_step)?;
+    let next_pos = route.get(1)?;
+    Some(dir_by_2pos(route[0], *next_pos))
+}
@@ -14,6 +14,9 @@ pub fn update_map(game: &mut Game) {
     if duration > Duration::from_minutes(RULES.npc.map_switch_recover_minutes.into()) {
         recover_npc(&mut game.gd);
     }
+
+    let hour = game.gd.time.current_date().hour;
+    crate::game::npc::update_ai_schedule(&mut game.gd, hour);
 }
 
 pub fn recover_npc(gd: &mut GameData) {
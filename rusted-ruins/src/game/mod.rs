@@ -2,7 +2,7 @@ mod action;
 pub mod active_skill;
 mod anim_queue;
 mod animation;
-mod building;
+pub mod building;
 pub mod chara;
 pub mod command;
 pub mod creation;
@@ -11,16 +11,20 @@ mod debug_command;
 mod dungeon_gen;
 pub mod effect;
 pub mod frequent_tex;
+pub mod headless;
 mod infogetter;
 pub mod item;
 pub mod map;
 pub mod newgame;
 mod npc;
+pub mod party;
+mod periodic;
 pub mod playeract;
 pub mod quest;
 mod region;
 pub mod saveload;
 pub mod script_exec;
+mod script_hook;
 pub mod script_methods;
 pub mod shop;
 pub mod site;
@@ -74,8 +78,6 @@ impl<'s> Game<'s> {
     pub fn new(gd: GameData, se: ScriptEngine<'s>) -> Game<'s> {
         let save_dir = self::saveload::get_each_save_dir(&gd);
 
-        rng::reseed(crate::config::CONFIG.fix_rand);
-
         Game {
             gd,
             state: GameState::PlayerTurn,
@@ -210,10 +212,27 @@ pub enum DialogOpenRequest {
     ShopBuy {
         cid: CharaId,
     },
-    ShopSell,
+    ShopSell {
+        cid: CharaId,
+    },
+    ContainerPutIn {
+        il: ItemLocation,
+    },
+    ContainerTakeOut {
+        il: ItemLocation,
+    },
+    StashPutIn {
+        sid: SiteId,
+    },
+    StashTakeOut {
+        sid: SiteId,
+    },
     RegisterAsShortcut {
         shortcut: ActionShortcut,
     },
+    Build {
+        pos: Vec2d,
+    },
     PickUpItem,
     Quest,
     GameOver,
@@ -8,6 +8,7 @@ pub struct NewGameBuilder {
     gd: GameData,
     player_name: Option<String>,
     chara_class: Option<CharaClass>,
+    fix_rand: bool,
 }
 
 impl NewGameBuilder {
@@ -16,6 +17,7 @@ impl NewGameBuilder {
             gd: GameData::empty(),
             player_name: None,
             chara_class: None,
+            fix_rand: crate::config::CONFIG.fix_rand,
         }
     }
 
@@ -27,8 +29,13 @@ impl NewGameBuilder {
         self.chara_class = Some(chara_class);
     }
 
+    /// Fixes the state of RNG when the game is built
+    pub fn set_fix_rand(&mut self, fix_rand: bool) {
+        self.fix_rand = fix_rand;
+    }
+
     pub fn build(mut self) -> GameData {
-        rng::reseed(crate::config::CONFIG.fix_rand);
+        rng::reseed(self.fix_rand);
         {
             let mut gd = &mut self.gd;
 
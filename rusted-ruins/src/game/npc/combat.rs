@@ -0,0 +1,225 @@
+//! Combat state AI by behaviors defined in rules
+
+use super::move_to_target_enemy;
+use crate::game::action;
+use crate::game::active_skill::use_active_skill;
+use crate::game::extrait::*;
+use crate::game::map::MapExt;
+use crate::game::target::Target;
+use crate::game::Game;
+use common::gamedata::*;
+use common::gobj;
+use geom::*;
+use rng::*;
+use rules::{npc_ai::*, RULES};
+
+/// Choose a behavior by scores and do it.
+/// If the chosen behavior cannot be done, tries the next one.
+/// Returns false if no behavior is done.
+pub fn process_combat_behaviors(
+    game: &mut Game,
+    cid: CharaId,
+    target: CharaId,
+    ai_rule: &NpcAi,
+) -> bool {
+    let mut candidates: Vec<(f32, &CombatBehavior)> = ai_rule
+        .combat_behaviors
+        .iter()
+        .filter(|b| {
+            b.conditions
+                .iter()
+                .all(|&c| check_condition(game, cid, target, c))
+        })
+        .map(|b| {
+            let r = if b.score_random > 0.0 {
+                gen_range(0.0..b.score_random)
+            } else {
+                0.0
+            };
+            (b.score + r, b)
+        })
+        .collect();
+    candidates.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    candidates
+        .into_iter()
+        .any(|(_, b)| do_behavior(game, cid, target, ai_rule, &b.kind))
+}
+
+fn check_condition(game: &Game, cid: CharaId, target: CharaId, condition: AiCondition) -> bool {
+    let chara = game.gd.chara.get(cid);
+    match condition {
+        AiCondition::HpBelow(r) => hp_ratio(chara) < r,
+        AiCondition::HpAbove(r) => hp_ratio(chara) >= r,
+        AiCondition::AllyHpBelow(r) => most_injured_ally(game, cid, false)
+            .is_some_and(|ally| hp_ratio(game.gd.chara.get(ally)) < r),
+        AiCondition::TargetNearer(d) => target_distance(game, cid, target) < d as i32,
+        AiCondition::TargetFarther(d) => target_distance(game, cid, target) >= d as i32,
+        AiCondition::HasRangedWeapon => chara.equip.item(EquipSlotKind::RangedWeapon, 0).is_some(),
+        AiCondition::Probability(p) => gen_bool(p),
+    }
+}
+
+fn do_behavior(
+    game: &mut Game,
+    cid: CharaId,
+    target: CharaId,
+    ai_rule: &NpcAi,
+    kind: &CombatBehaviorKind,
+) -> bool {
+    match kind {
+        CombatBehaviorKind::Approach => {
+            move_to_target_enemy(game, cid, ai_rule, target);
+            true
+        }
+        CombatBehaviorKind::KeepDistance { min, max } => {
+            let distance = target_distance(game, cid, target);
+            if distance < *min as i32 {
+                flee(game, cid, target)
+            } else if distance > *max as i32 {
+                move_to_target_enemy(game, cid, ai_rule, target);
+                true
+            } else {
+                action::shoot_target(game, cid, target)
+            }
+        }
+        CombatBehaviorKind::Flee => flee(game, cid, target),
+        CombatBehaviorKind::UseActiveSkill(target_mode) => {
+            use_active_skill_by_target_mode(game, cid, target, target_mode)
+        }
+        CombatBehaviorKind::UseItem(effect) => use_item(game, cid, target, *effect),
+    }
+}
+
+fn hp_ratio(chara: &Chara) -> f32 {
+    chara.hp as f32 / chara.attr.max_hp as f32
+}
+
+fn target_distance(game: &Game, cid: CharaId, target: CharaId) -> i32 {
+    match (game.gd.chara_pos(cid), game.gd.chara_pos(target)) {
+        (Some(pos), Some(target_pos)) => pos.mdistance(target_pos),
+        _ => i32::MAX,
+    }
+}
+
+/// Search the ally who has the lowest HP ratio in the detection range
+fn most_injured_ally(game: &Game, cid: CharaId, include_self: bool) -> Option<CharaId> {
+    let center = game.gd.chara_pos(cid)?;
+    game.gd
+        .get_charas_on_map()
+        .into_iter()
+        .filter(|&other| {
+            if other == cid {
+                return include_self;
+            }
+            game.gd.chara_relation(cid, other) == Relationship::Ally
+                && game
+                    .gd
+                    .chara_pos(other)
+                    .is_some_and(|pos| center.mdistance(pos) <= RULES.combat.detection_range)
+        })
+        .map(|other| (other, hp_ratio(game.gd.chara.get(other))))
+        .filter(|&(_, r)| r < 1.0)
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(other, _)| other)
+}
+
+/// Move to the adjacent tile farthest from the target
+fn flee(game: &mut Game, cid: CharaId, target: CharaId) -> bool {
+    let (pos, target_pos) = match (game.gd.chara_pos(cid), game.gd.chara_pos(target)) {
+        (Some(pos), Some(target_pos)) => (pos, target_pos),
+        _ => return false,
+    };
+    let map = game.gd.get_current_map();
+    let chara = game.gd.chara.get(cid);
+    let dir = Direction::EIGHT_DIRS
+        .iter()
+        .copied()
+        .filter(|dir| {
+            let dest = pos + dir.as_vec();
+            map.is_passable(chara, dest) && map.tile[dest].chara.is_none()
+        })
+        .max_by_key(|dir| (pos + dir.as_vec()).mdistance(target_pos))
+        .filter(|dir| (pos + dir.as_vec()).mdistance(target_pos) > pos.mdistance(target_pos));
+
+    if let Some(dir) = dir {
+        action::try_move(game, cid, dir)
+    } else {
+        false
+    }
+}
+
+/// Use one of the character's active skills with given target mode
+fn use_active_skill_by_target_mode(
+    game: &mut Game,
+    cid: CharaId,
+    target: CharaId,
+    target_mode: &TargetMode,
+) -> bool {
+    let chara = game.gd.chara.get(cid);
+    let ct: &CharaTemplateObject = gobj::get_obj(chara.template);
+    let skills: Vec<&ActiveSkillId> = ct
+        .active_skills
+        .iter()
+        .filter(|id| {
+            RULES.active_skills.get(id).is_some_and(|skill| {
+                skill.effect.target_mode == *target_mode && chara.active_skill_available(skill)
+            })
+        })
+        .collect();
+    let active_skill_id = if let Some(id) = skills.choose(&mut get_rng()) {
+        *id
+    } else {
+        return false;
+    };
+
+    let skill_target = match target_mode {
+        TargetMode::Enemy => target,
+        TargetMode::Ally => {
+            if let Some(ally) = most_injured_ally(game, cid, true) {
+                ally
+            } else {
+                return false;
+            }
+        }
+        TargetMode::None | TargetMode::Player => cid,
+    };
+    use_active_skill(game, active_skill_id, cid, skill_target)
+}
+
+/// Use an item in the character's inventory that has the effect
+fn use_item(game: &mut Game, cid: CharaId, target: CharaId, effect: AiItemEffect) -> bool {
+    let ill = ItemListLocation::Chara { cid };
+    let has_kind = |effect_obj: &Option<Effect>, kind: EffectKind| {
+        effect_obj.as_ref().is_some_and(|e| e.kind.contains(&kind))
+    };
+    let i = game.gd.get_item_list(ill).iter().position(|(item, _)| {
+        let obj = item.obj();
+        match effect {
+            AiItemEffect::RecoverHp => has_kind(&obj.medical_effect, EffectKind::RecoverHp),
+            AiItemEffect::RecoverSp => has_kind(&obj.medical_effect, EffectKind::RecoverSp),
+            AiItemEffect::RecoverMp => has_kind(&obj.medical_effect, EffectKind::RecoverMp),
+            AiItemEffect::Attack => {
+                obj.kind == ItemKind::MagicDevice
+                    && item.charge().is_some_and(|n| n >= 1)
+                    && obj
+                        .magical_effect
+                        .as_ref()
+                        .is_some_and(|e| e.target_mode == TargetMode::Enemy)
+            }
+        }
+    });
+    let il = if let Some(i) = i {
+        (ill, i as u32)
+    } else {
+        return false;
+    };
+
+    match game.gd.get_item(il).0.obj().kind {
+        ItemKind::Potion => action::drink_item(game, il, cid),
+        ItemKind::Food => action::eat_item(game, il, cid),
+        ItemKind::MagicDevice => action::release_item(game, il, cid, Target::Chara(target)),
+        _ => return false,
+    }
+    true
+}
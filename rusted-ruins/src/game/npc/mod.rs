@@ -1,10 +1,10 @@
 //! Functions for NPC's AI and actions
 
+mod combat;
 pub mod map_search;
 
 use super::action;
 use super::active_skill::use_active_skill;
-use super::extrait::*;
 use super::{Game, InfoGetter};
 use common::gamedata::*;
 use common::gobj;
@@ -13,12 +13,52 @@ use rng::*;
 use rules::{npc_ai::*, RULES};
 
 pub fn process_npc_turn(game: &mut Game, cid: CharaId) {
+    // The target may be dead or have left the map
+    if let AiState::Combat { target } = game.gd.chara.get(cid).ai.state {
+        if game.gd.chara_pos(target).is_none() {
+            game.gd.chara.get_mut(cid).ai.state = AiState::Normal;
+        }
+    }
+
+    if let Some(order) = game.gd.party.order(cid) {
+        process_party_member_turn(game, cid, order);
+        return;
+    }
+
     match game.gd.chara.get(cid).ai.state {
         AiState::Normal => process_npc_turn_normal(game, cid),
         AiState::Combat { .. } => process_npc_turn_combat(game, cid),
     }
 }
 
+/// Switch AI kinds of NPCs on the current map by the schedules of their default AI.
+/// Party members follow the player's orders instead of schedules.
+pub fn update_ai_schedule(gd: &mut GameData, hour: u16) {
+    for cid in gd.get_charas_on_map() {
+        if cid == CharaId::Player || gd.party.contains(cid) {
+            continue;
+        }
+        let chara = gd.chara.get_mut(cid);
+        let ct: &CharaTemplateObject = gobj::get_obj(chara.template);
+        let default_kind = ct.default_ai_kind;
+        let schedule = &RULES.npc_ai.get(default_kind).schedule;
+        if schedule.is_empty() {
+            continue;
+        }
+        chara.ai.kind = schedule
+            .iter()
+            .find(|&&(start, end, _)| {
+                if start <= end {
+                    start <= hour && hour < end
+                } else {
+                    start <= hour || hour < end
+                }
+            })
+            .map(|&(_, _, kind)| kind)
+            .unwrap_or(default_kind);
+    }
+}
+
 fn process_npc_turn_normal(game: &mut Game, cid: CharaId) {
     let chara = game.gd.chara.get(cid);
     let ai = &chara.ai;
@@ -56,6 +96,12 @@ fn process_npc_turn_combat(game: &mut Game, cid: CharaId) {
         AiState::Combat { target } => target,
         _ => unreachable!(),
     };
+    if !ai_rule.combat_behaviors.is_empty() {
+        if !combat::process_combat_behaviors(game, cid, target, ai_rule) {
+            move_to_target_enemy(game, cid, ai_rule, target);
+        }
+        return;
+    }
     if rng::gen_bool(ai_rule.approach_enemy_prob) {
         move_to_target_enemy(game, cid, ai_rule, target);
     } else if rng::gen_bool(ai_rule.active_skill_prob) {
@@ -67,6 +113,83 @@ fn process_npc_turn_combat(game: &mut Game, cid: CharaId) {
     }
 }
 
+/// Party members act by the player's order
+fn process_party_member_turn(game: &mut Game, cid: CharaId, order: PartyOrder) {
+    /// Party members following the player try to stay within this distance
+    const FOLLOW_DISTANCE: i32 = 2;
+
+    let chara = game.gd.chara.get(cid);
+    let ai_rule = RULES.npc_ai.get(chara.ai.kind);
+    let pos = game.gd.chara_pos(cid).unwrap();
+
+    let player_target = game.target_chara().filter(|&target| {
+        game.gd.chara_pos(target).is_some()
+            && game.gd.chara_relation(cid, target) == Relationship::Hostile
+    });
+    let target = match (order, chara.ai.state) {
+        (PartyOrder::AttackTarget, _) if player_target.is_some() => player_target,
+        (_, AiState::Combat { target }) => Some(target),
+        _ => None,
+    };
+
+    if let Some(target) = target {
+        let target_pos = game.gd.chara_pos(target).unwrap();
+        if order != PartyOrder::Wait {
+            if !combat::process_combat_behaviors(game, cid, target, ai_rule) {
+                move_to_target_enemy(game, cid, ai_rule, target);
+            }
+        } else if pos.is_adjacent(target_pos) {
+            // Waiting members fight only adjacent enemies
+            action::try_move(game, cid, geom::dir_by_2pos(pos, target_pos));
+        }
+        return;
+    }
+
+    if order != PartyOrder::Wait && pos.mdistance(game.gd.player_pos()) > FOLLOW_DISTANCE {
+        let dir = dir_to_chara(&game.gd, cid, CharaId::Player, ai_rule.pathfinding_step)
+            .unwrap_or(Direction::NONE);
+        action::try_move(game, cid, dir);
+    }
+}
+
+/// Decide and do the player's action automatically.
+/// Attacks the nearest visible enemy, or walks at random if there are no enemies.
+pub fn process_auto_player_turn(game: &mut Game) {
+    const AUTO_PLAYER_PATHFINDING_STEP: u32 = 20;
+
+    let player_pos = if let Some(pos) = game.gd.chara_pos(CharaId::Player) {
+        pos
+    } else {
+        return;
+    };
+    let target = game
+        .gd
+        .get_charas_on_map()
+        .into_iter()
+        .filter(|&cid| {
+            cid != CharaId::Player
+                && game.gd.chara_relation(CharaId::Player, cid) == Relationship::Hostile
+        })
+        .filter_map(|cid| game.gd.chara_pos(cid).map(|pos| (cid, pos)))
+        .filter(|&(_, pos)| game.view_map.get_tile_visible(pos))
+        .min_by_key(|&(_, pos)| player_pos.mdistance(pos))
+        .map(|(cid, _)| cid);
+
+    if let Some(target) = target {
+        let dir = dir_to_chara(
+            &game.gd,
+            CharaId::Player,
+            target,
+            AUTO_PLAYER_PATHFINDING_STEP,
+        );
+        if let Some(dir) = dir {
+            action::try_move(game, CharaId::Player, dir);
+            return;
+        }
+    }
+    random_walk(game, CharaId::Player);
+}
+
 /// Move npc at random
 fn random_walk(game: &mut Game, cid: CharaId) {
     let dir = Direction::new(
@@ -94,16 +217,6 @@ fn dir_to_chara(
     target: CharaId,
     pathfinding_step: u32,
 ) -> Option<Direction> {
-    let start_pos = gd.chara_pos(cid)?;
     let target_pos = gd.chara_pos(target)?;
-    let map = gd.get_current_map();
-    let chara = gd.chara.get(cid);
-
-    let route = geom::PathFinding::new(map.w, map.h, pathfinding_step, |pos| {
-        map.is_passable(chara, pos)
-    })
-    .route(start_pos, target_pos);
-
-    let next_pos = route.and_then(|route| route.get(1).copied())?;
-    Some(geom::dir_by_2pos(start_pos, next_pos))
+    crate::game::map::route::dir_to_dest(gd, cid, target_pos, pathfinding_step)
 }
@@ -0,0 +1,135 @@
+//! Party members recruited by the player
+
+use super::action::get_skill_kind_from_weapon;
+use super::extrait::*;
+use common::gamedata::*;
+use geom::*;
+use rules::RULES;
+
+/// Number of tiles around the player searched to locate party members after map switching
+const MAX_LOCATE_TILES: usize = 400;
+
+/// Make a character a party member. Returns the new id of the character.
+pub fn join_party(gd: &mut GameData, cid: CharaId) -> Option<CharaId> {
+    if gd.party.len() >= RULES.npc.party_size as usize {
+        game_log_i!("party-full");
+        return None;
+    }
+    let new_cid = gd.join_party(cid)?;
+    game_log_i!("party-join"; chara=gd.chara.get(new_cid));
+    Some(new_cid)
+}
+
+/// Make a party member leave the party. Returns the new id of the character.
+pub fn leave_party(gd: &mut GameData, cid: CharaId) -> Option<CharaId> {
+    let new_cid = gd.leave_party(cid)?;
+    game_log_i!("party-leave"; chara=gd.chara.get(new_cid));
+    Some(new_cid)
+}
+
+pub fn set_order(gd: &mut GameData, cid: CharaId, order: PartyOrder) {
+    gd.party.set_order(cid, order);
+    let text_id = match order {
+        PartyOrder::Follow => "party-order-follow",
+        PartyOrder::Wait => "party-order-wait",
+        PartyOrder::AttackTarget => "party-order-attack-target",
+    };
+    game_log_i!(text_id; chara=gd.chara.get(cid));
+}
+
+/// Money to hire the character. Returns None if the character cannot be hired.
+/// Characters who have talk scripts are recruited by their scripts.
+pub fn hire_cost(gd: &GameData, cid: CharaId) -> Option<i64> {
+    if cid == CharaId::Player || gd.party.contains(cid) {
+        return None;
+    }
+    let chara = gd.chara.get(cid);
+    if chara.trigger_talk.is_some() {
+        return None;
+    }
+    match gd.chara_relation(CharaId::Player, cid) {
+        Relationship::Friendly | Relationship::Neutral => (),
+        _ => return None,
+    }
+    Some(RULES.npc.hire_cost_per_level * i64::from(chara.level))
+}
+
+/// Hire a character by money
+pub fn hire(gd: &mut GameData, cid: CharaId) {
+    let cost = if let Some(cost) = hire_cost(gd, cid) {
+        cost
+    } else {
+        return;
+    };
+    if !gd.player.has_money(cost) {
+        game_log_i!("hire-lack-of-money"; chara=gd.chara.get(cid));
+        return;
+    }
+    if join_party(gd, cid).is_some() {
+        gd.player.sub_money(cost);
+    }
+}
+
+/// Move party members on the previous map to near the player.
+/// Waiting members remain on the previous map unless the site will be removed.
+/// If the site will be removed, members on all floors of the site are moved,
+/// and members who cannot be located leave the game.
+/// All maps of the site must be loaded in that case.
+pub fn move_party_members(gd: &mut GameData, prev_mid: MapId, prev_site_removed: bool) {
+    let player_pos = gd.player_pos();
+    let members: Vec<(CharaId, MapId)> = if prev_site_removed {
+        gd.party_members_on_site(prev_mid.sid())
+    } else {
+        gd.party
+            .iter()
+            .filter(|m| m.order != PartyOrder::Wait)
+            .map(|m| m.cid)
+            .filter(|&cid| gd.region.get_map(prev_mid).chara_pos(cid).is_some())
+            .map(|cid| (cid, prev_mid))
+            .collect()
+    };
+
+    for (cid, mid) in members {
+        if let Some(pos) = empty_tile_near(gd, gd.chara.get(cid), player_pos) {
+            gd.move_party_member(cid, mid, pos);
+        } else if prev_site_removed {
+            warn!("cannot find a tile to locate {:?}, so it is removed", cid);
+            gd.remove_chara(cid);
+        } else {
+            warn!("cannot find a tile to locate {:?}", cid);
+        }
+    }
+}
+
+/// Find an empty tile near the given position where the character can be located
+pub fn empty_tile_near(gd: &GameData, chara: &Chara, center: Vec2d) -> Option<Vec2d> {
+    let map = gd.get_current_map();
+    SpiralIter::new(center)
+        .skip(1)
+        .take(MAX_LOCATE_TILES)
+        .find(|&pos| map.is_passable(chara, pos) && map.tile[pos].chara.is_none())
+}
+
+/// Share attack exp of the player or a party member with other party members on the current map.
+/// Shared exp is added to the same skill, or the melee skill if they don't have the skill.
+pub fn share_attack_exp(gd: &mut GameData, cid: CharaId, kind: SkillKind, target_level: u32) {
+    if cid != CharaId::Player && !gd.party.contains(cid) {
+        return;
+    }
+    let others: Vec<CharaId> = std::iter::once(CharaId::Player)
+        .chain(gd.party.iter().map(|m| m.cid))
+        .filter(|&other| other != cid && gd.chara_pos(other).is_some())
+        .collect();
+
+    for other in others {
+        let chara = gd.chara.get_mut(other);
+        let kind = if chara.skills.skills.contains_key(&kind) {
+            kind
+        } else if let Some(weapon) = chara.equip.item(EquipSlotKind::MeleeWeapon, 0) {
+            get_skill_kind_from_weapon(weapon)
+        } else {
+            SkillKind::BareHands
+        };
+        chara.add_shared_attack_exp(kind, target_level);
+    }
+}
@@ -0,0 +1,139 @@
+//! Periodic updates of the world
+
+use super::script_hook::push_event;
+use super::Game;
+use common::gamedata::time::{Time, SECS_PER_DAY, SECS_PER_HOUR};
+use common::gamedata::*;
+use common::gobj;
+use common::obj::SiteGenObject;
+use rules::world::{PeriodicEventKind, PeriodicInterval};
+use rules::RULES;
+
+/// Hourly update. before is the time before advancing.
+pub fn update_hourly(game: &mut Game, before: Time) {
+    let now = game.gd.time.current_time();
+    let date = now.into_date();
+    let event = ScriptEvent::NewHour {
+        year: date.year,
+        month: date.month,
+        day: date.day,
+        hour: date.hour,
+    };
+    push_event(&mut game.gd, event.clone());
+
+    super::npc::update_ai_schedule(&mut game.gd, date.hour);
+
+    let before = before.as_secs() / SECS_PER_HOUR;
+    let now = now.as_secs() / SECS_PER_HOUR;
+    exec_periodic_events(&mut game.gd, &event, |interval| match interval {
+        PeriodicInterval::Hours(n) => passed(n, before, now),
+        _ => false,
+    });
+}
+
+/// Daily update. before is the time before advancing.
+pub fn update_daily(game: &mut Game, before: Time) {
+    let now = game.gd.time.current_time();
+    let date = now.into_date();
+    let event = ScriptEvent::NewDay {
+        year: date.year,
+        month: date.month,
+        day: date.day,
+    };
+    push_event(&mut game.gd, event.clone());
+
+    let before = before.as_secs() / SECS_PER_DAY;
+    let days = now.as_secs() / SECS_PER_DAY;
+
+    if passed(RULES.town.shop_restock_days, before, days) {
+        restock_shops(&mut game.gd);
+    }
+    recover_sold_count(&mut game.gd, (days - before) as u32);
+    refresh_town_quests(&mut game.gd, now);
+
+    exec_periodic_events(&mut game.gd, &event, |interval| match interval {
+        PeriodicInterval::Days(n) => passed(n, before, days),
+        _ => false,
+    });
+}
+
+/// Returns true if a multiple of n is in (before, now]
+fn passed(n: u32, before: u64, now: u64) -> bool {
+    n > 0 && now / n as u64 > before / n as u64
+}
+
+fn towns(gd: &GameData) -> Vec<(SiteId, &Town)> {
+    gd.region
+        .iter_sites()
+        .filter_map(|(sid, site)| match site.content {
+            SiteContent::Town { ref town } => Some((sid, town.as_ref())),
+            _ => None,
+        })
+        .collect()
+}
+
+fn restock_shops(gd: &mut GameData) {
+    let towns: Vec<(SiteId, String)> = towns(gd)
+        .into_iter()
+        .map(|(sid, town)| (sid, town.id().to_owned()))
+        .collect();
+
+    for (sid, id) in towns {
+        let sg: &SiteGenObject = gobj::get_by_id(&id);
+        super::town::update_shops(gd, sid, sg);
+    }
+}
+
+/// Let towns forget items sold by the player
+fn recover_sold_count(gd: &mut GameData, days: u32) {
+    let n = RULES.town.sold_count_recovery * days;
+    let towns: Vec<SiteId> = towns(gd).into_iter().map(|(sid, _)| sid).collect();
+
+    for sid in towns {
+        if let SiteContent::Town { ref mut town } = gd.region.get_site_mut(sid).content {
+            town.recover_sold_count(n);
+        }
+    }
+}
+
+/// Refresh quest boards that have been initialized
+fn refresh_town_quests(gd: &mut GameData, now: Time) {
+    let refresh_days = RULES.quest.quest_refresh_days as u64;
+    let towns: Vec<SiteId> = towns(gd)
+        .into_iter()
+        .filter(|(_, town)| {
+            town.quests_updated
+                .map(|t| now.duration_from(t).days() >= refresh_days)
+                .unwrap_or(false)
+        })
+        .map(|(sid, _)| sid)
+        .collect();
+
+    for sid in towns {
+        super::quest::update_town_quest(gd, sid);
+    }
+}
+
+/// Apply rule defined periodic events that filter returns true
+fn exec_periodic_events<F: Fn(PeriodicInterval) -> bool>(
+    gd: &mut GameData,
+    event: &ScriptEvent,
+    filter: F,
+) {
+    for periodic_event in &RULES.world.periodic_events {
+        if !filter(periodic_event.interval) {
+            continue;
+        }
+        match periodic_event.kind {
+            PeriodicEventKind::Script { ref id } => {
+                gd.script_exec
+                    .hook_queue
+                    .push_back((id.clone(), event.clone()));
+            }
+            PeriodicEventKind::FactionRelation { faction, value } => {
+                let relation = gd.faction.get(faction) + value;
+                gd.faction.set(faction, relation);
+            }
+        }
+    }
+}
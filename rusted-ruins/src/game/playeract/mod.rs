@@ -11,6 +11,7 @@ use crate::game::{DialogOpenRequest, InfoGetter};
 use common::gamedata::*;
 use common::objholder::ItemIdx;
 use geom::*;
+use rules::RULES;
 
 /// Player actions are processed through this.
 /// Mutable access to Game or GameData is limited by this wrapper.
@@ -82,6 +83,36 @@ impl<'a, 's> DoPlayerAction<'a, 's> {
         true
     }
 
+    /// Put items into a container.
+    /// Returns the location of the container after moving, or None if it does not exist.
+    pub fn put_in_container(
+        &mut self,
+        container: ItemLocation,
+        il: ItemLocation,
+    ) -> Option<ItemLocation> {
+        crate::game::item::container::put_in(self.gd_mut(), container, il)
+    }
+
+    /// Take items out of a container.
+    /// Returns the location of the container after moving, or None if it does not exist.
+    pub fn take_out_from_container(
+        &mut self,
+        container: ItemLocation,
+        il: ItemLocation,
+    ) -> Option<ItemLocation> {
+        crate::game::item::container::take_out(self.gd_mut(), container, il)
+    }
+
+    /// Put items into the stash at the player's base
+    pub fn put_in_stash(&mut self, sid: SiteId, il: ItemLocation) {
+        crate::game::item::container::put_in_stash(self.gd_mut(), sid, il)
+    }
+
+    /// Take items out of the stash at the player's base
+    pub fn take_out_from_stash(&mut self, il: ItemLocation) {
+        crate::game::item::container::take_out_from_stash(self.gd_mut(), il)
+    }
+
     /// Throw one item
     pub fn throw_item(&mut self, il: ItemLocation) {
         let effect = crate::game::item::throw::item_to_throw_effect(self.gd(), il, CharaId::Player);
@@ -186,8 +217,8 @@ impl<'a, 's> DoPlayerAction<'a, 's> {
     }
 
     /// Sell item
-    pub fn sell_item(&mut self, il: ItemLocation) {
-        super::shop::sell_item(self.gd_mut(), il);
+    pub fn sell_item(&mut self, il: ItemLocation, cid: CharaId) {
+        super::shop::sell_item(self.gd_mut(), il, cid);
     }
 
     /// Change specified character's equipment by given item
@@ -196,8 +227,49 @@ impl<'a, 's> DoPlayerAction<'a, 's> {
     }
 
     /// Use active skill. Returns false if the skill cost is not enough.
-    pub fn use_active_skill(&mut self, _active_skill_id: &ActiveSkillId) -> bool {
-        false
+    pub fn use_active_skill(&mut self, active_skill_id: &ActiveSkillId) -> bool {
+        let active_skill = if let Some(active_skill) = RULES.active_skills.get(active_skill_id) {
+            active_skill
+        } else {
+            return false;
+        };
+        let player = self.gd().chara.get(CharaId::Player);
+        if !player.active_skill_available(active_skill) {
+            if player.mp < active_skill.cost_mp as i32 {
+                game_log_i!("active-skill-lack-of-mp"; chara=player, active_skill=active_skill_id);
+            }
+            return false;
+        }
+        if super::active_skill::calc_power(self.gd(), active_skill, CharaId::Player).is_none() {
+            game_log_i!("active-skill-unavailable"; chara=player, active_skill=active_skill_id);
+            return false;
+        }
+
+        let target = if let Some(target) = auto_target_for_player(self.0, &active_skill.effect) {
+            target
+        } else {
+            let active_skill_id = active_skill_id.clone();
+            self.0.ui_request.push_back(UiRequest::StartTargeting {
+                effect: active_skill.effect.clone(),
+                callback: Box::new(move |pa, target| {
+                    if super::active_skill::use_active_skill(
+                        pa.0,
+                        &active_skill_id,
+                        CharaId::Player,
+                        target,
+                    ) {
+                        pa.0.finish_player_turn();
+                    }
+                }),
+            });
+            return true;
+        };
+        if super::active_skill::use_active_skill(self.0, active_skill_id, CharaId::Player, target) {
+            self.0.finish_player_turn();
+            true
+        } else {
+            false
+        }
     }
 
     /// Try talk to next chara
@@ -227,6 +299,7 @@ impl<'a, 's> DoPlayerAction<'a, 's> {
             }
         }
         if let Some(trigger_talk) = trigger_talk {
+            crate::game::quest::deliver_item(self.gd_mut(), cid.unwrap());
             self.0.start_script(&trigger_talk, cid);
         }
     }
@@ -237,6 +310,21 @@ impl<'a, 's> DoPlayerAction<'a, 's> {
         }
     }
 
+    /// Hire a character as a party member
+    pub fn hire(&mut self, cid: CharaId) {
+        super::party::hire(self.gd_mut(), cid);
+    }
+
+    /// Give an order to a party member
+    pub fn set_party_order(&mut self, cid: CharaId, order: PartyOrder) {
+        super::party::set_order(self.gd_mut(), cid, order);
+    }
+
+    /// Make a party member leave the party
+    pub fn leave_party(&mut self, cid: CharaId) {
+        super::party::leave_party(self.gd_mut(), cid);
+    }
+
     /// Advance current talk. Give player's choice if the talk has choices.
     /// If returns new text, continue talk dialog.
     pub fn advance_talk(&mut self, choice: Option<u32>) -> AdvanceScriptResult {
@@ -253,6 +341,15 @@ impl<'a, 's> DoPlayerAction<'a, 's> {
         crate::game::quest::undertake_quest(self.0, i);
     }
 
+    /// Abandon undertaken quest
+    pub fn abandon_quest(&mut self, i: u32) {
+        crate::game::quest::abandon_quest(self.gd_mut(), i as usize);
+    }
+
+    pub fn dismiss_quest(&mut self, i: u32) {
+        crate::game::quest::dismiss_quest(self.gd_mut(), i as usize);
+    }
+
     pub fn request_dialog_open(&mut self, req: DialogOpenRequest) {
         self.0.request_dialog_open(req);
     }
@@ -276,6 +373,11 @@ impl<'a, 's> DoPlayerAction<'a, 's> {
         self.0.finish_player_turn();
     }
 
+    pub fn start_build(&mut self, pos: Vec2d, obj: BuildObj) {
+        super::building::start_build(self.0, pos, CharaId::Player, obj);
+        self.0.finish_player_turn();
+    }
+
     pub fn exec_debug_command(&mut self, command: &str) {
         super::debug_command::exec_debug_command(self.0, command);
     }
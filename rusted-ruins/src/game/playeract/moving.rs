@@ -1,10 +1,14 @@
 use super::DoPlayerAction;
+use crate::game::map::route::dir_to_dest;
 use crate::game::map::MapExt;
 use crate::game::{action, DialogOpenRequest, InfoGetter};
 use crate::text::ToText;
 use common::gamedata::*;
 use geom::*;
 
+/// Max route length searched when moving to the destination
+const MOVE_TO_MAX_STEP: u32 = 100;
+
 impl<'a, 's> DoPlayerAction<'a, 's> {
     pub fn try_move(&mut self, dir: Direction) {
         let dest_tile = self
@@ -49,6 +53,12 @@ impl<'a, 's> DoPlayerAction<'a, 's> {
     }
 
     pub fn move_to(&mut self, dest: Vec2d) {
+        if let Some(dir) = dir_to_dest(self.gd(), CharaId::Player, dest, MOVE_TO_MAX_STEP) {
+            self.try_move(dir);
+            return;
+        }
+
+        // If no route is found, try to move toward dest
         let d = dest - self.gd().player_pos();
         let hdir = if d.0 < 0 {
             HDirection::Left
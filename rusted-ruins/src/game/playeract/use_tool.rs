@@ -1,7 +1,8 @@
 use super::DoPlayerAction;
 use crate::game::effect::do_effect;
 use crate::game::extrait::*;
-use crate::game::{Animation, InfoGetter};
+use crate::game::{Animation, DialogOpenRequest, InfoGetter};
+use crate::text::ToText;
 use common::gamedata::*;
 use common::gobj;
 use common::objholder::AnimImgIdx;
@@ -37,8 +38,21 @@ impl<'a, 's> DoPlayerAction<'a, 's> {
                     game_log_i!("building-not-adjacent-tile");
                     return;
                 }
-                trace!("building at {}", &pos);
-                crate::game::building::start_build(self.0, pos, Player);
+                if let Some(obj) = crate::game::building::deconstructible_obj(self.gd(), pos) {
+                    let (id, _, _) = crate::game::building::build_requirements(obj);
+                    let obj_name = crate::text::obj_txt(id);
+                    let msg = ui_txt_format!("dialog-deconstruct"; obj=obj_name);
+                    let cb = Box::new(move |pa: &mut DoPlayerAction, yes: bool| {
+                        if yes {
+                            trace!("deconstructing at {}", &pos);
+                            crate::game::building::start_deconstruct(pa.0, pos, Player);
+                            pa.0.finish_player_turn();
+                        }
+                    });
+                    self.request_dialog_open(DialogOpenRequest::YesNo { callback: cb, msg });
+                } else {
+                    self.request_dialog_open(DialogOpenRequest::Build { pos });
+                }
             }
             ToolEffect::Chop => {
                 trace!("chopping at {}", &pos);
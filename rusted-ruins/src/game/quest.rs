@@ -1,22 +1,46 @@
 //! Quest handlings
 
-use super::chara::gen::choose_npc_chara_template;
+use super::chara::gen::{choose_npc_chara_template, create_chara};
+use super::extrait::*;
+use super::item::gen::gen_item_from_idx;
+use super::party::{empty_tile_near, join_party, leave_party};
+use super::script_hook::push_event;
 use super::Game;
+use crate::text::{obj_txt, ToText};
+use common::gamedata::time::Duration;
 use common::gamedata::*;
-use common::objholder::CharaTemplateIdx;
+use common::gobj;
+use common::objholder::{CharaTemplateIdx, ItemIdx};
+use rand::seq::SliceRandom;
+use rules::quest::{QuestDef, QuestKind};
 use rules::RULES;
 
-/// Update quest list of current town
-pub fn update_town_quest(gd: &mut GameData) {
-    let mid = gd.get_current_mapid();
-    let town = match gd.region.get_site_mut(mid.sid()).content {
+/// Update quest list of the town
+pub fn update_town_quest(gd: &mut GameData, sid: SiteId) {
+    let quests: Vec<Quest> = (0..RULES.quest.n_town_quests)
+        .filter_map(|_| gen_quest(gd, sid))
+        .collect();
+    let now = gd.time.current_time();
+
+    let town = match gd.region.get_site_mut(sid).content {
         SiteContent::Town { ref mut town } => town,
         _ => unreachable!(),
     };
 
-    town.quests.clear();
+    town.quests = quests;
+    town.quests_updated = Some(now);
+}
 
-    town.quests.push(gen_quest());
+/// Generate quests of the current town if they have never been generated
+pub fn init_town_quest(gd: &mut GameData) {
+    let sid = gd.get_current_mapid().sid();
+    let initialized = match gd.region.get_site(sid).content {
+        SiteContent::Town { ref town } => town.quests_updated.is_some(),
+        _ => unreachable!(),
+    };
+    if !initialized {
+        update_town_quest(gd, sid);
+    }
 }
 
 /// Returns available quest in the current town
@@ -32,67 +56,510 @@ pub fn available_quests(gd: &GameData) -> &[Quest] {
 
 /// Undertake quest in the current town
 pub fn undertake_quest(game: &mut Game, i: u32) {
+    let quest = available_quests(&game.gd)[i as usize].clone();
+    if !start_quest(&mut game.gd, quest) {
+        return;
+    }
+
     let mid = game.gd.get_current_mapid();
     let town = match game.gd.region.get_site_mut(mid.sid()).content {
         SiteContent::Town { ref mut town } => town,
         _ => unreachable!(),
     };
+    town.quests.remove(i as usize);
+}
+
+/// Start the quest defined in rules with the given id. Used by scripts.
+/// Returns false if the quest cannot be started.
+pub fn start_quest_by_id(gd: &mut GameData, id: &str) -> bool {
+    let def = if let Some(def) = RULES.quest.quests.get(id) {
+        def
+    } else {
+        warn!("unknown quest \"{}\"", id);
+        return false;
+    };
+    let mid = gd.get_current_mapid();
+    let town = if mid.is_region_map() {
+        None
+    } else {
+        Some(mid.sid())
+    };
+    let quest = if let Some(quest) = gen_quest_from_def(gd, mid.rid(), town, id, def) {
+        quest
+    } else {
+        warn!("cannot generate quest \"{}\"", id);
+        return false;
+    };
+    start_quest(gd, quest)
+}
+
+/// Start the quest as an active quest. Returns false if the quest cannot be started.
+fn start_quest(gd: &mut GameData, mut quest: Quest) -> bool {
+    // The traveller of an escort quest joins the party at the start
+    if let QuestObjective::Escort {
+        idx, ref mut cid, ..
+    } = quest.objective
+    {
+        if let Some(traveller) = spawn_traveller(gd, idx) {
+            *cid = Some(traveller);
+        } else {
+            return false;
+        }
+    }
+
+    // The item to deliver is given at the start
+    if let QuestObjective::Deliver { idx, .. } = quest.objective {
+        let level = gd.chara.get(CharaId::Player).level;
+        let item = gen_item_from_idx(idx, level);
+        game_log_i!("quest-receive-item"; item=item);
+        gd.get_item_list_mut(ItemListLocation::Chara {
+            cid: CharaId::Player,
+        })
+        .append(item, 1);
+    }
+
+    let event = ScriptEvent::QuestStateChanged {
+        quest: quest.clone(),
+        state: QuestState::Active,
+    };
+    gd.quest.start_new_quest(quest);
+    push_event(gd, event);
+    true
+}
+
+/// Spawn the traveller of an escort quest near the player, and make it join the party
+fn spawn_traveller(gd: &mut GameData, idx: CharaTemplateIdx) -> Option<CharaId> {
+    if gd.party.len() >= RULES.npc.party_size as usize {
+        game_log_i!("party-full");
+        return None;
+    }
+
+    let ct = gobj::get_obj(idx);
+    let faction = ct.faction.unwrap_or_else(FactionId::unknown);
+    let chara = create_chara(idx, ct.gen_level, faction, None);
+    let pos = if let Some(pos) = empty_tile_near(gd, &chara, gd.player_pos()) {
+        pos
+    } else {
+        warn!("cannot find a tile to locate the traveller");
+        return None;
+    };
+
+    let mid = gd.get_current_mapid();
+    let cid = gd.add_chara_to_map(chara, mid);
+    gd.get_current_map_mut().locate_chara(cid, pos);
+    join_party(gd, cid)
+}
 
-    let quest = town.quests.remove(i as usize);
-    game.gd.quest.start_new_quest(quest);
+/// Generate a quest from the quest definitions in rules
+fn gen_quest(gd: &GameData, sid: SiteId) -> Option<Quest> {
+    let mut defs: Vec<(&String, &QuestDef)> = RULES.quest.quests.iter().collect();
+    defs.sort_by_key(|(id, _)| *id);
+    let (_, (id, def)) = rng::choose(&defs, |(_, def)| def.weight)?;
+    gen_quest_from_def(gd, sid.rid, Some(sid), id, def)
 }
 
-/// Generate an quest
-fn gen_quest() -> Quest {
+/// Generate a quest from the definition.
+/// The objective is chosen in the given region, and the given town is excluded from destinations.
+fn gen_quest_from_def(
+    gd: &GameData,
+    rid: RegionId,
+    town: Option<SiteId>,
+    id: &str,
+    def: &QuestDef,
+) -> Option<Quest> {
+    let (objective, units) = gen_objective(gd, rid, town, &def.kind)?;
+
+    let level = gd.chara.get(CharaId::Player).level;
+    let money = (def.reward_money + def.reward_money_per_unit * units as i64) as f32
+        * (1.0 + RULES.quest.reward_level_factor * level as f32);
     let reward = Reward {
-        money: 1000,
-        item: Vec::new(),
+        money: money as i64,
+        item: def
+            .reward_items
+            .iter()
+            .filter_map(|id| item_idx(id))
+            .collect(),
     };
 
-    Quest::SlayMonsters {
+    let deadline = def
+        .deadline_days
+        .map(|days| gd.time.current_time() + Duration::from_days(days.into()));
+
+    Some(Quest {
+        id: id.to_owned(),
         reward,
-        idx: choose_npc_chara_template(&RULES.quest.slay_race_probability, 1).unwrap(),
-        goal: 10,
-        killed: 0,
+        objective,
+        deadline,
+    })
+}
+
+/// Generate an objective and the number of units for reward calculation
+fn gen_objective(
+    gd: &GameData,
+    rid: RegionId,
+    town: Option<SiteId>,
+    kind: &QuestKind,
+) -> Option<(QuestObjective, u32)> {
+    let objective = match kind {
+        QuestKind::SlayMonsters { n } => {
+            let goal = gen_n(*n);
+            let objective = QuestObjective::SlayMonsters {
+                idx: choose_npc_chara_template(&RULES.quest.slay_race_probability, 1)?,
+                goal,
+                killed: 0,
+            };
+            return Some((objective, goal));
+        }
+        QuestKind::FetchItems { items, n } => {
+            let n = gen_n(*n);
+            let objective = QuestObjective::FetchItems {
+                idx: choose_item(items)?,
+                n,
+            };
+            return Some((objective, n));
+        }
+        QuestKind::Deliver { items } => {
+            let idx = choose_item(items)?;
+            let mut targets = Vec::new();
+            for (town_sid, site) in other_towns(gd, rid, town) {
+                for cid in gd.chara.on_site_charas(town_sid) {
+                    if gd.chara.get(cid).trigger_talk.is_some() {
+                        targets.push((cid, site));
+                    }
+                }
+            }
+            let (cid, site) = *targets.choose(&mut rng::GameRng)?;
+            QuestObjective::Deliver {
+                idx,
+                cid,
+                name: gd.chara.get(cid).to_text().into(),
+                town: site.to_text().into(),
+            }
+        }
+        QuestKind::Escort { traveller } => {
+            let idx = if let Some(idx) = gobj::id_to_idx_checked(traveller) {
+                idx
+            } else {
+                warn!("unknown chara template \"{}\" for escort quest", traveller);
+                return None;
+            };
+            let (dest, site) = *other_towns(gd, rid, town).choose(&mut rng::GameRng)?;
+            QuestObjective::Escort {
+                name: obj_txt(traveller),
+                dest,
+                dest_name: site.to_text().into(),
+                idx,
+                cid: None,
+            }
+        }
+        QuestKind::ExploreDungeon { floor } => {
+            let mut dungeons: Vec<(SiteId, &Site)> = gd
+                .region
+                .get(rid)
+                .iter_sites()
+                .filter(|(sid, _)| sid.kind == SiteKind::AutoGenDungeon)
+                .collect();
+            dungeons.sort_by_key(|(sid, _)| sid.n);
+            let (sid, site) = *dungeons.choose(&mut rng::GameRng)?;
+            let floor = std::cmp::min(gen_n(*floor), site.max_floor()).max(1);
+            let objective = QuestObjective::ExploreDungeon {
+                sid,
+                name: site.to_text().into(),
+                floor,
+            };
+            return Some((objective, floor));
+        }
+        QuestKind::Craft { items, n } => {
+            let goal = gen_n(*n);
+            let objective = QuestObjective::Craft {
+                idx: choose_item(items)?,
+                goal,
+                crafted: 0,
+            };
+            return Some((objective, goal));
+        }
+    };
+    Some((objective, 1))
+}
+
+fn gen_n(n: (u32, u32)) -> u32 {
+    rng::gen_range(n.0..=std::cmp::max(n.0, n.1))
+}
+
+fn choose_item(items: &[String]) -> Option<ItemIdx> {
+    item_idx(items.choose(&mut rng::GameRng)?)
+}
+
+fn item_idx(id: &str) -> Option<ItemIdx> {
+    let idx = gobj::id_to_idx_checked(id);
+    if idx.is_none() {
+        warn!("unknown item \"{}\" for quest", id);
     }
+    idx
+}
+
+/// Towns in the region except the given site
+fn other_towns(gd: &GameData, rid: RegionId, except: Option<SiteId>) -> Vec<(SiteId, &Site)> {
+    let mut towns: Vec<(SiteId, &Site)> = gd
+        .region
+        .get(rid)
+        .iter_sites()
+        .filter(|(town_sid, _)| town_sid.kind == SiteKind::Town && Some(*town_sid) != except)
+        .collect();
+    towns.sort_by_key(|(sid, _)| sid.n);
+    towns
+}
+
+/// Complete active quests that f returns true, and push events
+fn update_active_quests<F: FnMut(&mut QuestObjective) -> bool>(gd: &mut GameData, f: F) {
+    for event in complete_quests(&mut gd.quest, f) {
+        push_event(gd, event);
+    }
+}
+
+fn complete_quests<F: FnMut(&mut QuestObjective) -> bool>(
+    quests: &mut QuestHolder,
+    mut f: F,
+) -> Vec<ScriptEvent> {
+    let mut events = Vec::new();
+
+    for (state, quest) in quests.iter_mut() {
+        if *state == QuestState::Active && f(&mut quest.objective) {
+            *state = QuestState::Completed;
+            // Log
+            match quest.objective {
+                QuestObjective::SlayMonsters { idx, goal, .. } => {
+                    game_log_i!("quest-complete-slay_monsters"; monster=idx, n=goal);
+                }
+                _ => {
+                    game_log_i!("quest-complete"; quest=quest);
+                }
+            }
+            events.push(ScriptEvent::QuestStateChanged {
+                quest: quest.clone(),
+                state: *state,
+            });
+        }
+    }
+    events
 }
 
 pub fn count_slayed_monster(gd: &mut GameData, t: CharaTemplateIdx) {
+    update_active_quests(gd, |objective| match objective {
+        QuestObjective::SlayMonsters {
+            idx, goal, killed, ..
+        } if *idx == t => {
+            *killed += 1;
+            *killed == *goal
+        }
+        _ => false,
+    });
+}
+
+pub fn count_crafted_item(gd: &mut GameData, t: ItemIdx) {
+    update_active_quests(gd, |objective| match objective {
+        QuestObjective::Craft {
+            idx, goal, crafted, ..
+        } if *idx == t => {
+            *crafted += 1;
+            *crafted == *goal
+        }
+        _ => false,
+    });
+}
+
+/// Check quests when the player arrives at a map
+pub fn check_arrival(gd: &mut GameData, mid: MapId) {
+    if mid.is_region_map() {
+        return;
+    }
+    let sid = mid.sid();
+    let charas_on_map = gd.get_charas_on_map();
+    update_active_quests(gd, |objective| match objective {
+        QuestObjective::Escort { dest, cid, .. } => {
+            // The traveller must be with the player
+            let traveller_arrived = match cid {
+                Some(cid) => charas_on_map.contains(cid),
+                None => true,
+            };
+            *dest == sid && traveller_arrived
+        }
+        QuestObjective::ExploreDungeon { sid: s, floor, .. } => {
+            *s == sid && mid.floor() + 1 >= *floor
+        }
+        _ => false,
+    });
+
+    // Travellers who reached the destination leave the party
+    let mut arrived = Vec::new();
     for (state, quest) in gd.quest.iter_mut() {
-        match quest {
-            Quest::SlayMonsters {
-                idx, goal, killed, ..
-            } => {
-                if *state == QuestState::Active && *idx == t {
-                    *killed += 1;
-                    if *killed == *goal {
-                        *state = QuestState::Completed;
-                        // Log
-                        game_log_i!("quest-complete-slay_monsters"; monster=idx, n=goal);
-                    }
-                }
+        if let QuestObjective::Escort { ref mut cid, .. } = quest.objective {
+            if *state != QuestState::Active {
+                arrived.extend(cid.take());
+            }
+        }
+    }
+    for cid in arrived {
+        leave_party(gd, cid);
+    }
+}
+
+/// Fail escort quests whose traveller is dead
+pub fn check_dead_chara(gd: &mut GameData, dead: CharaId) {
+    while let Some(i) = gd.quest.iter().position(|(state, quest)| {
+        *state == QuestState::Active
+            && matches!(quest.objective, QuestObjective::Escort { cid: Some(cid), .. } if cid == dead)
+    }) {
+        // The traveller is removed as a dying character
+        if let Some((_, quest)) = gd.quest.get_mut(i) {
+            if let QuestObjective::Escort { ref mut cid, .. } = quest.objective {
+                *cid = None;
             }
         }
+        let quest = fail_quest(gd, i);
+        game_log_i!("quest-failed"; quest=quest);
     }
 }
 
+/// Deliver quest items to the given character. Returns true if delivered.
+pub fn deliver_item(gd: &mut GameData, target: CharaId) -> bool {
+    let player = gd.chara.get_mut(CharaId::Player);
+    let mut delivered = false;
+
+    let events = complete_quests(&mut gd.quest, |objective| match *objective {
+        QuestObjective::Deliver { idx, cid, .. }
+            if !delivered && cid == target && player.item_list.count(idx) > 0 =>
+        {
+            player.item_list.consume(idx, 1, |_, _| (), false);
+            delivered = true;
+            true
+        }
+        _ => false,
+    });
+    for event in events {
+        push_event(gd, event);
+    }
+    delivered
+}
+
+/// Complete fetch quests if the player has the items
+fn fetch_items(gd: &mut GameData) {
+    let player = gd.chara.get_mut(CharaId::Player);
+
+    let events = complete_quests(&mut gd.quest, |objective| match *objective {
+        QuestObjective::FetchItems { idx, n } if player.item_list.count(idx) >= n => {
+            player.item_list.consume(idx, n, |_, _| (), false);
+            true
+        }
+        _ => false,
+    });
+    for event in events {
+        push_event(gd, event);
+    }
+}
+
+/// Fail active quests that exceed their deadlines
+pub fn check_deadlines(gd: &mut GameData) {
+    let now = gd.time.current_time();
+
+    while let Some(i) = gd.quest.iter().position(|(state, quest)| {
+        *state == QuestState::Active && quest.deadline.map(|d| d < now).unwrap_or(false)
+    }) {
+        let quest = fail_quest(gd, i);
+        game_log_i!("quest-failed"; quest=quest);
+    }
+}
+
+/// Abandon the i-th undertaken quest
+pub fn abandon_quest(gd: &mut GameData, i: usize) {
+    if gd.quest.get(i).map(|(state, _)| *state) != Some(QuestState::Active) {
+        return;
+    }
+    let quest = fail_quest(gd, i);
+    game_log_i!("quest-abandoned"; quest=quest);
+}
+
+/// Make the quest failed, and apply the failure penalty.
+/// Failed quests remain until the player dismisses them.
+fn fail_quest(gd: &mut GameData, i: usize) -> Quest {
+    let (state, quest) = gd.quest.get_mut(i).unwrap();
+    *state = QuestState::Failed;
+    let traveller = match quest.objective {
+        QuestObjective::Escort { ref mut cid, .. } => cid.take(),
+        _ => None,
+    };
+    let quest = quest.clone();
+
+    if let Some(cid) = traveller {
+        if gd.party.contains(cid) {
+            leave_party(gd, cid);
+        }
+    }
+
+    // Quests started by scripts or old quests don't have definitions
+    let penalty = if let Some(def) = RULES.quest.quests.get(&quest.id) {
+        &def.failure_penalty
+    } else {
+        &RULES.quest.default_failure_penalty
+    };
+    for &(faction, value) in penalty {
+        let relation = gd.faction.get(faction) + value;
+        gd.faction.set(faction, relation);
+    }
+
+    let event = ScriptEvent::QuestStateChanged {
+        quest: quest.clone(),
+        state: QuestState::Failed,
+    };
+    push_event(gd, event);
+    quest
+}
+
+/// Remove the i-th undertaken quest if it has failed
+pub fn dismiss_quest(gd: &mut GameData, i: usize) {
+    if gd.quest.get(i).map(|(state, _)| *state) != Some(QuestState::Failed) {
+        return;
+    }
+    gd.quest.remove(i);
+}
+
 pub fn receive_rewards(gd: &mut GameData) -> bool {
+    fetch_items(gd);
+
     let mut money = 0;
+    let mut items = Vec::new();
     let mut exist_completed_quest = false;
+    let mut events = Vec::new();
 
     for (state, quest) in gd.quest.iter_mut() {
         if *state == QuestState::Completed {
             exist_completed_quest = true;
-            let reward = quest.reward();
-            money += reward.money;
+            money += quest.reward.money;
+            items.extend_from_slice(&quest.reward.item);
             *state = QuestState::RewardReceived;
+            events.push(ScriptEvent::QuestStateChanged {
+                quest: quest.clone(),
+                state: *state,
+            });
         }
     }
 
+    for event in events {
+        push_event(gd, event);
+    }
+
     if exist_completed_quest {
         gd.quest.remove_reward_received();
         gd.player.add_money(money);
         game_log_i!("quest-reward-receive-money"; money=money);
+
+        let player = gd.chara.get_mut(CharaId::Player);
+        for idx in items {
+            let item = gen_item_from_idx(idx, player.level);
+            game_log_i!("player-receive-item"; chara=player, item=item, n=1);
+            player.item_list.append(item, 1);
+        }
+        player.update();
     }
     exist_completed_quest
 }
@@ -1,5 +1,5 @@
 use super::map::choose_empty_tile;
-use super::saveload::{gen_box_id, get_map_dir};
+use super::saveload::gen_box_id;
 use common::basic::MAX_AUTO_GEN_DUNGEONS;
 use common::gamedata::*;
 use common::gobj;
@@ -42,8 +42,8 @@ pub fn gen_dungeon(gd: &mut GameData, rid: RegionId) {
         return;
     }
 
+    // The region map is loaded when the game data is loaded
     let mid = MapId::from(rid);
-    gd.region.preload_map(mid, get_map_dir(gd));
 
     let pos = {
         let region_map = gd.region.get_map(mid);
@@ -3,41 +3,46 @@ use crate::game::Game;
 use common::basic::{SAVE_DIR_NAME, SAVE_EXTENSION};
 use common::gamedata::GameData;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 impl<'s> Game<'s> {
     pub fn save_file(&self) {
-        let save_dir = get_save_dir();
-
-        if !save_dir.exists() {
-            match fs::create_dir_all(&save_dir) {
-                Ok(()) => (),
-                Err(e) => {
-                    warn!("Failed to create save directory : {}", e);
-                    return;
-                }
-            }
-        }
-
-        let path = self.gd.save_dir(save_dir);
+        let path = if let Some(path) = self.save_dir.as_ref() {
+            path
+        } else {
+            warn!("Failed to save: save directory is not set");
+            return;
+        };
 
-        match self.gd.save(&path) {
+        match self.gd.save(path) {
             Ok(_) => info!("Saved to {:?}", path.to_string_lossy()),
             Err(e) => warn!("Faild to saving to {:?}: {}", path.to_string_lossy(), e),
         }
     }
 
     pub fn clean_save_data(&self) {
-        let save_dir = get_save_dir();
-        let path = self.gd.save_dir(save_dir);
+        let path = if let Some(path) = self.save_dir.as_ref() {
+            path
+        } else {
+            return;
+        };
         if !path.exists() {
             return;
         }
-        match self.gd.clean_map_dir(&path) {
+        match self.gd.clean_map_dir(path) {
             Ok(_) => info!("Clean map dir {:?}", path.to_string_lossy()),
             Err(e) => warn!("Faild to clean map dir {:?}: {}", path.to_string_lossy(), e),
         }
     }
+
+    pub fn save_dir(&self) -> Option<&Path> {
+        self.save_dir.as_deref()
+    }
+
+    /// Change the directory used for saving and loading maps
+    pub fn set_save_dir(&mut self, path: PathBuf) {
+        self.save_dir = Some(path);
+    }
 }
 
 pub fn save_file_list() -> Result<Vec<PathBuf>, std::io::Error> {
@@ -75,11 +80,10 @@ pub fn gen_box_id(gd: &GameData) -> u64 {
     loop {
         let s = thread_rng().gen::<u64>();
 
-        // Check generated name is not used
-        let mut path = get_each_save_dir(gd);
-        path.push("maps");
-        path.push(format!("{:016x}", s));
-        if !path.exists() {
+        // Check generated id is not used by other maps
+        let mut used = false;
+        gd.region.visit_all_maps(|_, map| used |= map.id() == s);
+        if !used {
             return s;
         }
     }
@@ -93,8 +97,3 @@ fn get_save_dir() -> PathBuf {
 pub fn get_each_save_dir(gd: &GameData) -> PathBuf {
     get_save_dir().join(format!("{}.{}", gd.meta.save_name(), SAVE_EXTENSION))
 }
-
-/// Get map save directory
-pub fn get_map_dir(gd: &GameData) -> PathBuf {
-    get_each_save_dir(gd).join("maps")
-}
@@ -72,14 +72,32 @@ impl<'s> Game<'s> {
                 AdvanceScriptResult::Continue
             }
             ScriptYield::ShopSell => {
-                self.request_dialog_open(DialogOpenRequest::ShopSell);
+                let cid = if let Some(cid) = self.gd.script_exec.target_chara {
+                    cid
+                } else {
+                    return AdvanceScriptResult::Quit;
+                };
+                self.request_dialog_open(DialogOpenRequest::ShopSell { cid });
                 AdvanceScriptResult::Continue
             }
             ScriptYield::Quest => {
-                crate::game::quest::update_town_quest(&mut self.gd);
+                crate::game::quest::init_town_quest(&mut self.gd);
                 self.request_dialog_open(DialogOpenRequest::Quest);
                 AdvanceScriptResult::Continue
             }
+            ScriptYield::Teleport { mid, pos } => {
+                if self.gd.region.map_exist(mid) {
+                    let dest = if let Some(pos) = pos {
+                        Destination::MapIdWithPos(mid, pos)
+                    } else {
+                        Destination::MapId(mid)
+                    };
+                    crate::game::map::switch_map(self, dest);
+                } else {
+                    warn!("teleport destination {:?} does not exist", mid);
+                }
+                self.advance_script(None)
+            }
         }
     }
 }
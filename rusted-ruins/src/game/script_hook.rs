@@ -0,0 +1,46 @@
+//! Execute scripts hooked to game events
+
+use super::Game;
+use common::gamedata::*;
+use common::gobj;
+use fnv::FnvHashMap;
+use once_cell::sync::Lazy;
+
+/// Script ids for each hook kind
+static HOOKED_SCRIPTS: Lazy<FnvHashMap<ScriptHookKind, Vec<String>>> = Lazy::new(|| {
+    let mut hooked_scripts: FnvHashMap<ScriptHookKind, Vec<String>> = FnvHashMap::default();
+    for script in &gobj::get_objholder().script {
+        for &hook in &script.hooks {
+            hooked_scripts
+                .entry(hook)
+                .or_default()
+                .push(script.id.clone());
+        }
+    }
+    hooked_scripts
+});
+
+/// Queue scripts hooked to the event.
+/// Queued scripts are executed at the start of the next player turn.
+pub fn push_event(gd: &mut GameData, event: ScriptEvent) {
+    if let Some(ids) = HOOKED_SCRIPTS.get(&event.kind()) {
+        for id in ids {
+            gd.script_exec
+                .hook_queue
+                .push_back((id.clone(), event.clone()));
+        }
+    }
+}
+
+/// Execute queued scripts until a script opens a dialog
+pub fn exec_hooked_scripts(game: &mut Game) {
+    while game.dialog_open_request.is_none() && !game.se.during_exec() {
+        let (id, event) = if let Some(hooked) = game.gd.script_exec.hook_queue.pop_front() {
+            hooked
+        } else {
+            return;
+        };
+        game.gd.script_exec.event = Some(event);
+        game.start_script(&id, None);
+    }
+}
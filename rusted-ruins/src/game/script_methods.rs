@@ -1,6 +1,8 @@
 use crate::game::extrait::*;
 use crate::game::InfoGetter;
 use common::gamedata::*;
+use common::gobj;
+use common::objholder::CharaTemplateIdx;
 use script::{set_game_methods, GameMethods};
 
 pub fn init() {
@@ -11,6 +13,7 @@ pub fn init() {
             crate::game::region::gen_dungeon_max(gd, mid.rid());
         },
         receive_quest_rewards: crate::game::quest::receive_rewards,
+        start_quest: crate::game::quest::start_quest_by_id,
         receive_item: |gd, id, n| {
             let item = crate::game::item::gen::gen_item_from_id(id, 1);
             let il = gd.get_item_list_mut(ItemListLocation::PLAYER);
@@ -30,5 +33,56 @@ pub fn init() {
             gd.chara.get_mut(CharaId::Player).update();
             Ok(())
         },
+        update_chara: |gd, cid| gd.chara.get_mut(cid).update(),
+        spawn_chara: |gd, id, pos| {
+            let idx: CharaTemplateIdx = if let Some(idx) = gobj::id_to_idx_checked(id) {
+                idx
+            } else {
+                warn!("unknown chara template \"{}\"", id);
+                return None;
+            };
+            let ct = gobj::get_obj(idx);
+            let faction = ct.faction.unwrap_or_else(FactionId::unknown);
+            let chara = crate::game::chara::gen::create_chara(idx, ct.gen_level, faction, None);
+
+            let map = gd.get_current_map();
+            let pos = if let Some(pos) = pos {
+                if !map.is_passable(&chara, pos) || map.tile[pos].chara.is_some() {
+                    warn!("cannot spawn a character at {}", pos);
+                    return None;
+                }
+                pos
+            } else {
+                crate::game::map::choose_empty_tile(map)?
+            };
+
+            let mid = gd.get_current_mapid();
+            let cid = gd.add_chara_to_map(chara, mid);
+            gd.get_current_map_mut().locate_chara(cid, pos);
+            Some(cid)
+        },
+        join_party: |gd, cid| {
+            let new_cid = crate::game::party::join_party(gd, cid)?;
+            if gd.script_exec.target_chara == Some(cid) {
+                gd.script_exec.target_chara = Some(new_cid);
+            }
+            Some(new_cid)
+        },
+        leave_party: |gd, cid| {
+            let new_cid = crate::game::party::leave_party(gd, cid)?;
+            if gd.script_exec.target_chara == Some(cid) {
+                gd.script_exec.target_chara = Some(new_cid);
+            }
+            Some(new_cid)
+        },
+        log: |text_id, args| {
+            let mut table = fluent::FluentArgs::new();
+            for (k, v) in args {
+                table.add(k.as_str(), fluent::FluentValue::String(v.into()));
+            }
+            let s = crate::text::log_txt_with_args(text_id, Some(&table));
+            crate::log::push(s);
+            crate::log::new_line();
+        },
     });
 }
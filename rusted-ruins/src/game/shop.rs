@@ -1,12 +1,21 @@
 use crate::game::extrait::*;
 use crate::game::item::gen::gen_item_by_level;
 use common::gamedata::*;
+use common::gobj;
 use common::item_selector::ItemSelector;
+use common::obj::SiteGenObject;
 use common::sitegen::ShopGenData;
 use rules::RULES;
 
 pub fn buy_item(gd: &mut GameData, il: ItemLocation) {
-    let price = gd.get_item(il).0.price();
+    let cid = match il.0 {
+        ItemListLocation::Shop { cid } => cid,
+        _ => {
+            warn!("tried to buy an item not in a shop: {:?}", il);
+            return;
+        }
+    };
+    let price = buy_price(gd, cid, gd.get_item(il).0);
     if gd.player.has_money(price) {
         gd.player.sub_money(price);
         gd.move_item(
@@ -16,19 +25,128 @@ pub fn buy_item(gd: &mut GameData, il: ItemLocation) {
             },
             1,
         );
+        gain_negotiation_exp(gd, cid);
         gd.chara.get_mut(CharaId::Player).update();
     } else {
         game_log_i!("shop-lack-of-money"; chara=gd.chara.get(CharaId::Player));
     }
 }
 
-pub fn sell_item(gd: &mut GameData, il: ItemLocation) {
-    let price = gd.get_item(il).0.selling_price();
+pub fn sell_item(gd: &mut GameData, il: ItemLocation, cid: CharaId) {
+    let item = gd.get_item(il).0;
+    let price = if let Some(price) = sell_price(gd, cid, item) {
+        price
+    } else {
+        game_log_i!("shop-not-buy"; item=item);
+        return;
+    };
+    let id = gobj::idx_to_id(item.idx).to_owned();
     gd.player.add_money(price);
     gd.remove_item(il, 1);
+    if let Some(town) = shop_town_mut(gd, cid) {
+        town.add_sold_count(&id, 1);
+    }
+    gain_negotiation_exp(gd, cid);
     gd.chara.get_mut(CharaId::Player).update();
 }
 
+/// Calculate the price to buy an item from the shop of given shopkeeper
+pub fn buy_price(gd: &GameData, cid: CharaId, item: &Item) -> i64 {
+    let price = item.price() as f32 * rarity_factor(gd, cid, item) * (1.0 - discount(gd, cid));
+    std::cmp::max(price as i64, 0)
+}
+
+/// Calculate the price to sell an item to the shop of given shopkeeper.
+/// Returns None if the shop does not buy the item.
+pub fn sell_price(gd: &GameData, cid: CharaId, item: &Item) -> Option<i64> {
+    if item.container().is_some_and(|list| !list.is_empty()) {
+        return None;
+    }
+    let item_obj = gobj::get_obj(item.idx);
+    if !shop_item_selector(gd, cid).is_some_and(|selector| selector.is(item_obj)) {
+        return None;
+    }
+
+    let sold_count = shop_town(gd, cid).map_or(0, |town| town.sold_count(&item_obj.id));
+    let price = item.price() as f32
+        * RULES.town.selling_price_factor
+        * rarity_factor(gd, cid, item)
+        * (1.0 + discount(gd, cid))
+        / (1.0 + RULES.town.sold_price_factor * sold_count as f32);
+
+    // Selling price must not exceed buying price to prevent endless profit
+    Some(std::cmp::min(
+        std::cmp::max(price as i64, 0),
+        buy_price(gd, cid, item),
+    ))
+}
+
+/// Discount rate by the player's Charisma, Negotiation skill and
+/// the relation with the shopkeeper's faction
+fn discount(gd: &GameData, cid: CharaId) -> f32 {
+    let player = gd.chara.get(CharaId::Player);
+    let relation = gd.faction.get(gd.chara.get(cid).faction).value();
+    let discount = RULES.town.discount_per_cha * player.attr.cha as f32
+        + RULES.town.discount_per_negotiation * player.skill_level(SkillKind::Negotiation) as f32
+        + RULES.town.discount_per_relation * relation as f32;
+    let max = RULES.town.max_discount;
+    discount.clamp(-max, max)
+}
+
+/// Items that are rare in the town are expensive
+fn rarity_factor(gd: &GameData, cid: CharaId, item: &Item) -> f32 {
+    let n_in_stock: u32 = shop_town(gd, cid).map_or(0, |town| {
+        town.iter_shops()
+            .flat_map(|shop| shop.items.iter())
+            .filter(|(shop_item, _)| shop_item.idx == item.idx)
+            .map(|(_, n)| *n)
+            .sum()
+    });
+    1.0 + RULES.town.rarity_price_factor / (1.0 + n_in_stock as f32)
+}
+
+fn gain_negotiation_exp(gd: &mut GameData, cid: CharaId) {
+    let level = gd.chara.get(cid).level;
+    gd.chara.get_mut(CharaId::Player).skills.add_exp(
+        SkillKind::Negotiation,
+        RULES.exp.negotiation,
+        level,
+    );
+}
+
+fn shop_town(gd: &GameData, cid: CharaId) -> Option<&Town> {
+    let sid = match cid {
+        CharaId::OnSite { sid, .. } => sid,
+        _ => return None,
+    };
+    match gd.region.get_site(sid).content {
+        SiteContent::Town { ref town } => Some(town),
+        _ => None,
+    }
+}
+
+fn shop_town_mut(gd: &mut GameData, cid: CharaId) -> Option<&mut Town> {
+    let sid = match cid {
+        CharaId::OnSite { sid, .. } => sid,
+        _ => return None,
+    };
+    match gd.region.get_site_mut(sid).content {
+        SiteContent::Town { ref mut town } => Some(town),
+        _ => None,
+    }
+}
+
+/// Get the item selector of the shop of given shopkeeper
+fn shop_item_selector(gd: &GameData, cid: CharaId) -> Option<ItemSelector> {
+    let n = match cid {
+        CharaId::OnSite { n, .. } => n,
+        _ => return None,
+    };
+    let sg: &SiteGenObject = gobj::get_by_id(shop_town(gd, cid)?.id());
+    let shop_gen = sg.shops.iter().find(|shop_gen| shop_gen.chara_n == n)?;
+    Some(item_selector(shop_gen))
+}
+
 /// Update items on a shop
 pub fn update_items_on_shop(shop: &mut Shop, shop_gen: &ShopGenData) {
     shop.items.clear();
@@ -44,7 +162,19 @@ pub fn update_items_on_shop(shop: &mut Shop, shop_gen: &ShopGenData) {
 
 /// Generate new item at shops
 fn gen_shop_item(floor_level: u32, shop_gen: &ShopGenData) -> Option<Item> {
-    let item_selector: ItemSelector = if shop_gen.shop_kind.is_empty() {
+    let item_selector = item_selector(shop_gen);
+    let f = |item_obj: &ItemObject| {
+        if item_selector.is(item_obj) {
+            1.0
+        } else {
+            0.0
+        }
+    };
+    gen_item_by_level(floor_level, f, true)
+}
+
+fn item_selector(shop_gen: &ShopGenData) -> ItemSelector {
+    if shop_gen.shop_kind.is_empty() {
         shop_gen
             .selector
             .parse()
@@ -56,13 +186,5 @@ fn gen_shop_item(floor_level: u32, shop_gen: &ShopGenData) -> Option<Item> {
             .get(&shop_gen.shop_kind)
             .cloned()
             .unwrap_or_else(|| panic!("unknown shop kind\n{}", shop_gen.shop_kind))
-    };
-    let f = |item_obj: &ItemObject| {
-        if item_selector.is(item_obj) {
-            1.0
-        } else {
-            0.0
-        }
-    };
-    gen_item_by_level(floor_level, f, true)
+    }
 }
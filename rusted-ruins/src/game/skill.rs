@@ -19,8 +19,10 @@ impl SkillList {
         };
         let add_exp = add_exp as f32 * search_adjust_coeff(base_level, skill_level);
 
-        // Multiply by base_factor
-        let add_exp = (add_exp * RULES.exp.base_factor) as u32;
+        // Multiply by base_factor and the exp curve of the skill
+        let def = kind.def();
+        let add_exp = (add_exp * RULES.exp.base_factor * def.exp_factor
+            / (1.0 + def.exp_level_factor * skill_level as f32)) as u32;
 
         // Add exp
         if let Some(ref mut exp) = self.exp {
@@ -34,18 +34,26 @@ pub fn advance_game_time(game: &mut Game, advanced_clock: u32) {
     *CURRENT_TIME.lock().unwrap() = now;
 
     // Update checks
-    let before = before.into_date();
-    let now = now.into_date();
+    let before_date = before.into_date();
+    let now_date = now.into_date();
 
     // 10 minutes
-    if before.minute / 10 != now.minute / 10 {
+    if before_date.minute / 10 != now_date.minute / 10 {
         info!("time update process (10 minutes)");
         crate::game::item::time::update_item_time(&mut game.gd);
+        crate::game::quest::check_deadlines(&mut game.gd);
+    }
+
+    // hour
+    if before_date.hour != now_date.hour {
+        info!("time update process (hour)");
+        super::periodic::update_hourly(game, before);
     }
 
     // day
-    if before.day != now.day {
+    if before_date.day != now_date.day {
         info!("time update process (day)");
+        super::periodic::update_daily(game, before);
     }
 }
 
@@ -1,9 +1,11 @@
 use super::chara::preturn::preturn;
 use super::chara::CharaExt;
 use super::npc::process_npc_turn;
+use super::script_hook;
 use super::DialogOpenRequest;
 use super::{Game, GameState, InfoGetter};
 use common::gamedata::*;
+use common::gobj;
 
 /// Main game turn loop
 pub fn turn_loop(game: &mut Game) {
@@ -28,6 +30,8 @@ pub fn turn_loop(game: &mut Game) {
             if cid == CharaId::Player {
                 game.state = GameState::PlayerTurn;
                 game.update_before_player_turn();
+                script_hook::push_event(&mut game.gd, ScriptEvent::PlayerTurn);
+                script_hook::exec_hooked_scripts(game);
                 return;
             } else {
                 process_npc_turn(game, cid);
@@ -95,6 +99,9 @@ fn remove_dying_charas(game: &mut Game) -> bool {
                 game.request_dialog_open(DialogOpenRequest::GameOver);
                 return true;
             }
+            let template = gobj::idx_to_id(chara.template).to_owned();
+            script_hook::push_event(&mut game.gd, ScriptEvent::CharaDead { cid, template });
+            super::quest::check_dead_chara(&mut game.gd, cid);
             // Remove dying chara
             game.gd.remove_chara_from_map(cid);
             // If the current target is cid, remove it
@@ -59,20 +59,24 @@ pub fn update_view_map(game: &mut Game) {
     let player_pos = game.gd.player_pos();
     let player_view_range = game.gd.chara.get(CharaId::Player).attr.view_range;
 
-    view_map.visible[player_pos] = true;
-
-    for (_, pos) in MDistRangeIter::new(player_pos, player_view_range) {
-        if !map.is_inside(pos) {
-            continue;
-        }
+    for_each_visible_tile(map, player_pos, player_view_range, |pos| {
+        view_map.visible[pos] = true;
+    });
+}
 
-        for p in LineIter::new(player_pos, pos).skip(1) {
-            view_map.visible[p] = true;
-            if !map.tile[p].wall.is_empty() {
-                break;
+/// Call f for each tile visible from center within range.
+/// Visibility is symmetric, so the tiles can also see center.
+pub fn for_each_visible_tile<F: FnMut(Vec2d)>(map: &Map, center: Vec2d, range: i32, mut f: F) {
+    fov(
+        center,
+        range,
+        |pos| !map.is_inside(pos) || !map.tile[pos].wall.is_empty(),
+        |pos| {
+            if map.is_inside(pos) {
+                f(pos);
             }
-        }
-    }
+        },
+    );
 }
 
 // pub fn calc_visual_distance(map: &Map, orig: Vec2d, dist: Vec2d) -> Option<i32> {
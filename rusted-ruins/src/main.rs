@@ -27,6 +27,7 @@ mod draw;
 mod eventhandler;
 mod game;
 mod lang_selector;
+mod replay;
 mod screen;
 mod sdltypeconv;
 mod window;
@@ -35,16 +36,38 @@ fn main() {
     setup_logger();
     init_lazy();
     init_obj();
+    if config::CONFIG.check_rules {
+        check_rules();
+    }
     // Must be after init_obj()
     init_rules();
 
+    // Headless mode does not use SDL
+    if let Some(headless_config) = config::CONFIG.headless.as_ref() {
+        let _audio_context = audio::init_with_backend(Box::new(audio::NullBackend));
+        crate::game::script_methods::init();
+        script::enter(|se| {
+            crate::game::headless::run(se, headless_config);
+        });
+        return;
+    }
+
+    if config::CONFIG.replay.is_some() {
+        replay::use_dummy_drivers();
+    }
+
     let sdl_context = SdlContext::init();
+
     let mut screen = screen::Screen::new(&sdl_context.sdl_context);
 
     // Script engine initialization
     crate::game::script_methods::init();
     script::enter(|se| {
-        screen.main_loop(&sdl_context, se);
+        if let Some(path) = config::CONFIG.replay.as_ref() {
+            screen.replay_loop(&sdl_context, se, path);
+        } else {
+            screen.main_loop(&sdl_context, se);
+        }
     });
 }
 
@@ -61,14 +84,20 @@ impl SdlContext {
             sdl_context: sdl2::init().expect("Init Failed : SDL Context"),
             ttf_context: sdl2::ttf::init().expect("Init Failed : SDL_ttf Context"),
             _image: sdl2::image::init(sdl2::image::InitFlag::PNG).expect("Init Failed : SDL_Image"),
-            _audio_context: audio::init(
-                &config::get_data_dirs(),
-                crate::config::CONFIG.music_volume,
-            ),
+            _audio_context: init_audio(),
         }
     }
 }
 
+fn init_audio() -> audio::AudioContext {
+    let config = &crate::config::CONFIG;
+    if config.mute || config.replay.is_some() {
+        audio::init_with_backend(Box::new(audio::NullBackend))
+    } else {
+        audio::init(&config::get_data_dirs(), config.music_volume)
+    }
+}
+
 /// Initialize lazy values
 fn init_lazy() {
     config::init();
@@ -92,6 +121,30 @@ fn init_rules() {
     );
 }
 
+/// Load and validate rules, print all errors, and exit
+fn check_rules() -> ! {
+    let result = rules::check(
+        &*crate::config::ASSETS_DIR,
+        crate::config::ADDON_DIR.as_ref(),
+    );
+    match result {
+        Ok(errors) if errors.is_empty() => {
+            println!("rules: ok");
+            std::process::exit(0);
+        }
+        Ok(errors) => {
+            for e in &errors {
+                println!("{}", e);
+            }
+            println!("rules: {} broken reference(s)", errors.len());
+        }
+        Err(e) => {
+            println!("{}", e);
+        }
+    }
+    std::process::exit(1)
+}
+
 /// Setup logger. It is not game logger. It is for debug and warning infomation.
 fn setup_logger() {
     env_logger::builder().format_timestamp(None).init();
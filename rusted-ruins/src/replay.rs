@@ -0,0 +1,233 @@
+//! Recording and replaying player inputs.
+//!
+//! Replay data is a directory that contains the save data at the start of recording,
+//! a header file, and the recorded commands in JSON Lines format.
+//! The save data includes the RNG state, so replaying the commands reproduces the game.
+
+use crate::game::Command;
+use anyhow::{anyhow, Result};
+use common::gamedata::GameData;
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+const HEADER_FILE_NAME: &str = "header";
+const COMMANDS_FILE_NAME: &str = "commands";
+const SAVE_DIR_NAME: &str = "save";
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ReplayHeader {
+    /// Game version used for recording
+    pub version: String,
+    /// Recording was started just after new game creation
+    pub new_game: bool,
+}
+
+/// Writes commands to the replay directory
+pub struct Recorder {
+    file: BufWriter<File>,
+}
+
+impl Recorder {
+    /// Start recording. `game_save_dir` is the save directory of the game
+    /// to copy maps which are not loaded now.
+    pub fn start<P: AsRef<Path>>(
+        path: P,
+        gd: &GameData,
+        game_save_dir: Option<&Path>,
+        new_game: bool,
+    ) -> Result<Recorder> {
+        let path = path.as_ref();
+        fs::create_dir_all(path)?;
+
+        let save_dir = path.join(SAVE_DIR_NAME);
+        if save_dir.exists() {
+            fs::remove_dir_all(&save_dir)?;
+        }
+        if let Some(game_save_dir) = game_save_dir.filter(|p| p.exists()) {
+            copy_dir(game_save_dir, &save_dir)?;
+        }
+        // Loaded maps may have changes after the last saving.
+        // Changed flags must be kept for the next saving of the game.
+        gd.save_copy(&save_dir).map_err(|e| anyhow!("{}", e))?;
+
+        let header = ReplayHeader {
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+            new_game,
+        };
+        let recorder = Recorder::create(path, &header)?;
+        info!("Start recording to \"{}\"", path.display());
+        Ok(recorder)
+    }
+
+    /// Write the header, and create the commands file
+    fn create(path: &Path, header: &ReplayHeader) -> Result<Recorder> {
+        let file = BufWriter::new(File::create(path.join(HEADER_FILE_NAME))?);
+        serde_json::to_writer_pretty(file, header)?;
+
+        let file = BufWriter::new(File::create(path.join(COMMANDS_FILE_NAME))?);
+        Ok(Recorder { file })
+    }
+
+    pub fn record(&mut self, command: &Command) {
+        // Mouse cursor moving without buttons does not affect the game
+        if let Command::MouseState {
+            left_button: false,
+            right_button: false,
+            ..
+        } = command
+        {
+            return;
+        }
+
+        // Flush at each command to keep recorded data if the game crashes
+        let result = serde_json::to_writer(&mut self.file, command)
+            .map_err(|e| e.into())
+            .and_then(|_| writeln!(self.file))
+            .and_then(|_| self.file.flush());
+        if let Err(e) = result {
+            warn!("Failed to record command: {}", e);
+        }
+    }
+}
+
+/// Loaded replay data
+pub struct Replay {
+    pub header: ReplayHeader,
+    pub gd: GameData,
+    /// Save directory used during replaying
+    pub save_dir: PathBuf,
+    pub commands: VecDeque<Command>,
+}
+
+pub fn load<P: AsRef<Path>>(path: P) -> Result<Replay> {
+    let path = path.as_ref();
+
+    let header = read_header(path)?;
+    if header.version != env!("CARGO_PKG_VERSION") {
+        warn!(
+            "Replay data was recorded by different version {}",
+            header.version
+        );
+    }
+
+    let commands = read_commands(path.join(COMMANDS_FILE_NAME))?;
+
+    // Copy save data not to overwrite the replay data during replaying
+    let save_dir = std::env::temp_dir().join(format!("rusted-ruins-replay-{}", std::process::id()));
+    if save_dir.exists() {
+        fs::remove_dir_all(&save_dir)?;
+    }
+    copy_dir(path.join(SAVE_DIR_NAME), &save_dir)?;
+    let gd =
+        GameData::load(&save_dir, crate::config::CONFIG.fix_rand).map_err(|e| anyhow!("{}", e))?;
+
+    info!(
+        "Loaded replay \"{}\" with {} commands",
+        path.display(),
+        commands.len()
+    );
+    Ok(Replay {
+        header,
+        gd,
+        save_dir,
+        commands,
+    })
+}
+
+fn read_header(path: &Path) -> Result<ReplayHeader> {
+    let file = BufReader::new(File::open(path.join(HEADER_FILE_NAME))?);
+    Ok(serde_json::from_reader(file)?)
+}
+
+/// Read commands from a file in JSON Lines format
+pub fn read_commands<P: AsRef<Path>>(path: P) -> Result<VecDeque<Command>> {
+    let mut commands = VecDeque::new();
+    let file = BufReader::new(File::open(path)?);
+    for line in file.lines() {
+        let line = line?;
+        if line.is_empty() {
+            continue;
+        }
+        commands.push_back(serde_json::from_str(&line)?);
+    }
+    Ok(commands)
+}
+
+/// Use dummy SDL drivers because replaying does not need any window.
+/// Audio is disabled by the null audio backend.
+pub fn use_dummy_drivers() {
+    std::env::set_var("SDL_VIDEODRIVER", "dummy");
+}
+
+fn copy_dir<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dest: Q) -> Result<()> {
+    let src = src.as_ref();
+    let dest = dest.as_ref();
+
+    for entry in WalkDir::new(src) {
+        let entry = entry?;
+        let dest_path = dest.join(entry.path().strip_prefix(src)?);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&dest_path)?;
+        } else {
+            fs::copy(entry.path(), &dest_path)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::command::KeyState;
+    use geom::Direction;
+
+    #[test]
+    fn record_and_read_test() {
+        let path =
+            std::env::temp_dir().join(format!("rusted-ruins-replay-test-{}", std::process::id()));
+        fs::create_dir_all(&path).unwrap();
+
+        let header = ReplayHeader {
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+            new_game: true,
+        };
+        let commands = vec![
+            Command::Move { dir: Direction::N },
+            Command::MouseState {
+                x: 10,
+                y: 20,
+                left_button: true,
+                right_button: false,
+                key_state: KeyState::default(),
+                ui_only: false,
+            },
+            Command::Enter,
+            Command::ActionShortcut(2),
+        ];
+
+        let mut recorder = Recorder::create(&path, &header).unwrap();
+        for command in &commands {
+            recorder.record(command);
+        }
+        // Mouse moving without buttons is not recorded
+        recorder.record(&Command::MouseState {
+            x: 0,
+            y: 0,
+            left_button: false,
+            right_button: false,
+            key_state: KeyState::default(),
+            ui_only: false,
+        });
+        drop(recorder);
+
+        let read_header = read_header(&path).unwrap();
+        let read_commands = read_commands(path.join(COMMANDS_FILE_NAME)).unwrap();
+        fs::remove_dir_all(&path).unwrap();
+
+        assert_eq!(read_header, header);
+        assert_eq!(read_commands, commands);
+    }
+}
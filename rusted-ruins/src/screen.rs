@@ -1,6 +1,7 @@
 use crate::config::{CONFIG, SCREEN_CFG, UI_CFG};
 use sdl2::render::WindowCanvas;
 use sdl2::surface::Surface;
+use std::path::Path;
 use std::thread::sleep;
 use std::time::{Duration, Instant};
 
@@ -90,6 +91,45 @@ impl Screen {
         }
     }
 
+    /// Replay recorded commands without drawing and waiting
+    pub fn replay_loop(
+        &mut self,
+        sdl_context: &crate::SdlContext,
+        se: script::ScriptEngine,
+        path: &Path,
+    ) {
+        let replay = match crate::replay::load(path) {
+            Ok(replay) => replay,
+            Err(e) => {
+                error!("Failed to load replay \"{}\": {}", path.display(), e);
+                return;
+            }
+        };
+        let replay_save_dir = replay.save_dir.clone();
+        let texture_creator = self.canvas.texture_creator();
+        let mut window_manager = WindowManager::new(sdl_context, &texture_creator, se);
+        window_manager.start_replay(replay, &mut self.event_handler);
+
+        loop {
+            window_manager.update_without_drawing();
+            if !window_manager.advance_turn(&mut self.event_handler) {
+                break;
+            }
+            if self.event_handler.replay_finished() && window_manager.waiting_input() {
+                info!("Replay finished");
+                break;
+            }
+        }
+
+        if let Err(e) = std::fs::remove_dir_all(&replay_save_dir) {
+            warn!(
+                "Failed to remove replay save directory \"{}\": {}",
+                replay_save_dir.display(),
+                e
+            );
+        }
+    }
+
     fn redraw(&mut self, window_manager: &mut WindowManager) {
         self.canvas.set_viewport(None);
         self.canvas.set_clip_rect(None);
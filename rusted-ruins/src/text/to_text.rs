@@ -143,6 +143,7 @@ impl ToText for Command {
             OpenEquipWin => "command-open_equip_win",
             OpenExitWin => "command-open_exit_win",
             OpenGameInfoWin => "command-open_game_info_win",
+            OpenQuestWin => "command-open_quest_win",
             OpenHelpWin => "command-open_help_win",
             OpenStatusWin => "command-open_status_win",
             OpenItemMenu => "command-open_item_menu",
@@ -166,13 +167,24 @@ impl ToText for Command {
 
 impl ToText for Quest {
     fn to_text(&self) -> Cow<str> {
-        match self {
-            Quest::SlayMonsters { idx, .. } => {
-                let mut table = fluent::FluentArgs::new();
+        let mut table = fluent::FluentArgs::new();
+        match &self.objective {
+            QuestObjective::SlayMonsters { idx, .. } => {
                 table.add("monster", fluent::FluentValue::String(idx.to_text()));
-                crate::text::misc_txt_with_args("quest-slay_monsters", Some(&table)).into()
+            }
+            QuestObjective::FetchItems { idx, .. } | QuestObjective::Craft { idx, .. } => {
+                let item = obj_txt(gobj::idx_to_id(*idx));
+                table.add("item", fluent::FluentValue::String(item.into()));
+            }
+            QuestObjective::Deliver { name, .. } | QuestObjective::Escort { name, .. } => {
+                table.add("chara", fluent::FluentValue::String(name.into()));
+            }
+            QuestObjective::ExploreDungeon { name, .. } => {
+                table.add("site", fluent::FluentValue::String(name.into()));
             }
         }
+        let id = format!("quest-{}", self.objective.kind_id());
+        crate::text::misc_txt_with_args(&id, Some(&table)).into()
     }
 }
 
@@ -0,0 +1,151 @@
+use super::commonuse::*;
+use super::main_window::BUILD_PREVIEW;
+use super::widget::*;
+use crate::draw::border::draw_window_border;
+use crate::eventhandler::InputMode;
+use crate::game::building::{build_candidates, build_requirements};
+use crate::game::extrait::*;
+use crate::text::{obj_txt, ToText};
+use common::gamedata::*;
+use common::gobj;
+use common::objholder::ItemIdx;
+use geom::Vec2d;
+
+/// Window to choose an object to build at the tile
+pub struct BuildWindow {
+    rect: Rect,
+    pos: Vec2d,
+    list: ListWidget<(IconIdx, TextCache)>,
+    candidates: Vec<BuildObj>,
+    description: LabelWidget,
+    escape_click: bool,
+}
+
+impl BuildWindow {
+    pub fn new(gd: &GameData, pos: Vec2d) -> BuildWindow {
+        let c = &UI_CFG.build_window;
+        let rect: Rect = c.rect.into();
+        let candidates = build_candidates();
+
+        let mut list = ListWidget::with_scroll_bar(
+            (0i32, 0i32, rect.w as u32, rect.h as u32),
+            c.column_pos.clone(),
+            c.n_row,
+            true,
+        );
+        let items: Vec<(IconIdx, TextCache)> = candidates
+            .iter()
+            .map(|&obj| {
+                let icon = match obj {
+                    BuildObj::Wall(idx) => IconIdx::from(idx),
+                    BuildObj::Tile(idx) => IconIdx::from(idx),
+                    BuildObj::Deco(idx) => IconIdx::from(idx),
+                };
+                let (id, _, _) = build_requirements(obj);
+                let t = TextCache::one(obj_txt(id), FontKind::M, UI_CFG.color.normal_font.into());
+                (icon, t)
+            })
+            .collect();
+        list.set_items(items);
+
+        let mut w = BuildWindow {
+            rect,
+            pos,
+            list,
+            candidates,
+            description: LabelWidget::wrapped(
+                (
+                    0i32,
+                    (c.n_row as i32 + 1) * UI_CFG.list_widget.h_row_default as i32,
+                    rect.width(),
+                    0,
+                ),
+                "",
+                FontKind::M,
+                rect.width(),
+            ),
+            escape_click: false,
+        };
+        w.update_description(gd);
+        w
+    }
+
+    fn update_description(&mut self, gd: &GameData) {
+        let obj = if let Some(obj) = self.candidates.get(self.list.get_current_choice() as usize) {
+            *obj
+        } else {
+            self.description.set_text("");
+            *BUILD_PREVIEW.lock().unwrap() = None;
+            return;
+        };
+        *BUILD_PREVIEW.lock().unwrap() = Some((self.pos, obj));
+        let (_, build_skill, materials) = build_requirements(obj);
+        let player = gd.chara.get(CharaId::Player);
+        let player_skill = player.skill_level(SkillKind::Construction);
+
+        let materials = materials
+            .iter()
+            .map(|(item_id, n)| {
+                let idx: ItemIdx = gobj::id_to_idx(item_id);
+                format!(
+                    "{} x {} ({})",
+                    obj_txt(item_id),
+                    n,
+                    player.item_list.count(idx)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let skill_text = ui_txt_format!(
+            "label_text-build-required_skill"; skill_level=build_skill, player_skill_level=player_skill);
+        let materials_text = ui_txt_format!("label_text-build-materials"; materials=materials);
+        self.description
+            .set_text(&format!("{}\n{}", skill_text, materials_text));
+    }
+}
+
+impl Window for BuildWindow {
+    fn draw(&mut self, context: &mut Context, _game: &Game, _anim: Option<(&Animation, u32)>) {
+        draw_window_border(context, self.rect);
+        self.list.draw(context);
+        self.description.draw(context);
+    }
+}
+
+impl Drop for BuildWindow {
+    fn drop(&mut self) {
+        *BUILD_PREVIEW.lock().unwrap() = None;
+    }
+}
+
+impl DialogWindow for BuildWindow {
+    fn process_command(&mut self, command: &Command, pa: &mut DoPlayerAction) -> DialogResult {
+        check_escape_click!(self, command);
+
+        let command = command.relative_to(self.rect);
+        if let Some(response) = self.list.process_command(&command) {
+            match response {
+                ListWidgetResponse::Select(i) => {
+                    if let Some(&obj) = self.candidates.get(i as usize) {
+                        pa.start_build(self.pos, obj);
+                        return DialogResult::CloseAll;
+                    }
+                }
+                ListWidgetResponse::SelectionChanged | ListWidgetResponse::Scrolled => {
+                    self.update_description(pa.gd());
+                }
+                _ => (),
+            }
+            return DialogResult::Continue;
+        }
+
+        match command {
+            Command::Cancel => DialogResult::Close,
+            _ => DialogResult::Continue,
+        }
+    }
+
+    fn mode(&self) -> InputMode {
+        InputMode::Dialog
+    }
+}
@@ -13,38 +13,51 @@ pub fn create_creation_window_group(
     game: &Game,
     creation_kind: Option<CreationKind>,
 ) -> GroupWindow {
-    let mem_info = vec![
+    let mut mem_info = vec![
         MemberInfo {
             idx: gobj::id_to_idx("!tab-icon-creation-art"),
             text_id: "tab_text-creation_art",
-            creator: |game| Box::new(CreationWindow::new(&game.gd, CreationKind::Art)),
+            creator: |game| Box::new(CreationWindow::new(&game.gd, vec![CreationKind::Art])),
         },
         MemberInfo {
             idx: gobj::id_to_idx("!tab-icon-creation-construction"),
             text_id: "tab_text-creation_construction",
-            creator: |game| Box::new(CreationWindow::new(&game.gd, CreationKind::Construction)),
+            creator: |game| {
+                Box::new(CreationWindow::new(
+                    &game.gd,
+                    vec![CreationKind::Construction],
+                ))
+            },
         },
         MemberInfo {
             idx: gobj::id_to_idx("!tab-icon-creation-cooking"),
             text_id: "tab_text-creation_cooking",
-            creator: |game| Box::new(CreationWindow::new(&game.gd, CreationKind::Cooking)),
+            creator: |game| Box::new(CreationWindow::new(&game.gd, vec![CreationKind::Cooking])),
         },
         MemberInfo {
             idx: gobj::id_to_idx("!tab-icon-creation-craft"),
             text_id: "tab_text-creation_craft",
-            creator: |game| Box::new(CreationWindow::new(&game.gd, CreationKind::Craft)),
+            creator: |game| Box::new(CreationWindow::new(&game.gd, vec![CreationKind::Craft])),
         },
         MemberInfo {
             idx: gobj::id_to_idx("!tab-icon-creation-pharmacy"),
             text_id: "tab_text-creation_pharmacy",
-            creator: |game| Box::new(CreationWindow::new(&game.gd, CreationKind::Pharmacy)),
+            creator: |game| Box::new(CreationWindow::new(&game.gd, vec![CreationKind::Pharmacy])),
         },
         MemberInfo {
             idx: gobj::id_to_idx("!tab-icon-creation-smith"),
             text_id: "tab_text-creation_smith",
-            creator: |game| Box::new(CreationWindow::new(&game.gd, CreationKind::Smith)),
+            creator: |game| Box::new(CreationWindow::new(&game.gd, vec![CreationKind::Smith])),
         },
     ];
+    // Creation kinds defined by rules are shown in one tab
+    if !other_creation_kinds().is_empty() {
+        mem_info.push(MemberInfo {
+            idx: gobj::id_to_idx("!tab-icon-creation-craft"),
+            text_id: "tab_text-creation_other",
+            creator: |game| Box::new(CreationWindow::new(&game.gd, other_creation_kinds())),
+        });
+    }
     let rect: Rect = UI_CFG.creation_window.rect.into();
     let i = creation_kind.map(|creation_kind| match creation_kind {
         CreationKind::Art => 0,
@@ -53,6 +66,7 @@ pub fn create_creation_window_group(
         CreationKind::Craft => 3,
         CreationKind::Pharmacy => 4,
         CreationKind::Smith => 5,
+        _ => 6,
     });
     GroupWindow::new(
         "creation",
@@ -64,17 +78,24 @@ pub fn create_creation_window_group(
     )
 }
 
+fn other_creation_kinds() -> Vec<CreationKind> {
+    CreationKind::all()
+        .into_iter()
+        .filter(|kind| !kind.is_builtin())
+        .collect()
+}
+
 pub struct CreationWindow {
     rect: Rect,
     list: ListWidget<(IconIdx, TextCache)>,
-    recipes: Vec<&'static Recipe>,
-    kind: CreationKind,
+    recipes: Vec<(CreationKind, &'static Recipe)>,
+    kinds: Vec<CreationKind>,
     detail_dialog: Option<CreationDetailDialog>,
     escape_click: bool,
 }
 
 impl CreationWindow {
-    pub fn new(gd: &GameData, kind: CreationKind) -> CreationWindow {
+    pub fn new(gd: &GameData, kinds: Vec<CreationKind>) -> CreationWindow {
         let c = &UI_CFG.creation_window;
         let rect: Rect = c.rect.into();
 
@@ -87,23 +108,30 @@ impl CreationWindow {
                 true,
             ),
             recipes: Vec::new(),
-            kind,
+            kinds,
             detail_dialog: None,
             escape_click: false,
         };
 
-        w.update(gd, kind);
+        w.update(gd);
         w
     }
 
-    pub fn update(&mut self, gd: &GameData, kind: CreationKind) {
-        self.kind = kind;
-        self.recipes = creation::available_recipes(gd, kind);
+    pub fn update(&mut self, gd: &GameData) {
+        self.recipes = self
+            .kinds
+            .iter()
+            .flat_map(|&kind| {
+                creation::available_recipes(gd, kind)
+                    .into_iter()
+                    .map(move |recipe| (kind, recipe))
+            })
+            .collect();
 
         let items: Vec<(IconIdx, TextCache)> = self
             .recipes
             .iter()
-            .map(|r| {
+            .map(|(_, r)| {
                 let idx: ItemIdx = gobj::id_to_idx(&r.product);
                 let t = TextCache::one(
                     obj_txt(&r.product),
@@ -145,11 +173,8 @@ impl DialogWindow for CreationWindow {
         let command = command.relative_to(self.rect);
         if let Some(ListWidgetResponse::Select(i)) = self.list.process_command(&command) {
             // Any item is selected
-            self.detail_dialog = Some(CreationDetailDialog::new(
-                pa.gd(),
-                self.recipes[i as usize],
-                self.kind,
-            ));
+            let (kind, recipe) = self.recipes[i as usize];
+            self.detail_dialog = Some(CreationDetailDialog::new(pa.gd(), recipe, kind));
             return DialogResult::Continue;
         }
 
@@ -32,12 +32,35 @@ pub fn create_dialog_from_request(
         DialogOpenRequest::ShopBuy { cid } => {
             Box::new(ItemWindow::new(ItemWindowMode::ShopBuy { cid }, game))
         }
-        DialogOpenRequest::ShopSell => Box::new(ItemWindow::new(ItemWindowMode::ShopSell, game)),
+        DialogOpenRequest::ShopSell { cid } => {
+            Box::new(ItemWindow::new(ItemWindowMode::ShopSell { cid }, game))
+        }
+        DialogOpenRequest::ContainerPutIn { il } => Box::new(ItemWindow::new(
+            ItemWindowMode::PutIn { container: il },
+            game,
+        )),
+        DialogOpenRequest::ContainerTakeOut { il } => Box::new(ItemWindow::new(
+            ItemWindowMode::TakeOut { container: il },
+            game,
+        )),
+        DialogOpenRequest::StashPutIn { sid } => {
+            Box::new(ItemWindow::new(ItemWindowMode::PutInStash { sid }, game))
+        }
+        DialogOpenRequest::StashTakeOut { sid } => Box::new(ItemWindow::new(
+            ItemWindowMode::TakeOutFromStash { sid },
+            game,
+        )),
         DialogOpenRequest::RegisterAsShortcut { shortcut } => {
             Box::new(super::register_shortcut_dialog::RegisterShortcutDialog::new(shortcut))
         }
+        DialogOpenRequest::Build { pos } => {
+            Box::new(super::build_window::BuildWindow::new(&game.gd, pos))
+        }
         DialogOpenRequest::PickUpItem => Box::new(ItemWindow::new(ItemWindowMode::PickUp, game)),
-        DialogOpenRequest::Quest => Box::new(super::quest_window::QuestWindow::new(game)),
+        DialogOpenRequest::Quest => Box::new(super::quest_window::QuestWindow::new(
+            super::quest_window::QuestWindowMode::Board,
+            game,
+        )),
         DialogOpenRequest::GameOver => Box::new(super::exit_window::GameOverWindow::new()),
     })
 }
@@ -12,6 +12,7 @@ const COMMANDS: &[Command] = &[
     Command::OpenHelpWin,
     Command::OpenStatusWin,
     Command::OpenGameInfoWin,
+    Command::OpenQuestWin,
     Command::OpenItemMenu,
     Command::OpenEquipWin,
     Command::EatItem,
@@ -7,12 +7,14 @@ use crate::text::ToText;
 use common::gamedata::*;
 use common::gobj;
 use common::obj::UiImgObject;
+use common::objholder::UiImgIdx;
 use rules::RULES;
 
 #[derive(Clone, Copy, Debug)]
 pub enum BarIndicatorKind {
     Hp,
     Sp,
+    Mp,
 }
 
 impl BarIndicatorKind {
@@ -20,6 +22,16 @@ impl BarIndicatorKind {
         match self {
             BarIndicatorKind::Hp => "!label-hp",
             BarIndicatorKind::Sp => "!label-sp",
+            BarIndicatorKind::Mp => "!label-mp",
+        }
+    }
+
+    /// Used if the label image is not found
+    fn label_text(self) -> &'static str {
+        match self {
+            BarIndicatorKind::Hp => "HP",
+            BarIndicatorKind::Sp => "SP",
+            BarIndicatorKind::Mp => "MP",
         }
     }
 
@@ -27,6 +39,7 @@ impl BarIndicatorKind {
         match self {
             BarIndicatorKind::Hp => GaugeColorMode::Hp,
             BarIndicatorKind::Sp => GaugeColorMode::Sp,
+            BarIndicatorKind::Mp => GaugeColorMode::Mp,
         }
     }
 
@@ -34,6 +47,7 @@ impl BarIndicatorKind {
         match self {
             BarIndicatorKind::Hp => SCREEN_CFG.hp_indicator.into(),
             BarIndicatorKind::Sp => SCREEN_CFG.sp_indicator.into(),
+            BarIndicatorKind::Mp => SCREEN_CFG.mp_indicator.into(),
         }
     }
 }
@@ -42,7 +56,7 @@ pub struct BarIndicator {
     rect: Rect,
     kind: BarIndicatorKind,
     guage: GaugeWidget,
-    label: ImageWidget,
+    label: Box<dyn WidgetTrait<Response = ()>>,
 }
 
 impl BarIndicator {
@@ -50,10 +64,23 @@ impl BarIndicator {
         let rect: Rect = kind.rect();
 
         // Label is drawed over the guage
-        let label_img: &'static UiImgObject = gobj::get_by_id(kind.label_id());
-        let (label_w, label_h) = (label_img.img.w, label_img.img.h);
-        // Centering of the guage
-        let label_rect = Rect::from_center((rect.w / 2, rect.h / 2), label_w, label_h);
+        let label: Box<dyn WidgetTrait<Response = ()>> =
+            if gobj::id_to_idx_checked::<UiImgIdx>(kind.label_id()).is_some() {
+                let label_img: &'static UiImgObject = gobj::get_by_id(kind.label_id());
+                let (label_w, label_h) = (label_img.img.w, label_img.img.h);
+                // Centering of the guage
+                let label_rect = Rect::from_center((rect.w / 2, rect.h / 2), label_w, label_h);
+                Box::new(ImageWidget::ui_img(label_rect, kind.label_id()))
+            } else {
+                Box::new(
+                    LabelWidget::bordered(
+                        Rect::new(0, 0, rect.width(), rect.height()),
+                        kind.label_text(),
+                        FontKind::S,
+                    )
+                    .centering(),
+                )
+            };
 
         BarIndicator {
             rect,
@@ -64,7 +91,7 @@ impl BarIndicator {
                 1.0,
                 kind.color_mode(),
             ),
-            label: ImageWidget::ui_img(label_rect, kind.label_id()),
+            label,
         }
     }
 }
@@ -81,6 +108,10 @@ impl Window for BarIndicator {
                 let r = &RULES.chara;
                 self.guage.set_params(r.sp_starving, r.sp_max, sp);
             }
+            BarIndicatorKind::Mp => {
+                let (max_mp, mp) = game.gd.player_mp();
+                self.guage.set_params(0.0, max_mp as f32, mp as f32);
+            }
         }
 
         context.set_viewport(self.rect);
@@ -11,6 +11,8 @@ enum ItemMenuItem {
     Infomation,
     RegisterAsShortcut(ActionShortcut),
     DropAll,
+    PutIn,
+    TakeOut,
 }
 
 pub struct ItemMenu {
@@ -45,6 +47,21 @@ impl ItemMenu {
             menu_items.push(ItemMenuItem::DropAll);
         }
 
+        // Container
+        let is_container = gd.get_item(il).0.kind == ItemKind::Container;
+        let container_accessible = matches!(
+            il.0,
+            ItemListLocation::Chara {
+                cid: CharaId::Player
+            } | ItemListLocation::OnMap { .. }
+        );
+        if is_container && container_accessible {
+            choices.push(ui_txt("item_menu-put_in"));
+            menu_items.push(ItemMenuItem::PutIn);
+            choices.push(ui_txt("item_menu-take_out"));
+            menu_items.push(ItemMenuItem::TakeOut);
+        }
+
         // Register as shortcut
         let item_idx = gd.get_item(il).0.idx;
         let shortcut = match mode {
@@ -94,6 +111,14 @@ impl DialogWindow for ItemMenu {
                             pa.drop_item(il, n);
                             DialogResult::Special(SpecialDialogResult::ItemListUpdate)
                         }
+                        ItemMenuItem::PutIn => {
+                            pa.request_dialog_open(DialogOpenRequest::ContainerPutIn { il });
+                            DialogResult::Close
+                        }
+                        ItemMenuItem::TakeOut => {
+                            pa.request_dialog_open(DialogOpenRequest::ContainerTakeOut { il });
+                            DialogResult::Close
+                        }
                         ItemMenuItem::RegisterAsShortcut(shortcut) => {
                             pa.request_dialog_open(DialogOpenRequest::RegisterAsShortcut {
                                 shortcut,
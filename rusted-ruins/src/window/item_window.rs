@@ -7,6 +7,7 @@ use crate::draw::border::draw_window_border;
 use crate::eventhandler::InputMode;
 use crate::game::extrait::*;
 use crate::game::item::filter::*;
+use crate::game::shop::{buy_price, sell_price};
 use crate::game::{DialogOpenRequest, Game, InfoGetter};
 use crate::text::ToText;
 use common::gamedata::*;
@@ -24,10 +25,24 @@ pub enum ItemWindowMode {
     Use,
     Release,
     Read,
-    ShopSell,
+    ShopSell {
+        cid: CharaId,
+    },
     ShopBuy {
         cid: CharaId,
     },
+    PutIn {
+        container: ItemLocation,
+    },
+    TakeOut {
+        container: ItemLocation,
+    },
+    PutInStash {
+        sid: SiteId,
+    },
+    TakeOutFromStash {
+        sid: SiteId,
+    },
     Select {
         ill: ItemListLocation,
         filter: ItemFilter,
@@ -194,21 +209,21 @@ impl ItemWindow {
         match &self.mode {
             ItemWindowMode::List => {
                 let filtered_list = gd.get_filtered_item_list(ill_player, ItemFilter::all());
-                self.update_list(filtered_list);
+                self.update_list(filtered_list, gd);
             }
             ItemWindowMode::PickUp => {
                 let filtered_list = gd.get_filtered_item_list(ill_ground, ItemFilter::all());
-                self.update_list(filtered_list);
+                self.update_list(filtered_list, gd);
             }
             ItemWindowMode::Drop => {
                 let filtered_list = gd.get_filtered_item_list(ill_player, ItemFilter::all());
-                self.update_list(filtered_list);
+                self.update_list(filtered_list, gd);
             }
             ItemWindowMode::Throw => {
                 let player_str = gd.chara.get(CharaId::Player).attr.str;
                 let filter = ItemFilter::new().throwable(Some(player_str));
                 let filtered_list = gd.get_filtered_item_list(ill_player, filter);
-                self.update_list(filtered_list);
+                self.update_list(filtered_list, gd);
             }
             ItemWindowMode::Drink => {
                 let filtered_list = gd.get_merged_filtered_item_list(
@@ -216,7 +231,7 @@ impl ItemWindow {
                     ill_player,
                     ItemFilter::new().drinkable(true),
                 );
-                self.update_list(filtered_list);
+                self.update_list(filtered_list, gd);
             }
             ItemWindowMode::Eat => {
                 let filtered_list = gd.get_merged_filtered_item_list(
@@ -224,7 +239,7 @@ impl ItemWindow {
                     ill_player,
                     ItemFilter::new().eatable(true),
                 );
-                self.update_list(filtered_list);
+                self.update_list(filtered_list, gd);
             }
             ItemWindowMode::Use => {
                 let filtered_list = gd.get_merged_filtered_item_list(
@@ -232,7 +247,7 @@ impl ItemWindow {
                     ill_player,
                     ItemFilter::new().usable(true),
                 );
-                self.update_list(filtered_list);
+                self.update_list(filtered_list, gd);
             }
             ItemWindowMode::Release => {
                 let filtered_list = gd.get_merged_filtered_item_list(
@@ -240,7 +255,7 @@ impl ItemWindow {
                     ill_player,
                     ItemFilter::new().kind_rough(ItemKindRough::MagicDevice),
                 );
-                self.update_list(filtered_list);
+                self.update_list(filtered_list, gd);
             }
             ItemWindowMode::Read => {
                 let filtered_list = gd.get_merged_filtered_item_list(
@@ -248,29 +263,47 @@ impl ItemWindow {
                     ill_player,
                     ItemFilter::new().readable(true),
                 );
-                self.update_list(filtered_list);
+                self.update_list(filtered_list, gd);
             }
             ItemWindowMode::ShopBuy { cid } => {
                 let ill = ItemListLocation::Shop { cid: *cid };
                 let filtered_list = gd.get_filtered_item_list(ill, ItemFilter::new());
-                self.update_list(filtered_list);
+                self.update_list(filtered_list, gd);
             }
-            ItemWindowMode::ShopSell => {
+            ItemWindowMode::ShopSell { .. } => {
                 let ill = ItemListLocation::Chara {
                     cid: CharaId::Player,
                 };
                 let filtered_list = gd.get_filtered_item_list(ill, ItemFilter::new());
-                self.update_list(filtered_list);
+                self.update_list(filtered_list, gd);
+            }
+            ItemWindowMode::PutIn { .. } => {
+                let filtered_list = gd.get_filtered_item_list(ill_player, ItemFilter::all());
+                self.update_list(filtered_list, gd);
+            }
+            ItemWindowMode::TakeOut { container } => {
+                let ill = ItemListLocation::container(*container).expect("nested container");
+                let filtered_list = gd.get_filtered_item_list(ill, ItemFilter::all());
+                self.update_list(filtered_list, gd);
+            }
+            ItemWindowMode::PutInStash { .. } => {
+                let filtered_list = gd.get_filtered_item_list(ill_player, ItemFilter::all());
+                self.update_list(filtered_list, gd);
+            }
+            ItemWindowMode::TakeOutFromStash { sid } => {
+                let ill = ItemListLocation::Stash { sid: *sid };
+                let filtered_list = gd.get_filtered_item_list(ill, ItemFilter::all());
+                self.update_list(filtered_list, gd);
             }
             ItemWindowMode::Select { ill, filter, .. } => {
                 let filtered_list = gd.get_filtered_item_list(*ill, *filter);
-                self.update_list(filtered_list);
+                self.update_list(filtered_list, gd);
             }
         }
         self.update_label(gd);
     }
 
-    fn update_list(&mut self, list: FilteredItemList) {
+    fn update_list(&mut self, list: FilteredItemList, gd: &GameData) {
         self.list.set_n_item(list.clone().count() as u32);
 
         let mode = &self.mode;
@@ -289,8 +322,14 @@ impl ItemWindow {
 
             // Infomation displayed in the right column
             let additional_info = match mode {
-                ItemWindowMode::ShopBuy { .. } => format!("{}G", item.price()),
-                ItemWindowMode::ShopSell => format!("{}G", item.selling_price()),
+                ItemWindowMode::ShopBuy { cid } => format!("{}G", buy_price(gd, *cid, item)),
+                ItemWindowMode::ShopSell { cid } => {
+                    if let Some(price) = sell_price(gd, *cid, item) {
+                        format!("{}G", price)
+                    } else {
+                        "-".to_owned()
+                    }
+                }
                 _ => format!("{:.2}kg", item.w() as f32 / 1000.0),
             };
 
@@ -380,8 +419,36 @@ impl ItemWindow {
                 self.update_by_mode(pa.gd());
                 DialogResult::Continue
             }
-            ItemWindowMode::ShopSell => {
-                pa.sell_item(il);
+            ItemWindowMode::ShopSell { cid } => {
+                pa.sell_item(il, cid);
+                self.update_by_mode(pa.gd());
+                DialogResult::Continue
+            }
+            ItemWindowMode::PutIn { container } => {
+                if let Some(container) = pa.put_in_container(container, il) {
+                    self.mode = ItemWindowMode::PutIn { container };
+                    self.update_by_mode(pa.gd());
+                    DialogResult::Continue
+                } else {
+                    DialogResult::Close
+                }
+            }
+            ItemWindowMode::TakeOut { container } => {
+                if let Some(container) = pa.take_out_from_container(container, il) {
+                    self.mode = ItemWindowMode::TakeOut { container };
+                    self.update_by_mode(pa.gd());
+                    DialogResult::Continue
+                } else {
+                    DialogResult::Close
+                }
+            }
+            ItemWindowMode::PutInStash { sid } => {
+                pa.put_in_stash(sid, il);
+                self.update_by_mode(pa.gd());
+                DialogResult::Continue
+            }
+            ItemWindowMode::TakeOutFromStash { .. } => {
+                pa.take_out_from_stash(il);
                 self.update_by_mode(pa.gd());
                 DialogResult::Continue
             }
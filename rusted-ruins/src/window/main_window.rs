@@ -4,7 +4,7 @@ use crate::draw::mainwin::{MainWinDrawer, TargetModeDrawInfo};
 use crate::game::command::MouseButton;
 use crate::game::{Animation, Command, DoPlayerAction, Game, InfoGetter, Target};
 use crate::window::{DialogWindow, Window};
-use common::gamedata::Effect;
+use common::gamedata::{BuildObj, Effect};
 use geom::*;
 use once_cell::sync::Lazy;
 use sdl2::rect::Rect;
@@ -13,6 +13,9 @@ use std::sync::Mutex;
 
 pub(super) static CENTERING_START_REQ: Lazy<Mutex<Option<Vec2d>>> = Lazy::new(|| Mutex::new(None));
 pub(super) static CENTERING_STOP_REQ: Lazy<AtomicBool> = Lazy::new(|| AtomicBool::new(false));
+/// The object and the tile to draw as a preview of building
+pub(super) static BUILD_PREVIEW: Lazy<Mutex<Option<(Vec2d, BuildObj)>>> =
+    Lazy::new(|| Mutex::new(None));
 
 enum MainWindowMode {
     Normal,
@@ -221,6 +224,7 @@ impl Window for MainWindow {
             self.centering_tile,
             self.hover_tile,
             self.mode.get_draw_info().map(|i| &*i),
+            *BUILD_PREVIEW.lock().unwrap(),
         );
     }
 }
@@ -2,6 +2,7 @@
 mod tools;
 
 mod active_skill_window;
+mod build_window;
 mod choose_window;
 mod creation_window;
 mod dialogreq;
@@ -39,8 +40,10 @@ mod winpos;
 use self::log_window::LogWindow;
 use self::main_window::MainWindow;
 use self::widget::WidgetTrait;
+use crate::config::CONFIG;
 use crate::eventhandler::EventHandler;
 use crate::game::{Command, DoPlayerAction, GameState, InfoGetter, UiRequest};
+use crate::replay::{Recorder, Replay};
 use crate::SdlContext;
 use common::gamedata::*;
 use geom::*;
@@ -180,6 +183,7 @@ pub struct WindowManager<'sdl, 't, 's> {
     anim: Option<Animation>,
     passed_frame: u32,
     window_stack: Vec<Box<dyn DialogWindow>>,
+    recorder: Option<Recorder>,
 }
 
 impl<'sdl, 't, 's> WindowManager<'sdl, 't, 's> {
@@ -202,9 +206,17 @@ impl<'sdl, 't, 's> WindowManager<'sdl, 't, 's> {
             anim: None,
             passed_frame: 0,
             window_stack,
+            recorder: None,
         }
     }
 
+    /// Start game from replay data. Recorded commands are given to event_handler.
+    pub fn start_replay(&mut self, replay: Replay, event_handler: &mut EventHandler) {
+        self.start_game(replay.gd, replay.header.new_game);
+        self.game.set_save_dir(replay.save_dir);
+        event_handler.start_replay(replay.commands);
+    }
+
     // If return value is false, quit.
     pub fn advance_turn(&mut self, event_handler: &mut EventHandler) -> bool {
         // Animation must be finished before
@@ -300,6 +312,21 @@ impl<'sdl, 't, 's> WindowManager<'sdl, 't, 's> {
         self.anim.is_some()
     }
 
+    /// Used instead of draw() when the screen is not displayed. Animations are skipped.
+    pub fn update_without_drawing(&mut self) {
+        self.anim = None;
+        self.passed_frame = 0;
+        while self.game.pop_animation().is_some() {}
+        if self.mode.is_on_game() {
+            self.game.update_before_drawing();
+        }
+    }
+
+    /// Returns true if the game is waiting player's input
+    pub fn waiting_input(&self) -> bool {
+        self.game.get_state() == GameState::PlayerTurn && self.anim.is_none()
+    }
+
     // If return value is false, quit.
     pub fn process_command(&mut self, event_handler: &mut EventHandler) -> bool {
         text_input::check_mode(&self.text_input_util);
@@ -316,6 +343,10 @@ impl<'sdl, 't, 's> WindowManager<'sdl, 't, 's> {
         }
         let command = command.unwrap();
 
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder.record(&command);
+        }
+
         let command = if !self.window_stack.is_empty() {
             let mut tail = self.window_stack.len() - 1;
             let mut dialog_result = {
@@ -519,6 +550,13 @@ impl<'sdl, 't, 's> WindowManager<'sdl, 't, 's> {
                 let dialog = Box::new(game_info_window::GameInfoWindow::new(pa.game()));
                 self.push_dialog_window(dialog);
             }
+            Command::OpenQuestWin => {
+                let dialog = Box::new(quest_window::QuestWindow::new(
+                    quest_window::QuestWindowMode::Undertaken,
+                    pa.game(),
+                ));
+                self.push_dialog_window(dialog);
+            }
             Command::PickUpItem => {
                 if !pa.gd().item_on_player_tile().is_empty() {
                     let item_window = ItemWindow::new(ItemWindowMode::PickUp, pa.game());
@@ -591,14 +629,7 @@ impl<'sdl, 't, 's> WindowManager<'sdl, 't, 's> {
                     // Load from file
                     SpecialDialogResult::NewGameStart(gd) => {
                         info!("Load game from file");
-                        self.window_stack.clear();
-                        self.mode = WindowManageMode::OnGame(GameWindows::new());
-
-                        let game = Game::new(*gd, self.se.clone());
-                        self.game = game;
-                        self.game.update_before_player_turn();
-                        game_log_i!("start"; version=env!("CARGO_PKG_VERSION"));
-                        audio::play_music(&self.game.gd.get_current_map().music);
+                        self.start_game(*gd, false);
                     }
                     _ => unreachable!(),
                 }
@@ -606,14 +637,7 @@ impl<'sdl, 't, 's> WindowManager<'sdl, 't, 's> {
             WindowManageMode::NewGame(_) => match result {
                 SpecialDialogResult::NewGameStart(gd) => {
                     info!("Create newgame from dialog result");
-                    self.window_stack.clear();
-                    self.mode = WindowManageMode::OnGame(GameWindows::new());
-
-                    let game = Game::new(*gd, self.se.clone());
-                    self.game = game;
-                    self.game.update_before_player_turn();
-                    self.game.start_new_game();
-                    game_log_i!("start"; version=env!("CARGO_PKG_VERSION"));
+                    self.start_game(*gd, true);
                 }
                 _ => unreachable!(),
             },
@@ -630,6 +654,34 @@ impl<'sdl, 't, 's> WindowManager<'sdl, 't, 's> {
         }
     }
 
+    fn start_game(&mut self, gd: GameData, new_game: bool) {
+        self.window_stack.clear();
+        self.mode = WindowManageMode::OnGame(GameWindows::new());
+
+        let game = Game::new(gd, self.se.clone());
+        self.game = game;
+
+        if let Some(path) = CONFIG.record.as_ref() {
+            self.recorder =
+                match Recorder::start(path, &self.game.gd, self.game.save_dir(), new_game) {
+                    Ok(recorder) => Some(recorder),
+                    Err(e) => {
+                        warn!("Failed to start recording: {}", e);
+                        None
+                    }
+                };
+        }
+
+        self.game.update_before_player_turn();
+        if new_game {
+            self.game.start_new_game();
+        }
+        game_log_i!("start"; version=env!("CARGO_PKG_VERSION"));
+        if !new_game {
+            audio::play_music(&self.game.gd.get_current_map().music);
+        }
+    }
+
     fn push_dialog_window(&mut self, w: Box<dyn DialogWindow>) {
         w.sound(true);
         if !w.mainwin_cursor() {
@@ -704,6 +756,7 @@ struct GameWindows {
     shortcut_list: toolbar::ShortcutList,
     indicator_hp: indicator::BarIndicator,
     indicator_sp: indicator::BarIndicator,
+    indicator_mp: indicator::BarIndicator,
     floor_info: indicator::FloorInfo,
     status_info: indicator::StatusInfo,
     time_info: indicator::TimeInfo,
@@ -734,6 +787,7 @@ impl GameWindows {
             shortcut_list: toolbar::ShortcutList::new(),
             indicator_hp: BarIndicator::new(BarIndicatorKind::Hp),
             indicator_sp: BarIndicator::new(BarIndicatorKind::Sp),
+            indicator_mp: BarIndicator::new(BarIndicatorKind::Mp),
             floor_info: FloorInfo::new(),
             status_info: StatusInfo::new(),
             time_info: TimeInfo::new(),
@@ -758,6 +812,7 @@ impl GameWindows {
         self.shortcut_list.draw(context, game, anim);
         self.indicator_hp.draw(context, game, anim);
         self.indicator_sp.draw(context, game, anim);
+        self.indicator_mp.draw(context, game, anim);
         self.floor_info.draw(context, game, anim);
         self.status_info.draw(context, game, anim);
         self.time_info.draw(context, game, anim);
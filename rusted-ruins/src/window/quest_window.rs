@@ -4,10 +4,20 @@ use super::widget::*;
 use crate::draw::border::draw_window_border;
 use crate::eventhandler::InputMode;
 use crate::game::quest::available_quests;
-use crate::text::ToText;
-use common::gamedata::Quest;
+use crate::text::{misc_txt, obj_txt, ToText};
+use common::gamedata::{Quest, QuestObjective, QuestState};
+use common::gobj;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum QuestWindowMode {
+    /// Quests available at the town quest board
+    Board,
+    /// Quests undertaken by the player
+    Undertaken,
+}
 
 pub struct QuestWindow {
+    mode: QuestWindowMode,
     rect: Rect,
     list: TextListWidget,
     description: LabelWidget,
@@ -16,9 +26,10 @@ pub struct QuestWindow {
 }
 
 impl QuestWindow {
-    pub fn new(game: &Game) -> QuestWindow {
+    pub fn new(mode: QuestWindowMode, game: &Game) -> QuestWindow {
         let rect = UI_CFG.quest_window.rect.into();
         let mut w = QuestWindow {
+            mode,
             rect,
             list: TextListWidget::new(
                 (0i32, 0i32, rect.w as u32, rect.h as u32),
@@ -46,22 +57,43 @@ impl QuestWindow {
     }
 
     pub fn update(&mut self, game: &Game) {
-        let rows: Vec<TextCache> = available_quests(&game.gd)
+        let quests: Vec<(Option<QuestState>, &Quest)> = match self.mode {
+            QuestWindowMode::Board => available_quests(&game.gd)
+                .iter()
+                .map(|quest| (None, quest))
+                .collect(),
+            QuestWindowMode::Undertaken => game
+                .gd
+                .quest
+                .iter()
+                .map(|(state, quest)| (Some(*state), quest))
+                .collect(),
+        };
+
+        let rows: Vec<TextCache> = quests
             .iter()
-            .map(|quest| {
-                let text = quest.to_text();
+            .map(|(state, quest)| {
+                let text = if let Some(state) = state {
+                    let state_text_id = match state {
+                        QuestState::Active => "quest-state-active",
+                        QuestState::Completed => "quest-state-completed",
+                        QuestState::RewardReceived => "quest-state-reward_received",
+                        QuestState::Failed => "quest-state-failed",
+                    };
+                    format!("{} [{}]", quest.to_text(), misc_txt(state_text_id))
+                } else {
+                    quest.to_text().into()
+                };
                 TextCache::one(text, FontKind::M, UI_CFG.color.normal_font.into())
             })
             .collect();
 
-        let n_item = rows.len();
         self.list.set_items(rows);
 
-        if n_item == 0 {
-            self.description.set_text("");
-        } else {
-            let q = &available_quests(&game.gd)[self.list.get_current_choice() as usize];
+        if let Some((_, q)) = quests.get(self.list.get_current_choice() as usize) {
             self.description.set_text(&quest_decription_text(q));
+        } else {
+            self.description.set_text("");
         }
     }
 }
@@ -94,8 +126,19 @@ impl DialogWindow for QuestWindow {
                     };
                     self.dialog = None;
                     if n == 0 {
-                        // Undertake quest
-                        pa.undertake_quest(n);
+                        match self.mode {
+                            QuestWindowMode::Board => {
+                                pa.undertake_quest(self.list.get_current_choice());
+                            }
+                            QuestWindowMode::Undertaken => {
+                                let i = self.list.get_current_choice();
+                                match pa.gd().quest.get(i as usize).map(|(state, _)| *state) {
+                                    Some(QuestState::Active) => pa.abandon_quest(i),
+                                    Some(QuestState::Failed) => pa.dismiss_quest(i),
+                                    _ => (),
+                                }
+                            }
+                        }
                         self.update(pa.game())
                     }
                 }
@@ -107,14 +150,25 @@ impl DialogWindow for QuestWindow {
         let command = command.relative_to(self.rect);
         if let Some(response) = self.list.process_command(&command) {
             match response {
-                ListWidgetResponse::Select(_) => {
-                    // Any item is selected
-                    self.dialog = Some(MsgDialog::with_yesno(
-                        &crate::text::ui_txt("dialog-undertake_quest"),
-                        |_, n| DialogResult::CloseWithValue(DialogCloseValue::Index(n)),
-                    ));
+                ListWidgetResponse::Select(i) => {
+                    // Active quests can be abandoned, and failed quests can be dismissed
+                    let msg = match self.mode {
+                        QuestWindowMode::Board => "dialog-undertake_quest",
+                        QuestWindowMode::Undertaken => {
+                            match pa.gd().quest.get(i as usize).map(|(state, _)| *state) {
+                                Some(QuestState::Active) => "dialog-abandon_quest",
+                                Some(QuestState::Failed) => "dialog-dismiss_quest",
+                                _ => return DialogResult::Continue,
+                            }
+                        }
+                    };
+                    self.dialog = Some(MsgDialog::with_yesno(&crate::text::ui_txt(msg), |_, n| {
+                        DialogResult::CloseWithValue(DialogCloseValue::Index(n))
+                    }));
+                }
+                ListWidgetResponse::SelectionChanged | ListWidgetResponse::Scrolled => {
+                    self.update(pa.game());
                 }
-                ListWidgetResponse::Scrolled => {}
                 _ => (),
             }
             return DialogResult::Continue;
@@ -136,9 +190,47 @@ impl DialogWindow for QuestWindow {
 }
 
 fn quest_decription_text(quest: &Quest) -> String {
-    match quest {
-        Quest::SlayMonsters { idx, goal, .. } => {
+    let mut text = quest_objective_text(quest);
+    if let Some(deadline) = quest.deadline {
+        let date = deadline.into_date();
+        let date = format!(
+            "{}/{:02}/{:02} {:02}:00",
+            date.year, date.month, date.day, date.hour
+        );
+        text.push('\n');
+        text.push_str(&misc_txt_format!("quest-deadline"; date=date));
+    }
+    text
+}
+
+fn quest_objective_text(quest: &Quest) -> String {
+    let item = match quest.objective {
+        QuestObjective::FetchItems { idx, .. }
+        | QuestObjective::Deliver { idx, .. }
+        | QuestObjective::Craft { idx, .. } => obj_txt(gobj::idx_to_id(idx)),
+        _ => String::new(),
+    };
+
+    match &quest.objective {
+        QuestObjective::SlayMonsters { idx, goal, .. } => {
             misc_txt_format!("desc-quest-slay_monsters"; monster=idx, n=goal)
         }
+        QuestObjective::FetchItems { n, .. } => {
+            misc_txt_format!("desc-quest-fetch_items"; item=item, n=n)
+        }
+        QuestObjective::Deliver { name, town, .. } => {
+            misc_txt_format!("desc-quest-deliver"; item=item, chara=name, site=town)
+        }
+        QuestObjective::Escort {
+            name, dest_name, ..
+        } => {
+            misc_txt_format!("desc-quest-escort"; chara=name, site=dest_name)
+        }
+        QuestObjective::ExploreDungeon { name, floor, .. } => {
+            misc_txt_format!("desc-quest-explore_dungeon"; site=name, n=floor)
+        }
+        QuestObjective::Craft { goal, .. } => {
+            misc_txt_format!("desc-quest-craft"; item=item, n=goal)
+        }
     }
 }
@@ -127,7 +127,7 @@ impl DialogWindow for ChooseSaveFileDialog {
         if let Some(response) = self.list.process_command(&command) {
             if let ListWidgetResponse::Select(i) = response {
                 // Any item is selected
-                match GameData::load(&self.save_files[i as usize]) {
+                match GameData::load(&self.save_files[i as usize], crate::config::CONFIG.fix_rand) {
                     Ok(o) => {
                         return DialogResult::Special(SpecialDialogResult::NewGameStart(Box::new(
                             o,
@@ -64,6 +64,7 @@ pub struct StatusWindow {
     name_label: LabelWidget,
     hp_label: LabelWidget,
     sp_label: LabelWidget,
+    mp_label: LabelWidget,
     faction_label: LabelWidget,
     str_label: LabelWidget,
     vit_label: LabelWidget,
@@ -96,6 +97,11 @@ impl StatusWindow {
             &format!("SP  {:2.0}", chara.sp),
             FontKind::MonoM,
         );
+        let mp_label = LabelWidget::new(
+            cfg.mp_label_rect,
+            &format!("MP  {} / {}", chara.mp, chara.attr.max_mp),
+            FontKind::MonoM,
+        );
         let faction_label = LabelWidget::new(
             cfg.faction_label_rect,
             &format!(
@@ -141,6 +147,7 @@ impl StatusWindow {
             name_label,
             hp_label,
             sp_label,
+            mp_label,
             faction_label,
             str_label,
             vit_label,
@@ -160,6 +167,7 @@ impl Window for StatusWindow {
         self.name_label.draw(context);
         self.hp_label.draw(context);
         self.sp_label.draw(context);
+        self.mp_label.draw(context);
         self.faction_label.draw(context);
         self.str_label.draw(context);
         self.vit_label.draw(context);
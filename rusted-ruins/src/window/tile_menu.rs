@@ -2,6 +2,7 @@ use super::commonuse::*;
 use crate::config::CONTROL_CFG;
 use crate::game::map::tile_info::*;
 use crate::game::{DialogOpenRequest, InfoGetter};
+use crate::text::ToText;
 use common::gamedata::*;
 use common::gobj;
 use geom::*;
@@ -59,6 +60,16 @@ pub fn create_menu(
                 }));
             }
         }
+        if let Some(sid) = crate::game::item::container::current_stash(&game.gd) {
+            text_ids.push("tile-menu-put-in-stash");
+            callbacks.push(Box::new(move |pa: &mut DoPlayerAction| {
+                pa.request_dialog_open(DialogOpenRequest::StashPutIn { sid });
+            }));
+            text_ids.push("tile-menu-take-out-from-stash");
+            callbacks.push(Box::new(move |pa: &mut DoPlayerAction| {
+                pa.request_dialog_open(DialogOpenRequest::StashTakeOut { sid });
+            }));
+        }
         if !game.gd.item_on_player_tile().is_empty() {
             text_ids.push("tile-menu-pick-up-items");
             callbacks.push(Box::new(move |pa: &mut DoPlayerAction| {
@@ -109,6 +120,43 @@ pub fn create_menu(
         }));
     }
 
+    // Party orders and hiring
+    if let Some(cid) = t.chara.filter(|&cid| cid != CharaId::Player) {
+        if let Some(order) = game.gd.party.order(cid) {
+            let orders = [
+                (PartyOrder::Follow, "tile-menu-party-follow"),
+                (PartyOrder::Wait, "tile-menu-party-wait"),
+                (PartyOrder::AttackTarget, "tile-menu-party-attack-target"),
+            ];
+            for &(o, text_id) in orders.iter().filter(|(o, _)| *o != order) {
+                text_ids.push(text_id);
+                callbacks.push(Box::new(move |pa: &mut DoPlayerAction| {
+                    pa.set_party_order(cid, o);
+                }));
+            }
+            text_ids.push("tile-menu-party-leave");
+            callbacks.push(Box::new(move |pa: &mut DoPlayerAction| {
+                pa.leave_party(cid);
+            }));
+        } else if tile.is_adjacent(player_pos) {
+            if let Some(cost) = crate::game::party::hire_cost(&game.gd, cid) {
+                let msg = ui_txt_format!("dialog-hire"; chara=game.gd.chara.get(cid), cost=cost);
+                text_ids.push("tile-menu-hire");
+                callbacks.push(Box::new(move |pa: &mut DoPlayerAction| {
+                    let callback = Box::new(move |pa: &mut DoPlayerAction, result: bool| {
+                        if result {
+                            pa.hire(cid);
+                        }
+                    });
+                    pa.request_dialog_open(DialogOpenRequest::YesNo {
+                        callback,
+                        msg: msg.clone(),
+                    });
+                }));
+            }
+        }
+    }
+
     if CONTROL_CFG.menu_centering {
         text_ids.push("tile-menu-start-centering");
         callbacks.push(Box::new(move |_| {
@@ -18,6 +18,7 @@ pub struct GaugeWidget {
 pub enum GaugeColorMode {
     Hp,
     Sp,
+    Mp,
     // Exp,
     Work,
 }
@@ -37,6 +38,12 @@ impl GaugeColorMode {
                 border_light: UI_CFG.color.border_light.into(),
                 border_dark: UI_CFG.color.border_dark.into(),
             },
+            GaugeColorMode::Mp => Colors {
+                bar: UI_CFG.color.gauge_mp.into(),
+                bg: UI_CFG.color.gauge_bg.into(),
+                border_light: UI_CFG.color.border_light.into(),
+                border_dark: UI_CFG.color.border_dark.into(),
+            },
             GaugeColorMode::Work => Colors {
                 bar: UI_CFG.color.gauge_work.into(),
                 bg: UI_CFG.color.gauge_bg.into(),
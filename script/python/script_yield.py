@@ -24,6 +24,9 @@ class ScriptYield:
     def quest():
         return {"tag": "Quest"}
 
+    def teleport(mid, pos=None):
+        return {"tag": "Teleport", "mid": mid, "pos": pos}
+
 
 def _get_next_script_yield():
     if not isinstance(_rrscript_gen, _GeneratorType):
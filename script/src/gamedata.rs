@@ -1,14 +1,15 @@
 //! Management codes for GameData
 
-use common::gamedata::{GameData, Value};
+use common::gamedata::{CharaId, GameData, Value};
+use geom::Vec2d;
 use once_cell::sync::Lazy;
 use once_cell::unsync::Lazy as UnsyncLazy;
 use rustpython_vm as vm;
 use std::cell::RefCell;
 use std::convert::TryInto;
 use std::sync::RwLock;
-use vm::builtins::PyInt;
-use vm::pyobject::{BorrowValue, IntoPyObject, PyObjectRef, PyResult};
+use vm::builtins::{PyDict, PyFloat, PyInt, PyList, PyStr};
+use vm::pyobject::{BorrowValue, IdProtocol, IntoPyObject, PyObjectRef, PyResult};
 use vm::VirtualMachine;
 
 thread_local!(
@@ -55,19 +56,83 @@ pub fn value_to_py(vm: &VirtualMachine, value: Value) -> PyObjectRef {
     match value {
         Value::Bool(value) => value.into_pyobject(vm),
         Value::Int(value) => value.into_pyobject(vm),
+        Value::Str(value) => value.into_pyobject(vm),
+        Value::Float(value) => value.into_pyobject(vm),
+        Value::List(list) => {
+            let list = list.into_iter().map(|v| value_to_py(vm, v)).collect();
+            vm.ctx.new_list(list)
+        }
+        Value::Dict(dict) => {
+            let pydict = vm.ctx.new_dict();
+            for (k, v) in dict.into_iter() {
+                pydict
+                    .set_item(k.as_str(), value_to_py(vm, v), vm)
+                    .expect("setting str key to dict failed");
+            }
+            pydict.into_object()
+        }
     }
 }
 
 pub fn py_to_value(vm: &VirtualMachine, pyvalue: PyObjectRef) -> PyResult<Value> {
-    let value = if let Some(i) = pyvalue.payload::<PyInt>() {
-        let i: i64 = i.borrow_value().try_into().unwrap();
+    // bool is a subclass of int, so it must be checked before int
+    let value = if pyvalue.is(&vm.ctx.true_value) {
+        Value::Bool(true)
+    } else if pyvalue.is(&vm.ctx.false_value) {
+        Value::Bool(false)
+    } else if let Some(i) = pyvalue.payload::<PyInt>() {
+        let i: i64 = i
+            .borrow_value()
+            .try_into()
+            .map_err(|_| vm.new_overflow_error(format!("too large int value \"{}\"", pyvalue)))?;
         Value::Int(i)
+    } else if let Some(s) = pyvalue.payload::<PyStr>() {
+        Value::Str(s.borrow_value().to_owned())
+    } else if let Some(f) = pyvalue.payload::<PyFloat>() {
+        Value::Float(f.to_f64())
+    } else if let Some(list) = pyvalue.payload::<PyList>() {
+        let list = list
+            .borrow_value()
+            .iter()
+            .map(|v| py_to_value(vm, v.clone()))
+            .collect::<PyResult<Vec<Value>>>()?;
+        Value::List(list)
+    } else if let Ok(dict) = pyvalue.clone().downcast::<PyDict>() {
+        let mut map = std::collections::BTreeMap::new();
+        for (k, v) in dict {
+            let k = if let Some(k) = k.payload::<PyStr>() {
+                k.borrow_value().to_owned()
+            } else {
+                return Err(vm.new_type_error(format!("dict key \"{}\" is not str", k)));
+            };
+            map.insert(k, py_to_value(vm, v)?);
+        }
+        Value::Dict(map)
     } else {
         return Err(vm.new_type_error(format!("invalid type value \"{}\" for set_gvar", pyvalue)));
     };
     Ok(value)
 }
 
+/// Convert a Rust value to Python object through serde
+pub fn serde_to_py<T: serde::Serialize>(vm: &VirtualMachine, value: &T) -> PyResult<PyObjectRef> {
+    let value = serde_json::to_value(value).map_err(|e| vm.new_value_error(e.to_string()))?;
+    vm::py_serde::deserialize(vm, value).map_err(|e| vm.new_value_error(e.to_string()))
+}
+
+/// Convert a Python object to Rust value through serde
+pub fn py_to_serde<T: serde::de::DeserializeOwned>(
+    vm: &VirtualMachine,
+    pyvalue: &PyObjectRef,
+) -> PyResult<T> {
+    let mut buf = Vec::new();
+    let mut serializer = serde_json::ser::Serializer::new(&mut buf);
+    vm::py_serde::serialize(vm, pyvalue, &mut serializer)
+        .map_err(|e| vm.new_value_error(e.to_string()))?;
+    serde_json::from_slice(&buf)
+        .map_err(|e| vm.new_value_error(format!("invalid value \"{}\": {}", pyvalue, e)))
+}
+
 pub(crate) static GAME_METHODS: Lazy<RwLock<Option<GameMethods>>> = Lazy::new(|| RwLock::new(None));
 
 pub fn set_game_methods(game_methods: GameMethods) {
@@ -79,9 +144,15 @@ pub struct GameMethods {
     pub has_item: fn(&GameData, &str) -> Option<u32>,
     pub gen_dungeons: fn(&mut GameData),
     pub receive_quest_rewards: fn(&mut GameData) -> bool,
+    pub start_quest: fn(&mut GameData, &str) -> bool,
     pub receive_item: fn(&mut GameData, &str, u32),
     pub receive_money: fn(&mut GameData, u32),
     pub remove_item: fn(&mut GameData, &str, u32) -> Result<(), ()>,
+    pub update_chara: fn(&mut GameData, CharaId),
+    pub spawn_chara: fn(&mut GameData, &str, Option<Vec2d>) -> Option<CharaId>,
+    pub join_party: fn(&mut GameData, CharaId) -> Option<CharaId>,
+    pub leave_party: fn(&mut GameData, CharaId) -> Option<CharaId>,
+    pub log: fn(&str, &[(String, String)]),
 }
 
 macro_rules! call_game_method {
@@ -94,3 +165,23 @@ macro_rules! call_game_method {
             .$name)
     };
 }
+
+#[test]
+fn py_to_value_bool_test() {
+    use vm::{InitParameter, PySettings};
+
+    vm::Interpreter::new_with_init(PySettings::default(), |_| InitParameter::Internal).enter(
+        |vm| {
+            let to_value = |pyvalue: PyObjectRef| py_to_value(vm, pyvalue).unwrap();
+
+            // bool must not be read as int
+            assert_eq!(to_value(true.into_pyobject(vm)), Value::Bool(true));
+            assert_eq!(to_value(false.into_pyobject(vm)), Value::Bool(false));
+            assert_eq!(to_value(1i64.into_pyobject(vm)), Value::Int(1));
+            assert_eq!(to_value(0i64.into_pyobject(vm)), Value::Int(0));
+
+            let list = Value::List(vec![Value::Bool(true), Value::Int(1), Value::Bool(false)]);
+            assert_eq!(to_value(value_to_py(vm, list.clone())), list);
+        },
+    );
+}
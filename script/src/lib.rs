@@ -1,6 +1,7 @@
 #[macro_use]
 extern crate log;
 extern crate rusted_ruins_common as common;
+extern crate rusted_ruins_geom as geom;
 
 mod engine;
 mod error;
@@ -6,11 +6,20 @@ pub(crate) use _rr::make_module;
 
 #[pymodule(name = "rr")]
 mod _rr {
-    use crate::gamedata::{py_to_value, value_to_py, with_gd, with_gd_mut};
+    use crate::gamedata::{
+        py_to_serde, py_to_value, serde_to_py, value_to_py, with_gd, with_gd_mut,
+    };
+    use common::gamedata::{
+        CharaId, FactionId, FactionRelation, Quest, QuestObjective, Reward, SkillKind,
+    };
+    use common::gobj;
+    use common::objholder::CharaTemplateIdx;
+    use geom::Vec2d;
     use rustpython_vm as vm;
     use std::convert::TryInto;
-    use vm::builtins::{PyNone, PyStrRef};
-    use vm::pyobject::{PyObjectRef, PyResult, PyValue};
+    use vm::builtins::{PyDictRef, PyNone, PyStrRef};
+    use vm::function::OptionalArg;
+    use vm::pyobject::{BorrowValue, PyObjectRef, PyResult, PyValue};
     use vm::VirtualMachine;
 
     #[pyfunction]
@@ -18,6 +27,16 @@ mod _rr {
         with_gd(|gd| gd.script_exec.yield_result.clone()).map(|value| value_to_py(vm, value))
     }
 
+    /// Returns details of the event if the current script is started by a hook
+    #[pyfunction]
+    fn event(vm: &VirtualMachine) -> PyResult {
+        if let Some(event) = with_gd(|gd| gd.script_exec.event.clone()) {
+            serde_to_py(vm, &event)
+        } else {
+            Ok(vm.ctx.none())
+        }
+    }
+
     #[pyfunction]
     fn self_id() -> String {
         with_gd(|gd| gd.script_exec.current_script_id.clone().unwrap())
@@ -123,4 +142,233 @@ mod _rr {
             Ok(())
         })
     }
+
+    #[pyfunction]
+    fn get_player_stat(name: PyStrRef, vm: &VirtualMachine) -> PyResult<i64> {
+        let name: &str = name.as_ref();
+        with_gd(|gd| {
+            let player = gd.chara.get(CharaId::Player);
+            let attr = &player.attr;
+            Ok(match name {
+                "level" => player.level.into(),
+                "hp" => player.hp.into(),
+                "max_hp" => attr.max_hp.into(),
+                "sp" => player.sp as i64,
+                "mp" => player.mp.into(),
+                "max_mp" => attr.max_mp.into(),
+                "str" => attr.str.into(),
+                "vit" => attr.vit.into(),
+                "dex" => attr.dex.into(),
+                "int" => attr.int.into(),
+                "wil" => attr.wil.into(),
+                "cha" => attr.cha.into(),
+                "spd" => attr.spd.into(),
+                _ => return Err(vm.new_value_error(format!("unknown stat \"{}\"", name))),
+            })
+        })
+    }
+
+    /// Set player's level, hp, sp or mp. Other stats are calculated from them.
+    #[pyfunction]
+    fn set_player_stat(name: PyStrRef, value: i64, vm: &VirtualMachine) -> PyResult<()> {
+        let name: &str = name.as_ref();
+        with_gd_mut(|gd| {
+            let player = gd.chara.get_mut(CharaId::Player);
+            match name {
+                "level" => {
+                    player.level =
+                        value.try_into().ok().filter(|&lv| lv > 0).ok_or_else(|| {
+                            vm.new_value_error(format!("invalid level {}", value))
+                        })?;
+                }
+                "hp" => {
+                    player.hp = value.clamp(1, player.attr.max_hp.into()) as i32;
+                }
+                "sp" => {
+                    player.sp = value as f32;
+                }
+                "mp" => {
+                    player.mp = value.clamp(0, player.attr.max_mp.into()) as i32;
+                }
+                _ => {
+                    return Err(vm.new_value_error(format!("cannot set stat \"{}\"", name)));
+                }
+            }
+            call_game_method!(update_chara)(gd, CharaId::Player);
+            Ok(())
+        })
+    }
+
+    #[pyfunction]
+    fn get_skill(kind: PyStrRef, vm: &VirtualMachine) -> PyResult<u32> {
+        let kind = parse_skill_kind(kind.as_ref(), vm)?;
+        Ok(with_gd(|gd| gd.chara.get(CharaId::Player).skills.get(kind)))
+    }
+
+    #[pyfunction]
+    fn set_skill(kind: PyStrRef, lv: u32, vm: &VirtualMachine) -> PyResult<()> {
+        let kind = parse_skill_kind(kind.as_ref(), vm)?;
+        with_gd_mut(|gd| {
+            let skills = &mut gd.chara.get_mut(CharaId::Player).skills;
+            skills.skills.insert(kind, lv);
+            if let Some(exp) = skills.exp.as_mut() {
+                exp.entry(kind).or_insert(0);
+            }
+            call_game_method!(update_chara)(gd, CharaId::Player);
+        });
+        Ok(())
+    }
+
+    fn parse_skill_kind(s: &str, vm: &VirtualMachine) -> PyResult<SkillKind> {
+        s.parse::<SkillKind>()
+            .map_err(|e| vm.new_value_error(e.to_string()))
+    }
+
+    /// Spawn a character on the current map.
+    /// If the position is not given, an empty tile is chosen randomly.
+    #[pyfunction]
+    fn spawn_chara(
+        id: PyStrRef,
+        x: OptionalArg<i32>,
+        y: OptionalArg<i32>,
+        vm: &VirtualMachine,
+    ) -> PyResult {
+        let pos = match (x.into_option(), y.into_option()) {
+            (Some(x), Some(y)) => Some(Vec2d(x, y)),
+            (None, None) => None,
+            _ => return Err(vm.new_type_error("both x and y are needed".into())),
+        };
+        if let Some(cid) = with_gd_mut(|gd| call_game_method!(spawn_chara)(gd, id.as_ref(), pos)) {
+            serde_to_py(vm, &cid)
+        } else {
+            Ok(vm.ctx.none())
+        }
+    }
+
+    #[pyfunction]
+    fn remove_chara(cid: PyObjectRef, vm: &VirtualMachine) -> PyResult<()> {
+        let cid: CharaId = py_to_serde(vm, &cid)?;
+        with_gd_mut(|gd| {
+            if cid == CharaId::Player {
+                return Err(vm.new_value_error("cannot remove the player".into()));
+            }
+            if gd.get_current_map().chara_pos(cid).is_none() {
+                return Err(vm.new_value_error(format!("{:?} is not on the current map", cid)));
+            }
+            gd.remove_chara(cid);
+            Ok(())
+        })
+    }
+
+    /// Make the talking character, or the given character a party member.
+    /// Returns the new id of the character, or None if the character cannot join.
+    #[pyfunction]
+    fn join_party(cid: OptionalArg<PyObjectRef>, vm: &VirtualMachine) -> PyResult {
+        let cid = cid_or_target(cid, vm)?;
+        if let Some(cid) = with_gd_mut(|gd| call_game_method!(join_party)(gd, cid)) {
+            serde_to_py(vm, &cid)
+        } else {
+            Ok(vm.ctx.none())
+        }
+    }
+
+    /// Make a party member leave the party.
+    /// Returns the new id of the character, or None if the character is not in the party.
+    #[pyfunction]
+    fn leave_party(cid: OptionalArg<PyObjectRef>, vm: &VirtualMachine) -> PyResult {
+        let cid = cid_or_target(cid, vm)?;
+        if let Some(cid) = with_gd_mut(|gd| call_game_method!(leave_party)(gd, cid)) {
+            serde_to_py(vm, &cid)
+        } else {
+            Ok(vm.ctx.none())
+        }
+    }
+
+    /// Returns the ids of party members
+    #[pyfunction]
+    fn party_members(vm: &VirtualMachine) -> PyResult {
+        let members: Vec<CharaId> = with_gd(|gd| gd.party.iter().map(|m| m.cid).collect());
+        serde_to_py(vm, &members)
+    }
+
+    fn cid_or_target(cid: OptionalArg<PyObjectRef>, vm: &VirtualMachine) -> PyResult<CharaId> {
+        if let OptionalArg::Present(cid) = cid {
+            py_to_serde(vm, &cid)
+        } else {
+            with_gd(|gd| gd.script_exec.target_chara)
+                .ok_or_else(|| vm.new_value_error("no target character".into()))
+        }
+    }
+
+    /// Returns the current map id. It can be used as the destination of teleport.
+    #[pyfunction]
+    fn current_map_id(vm: &VirtualMachine) -> PyResult {
+        serde_to_py(vm, &with_gd(|gd| gd.get_current_mapid()))
+    }
+
+    #[pyfunction]
+    fn get_faction_relation(faction: PyStrRef, vm: &VirtualMachine) -> PyResult<i16> {
+        let faction = parse_faction_id(faction.as_ref(), vm)?;
+        Ok(with_gd(|gd| gd.faction.get(faction).value()))
+    }
+
+    #[pyfunction]
+    fn set_faction_relation(faction: PyStrRef, value: i16, vm: &VirtualMachine) -> PyResult<()> {
+        let faction = parse_faction_id(faction.as_ref(), vm)?;
+        with_gd_mut(|gd| gd.faction.set(faction, FactionRelation::new(value)));
+        Ok(())
+    }
+
+    fn parse_faction_id(s: &str, vm: &VirtualMachine) -> PyResult<FactionId> {
+        FactionId::new(s).ok_or_else(|| vm.new_value_error(format!("invalid faction id \"{}\"", s)))
+    }
+
+    /// Start the quest defined in rules with the given id.
+    /// Returns false if the quest cannot be started.
+    #[pyfunction]
+    fn start_quest(id: PyStrRef) -> bool {
+        with_gd_mut(|gd| call_game_method!(start_quest)(gd, id.as_ref()))
+    }
+
+    #[pyfunction]
+    fn start_slay_monsters_quest(
+        id: PyStrRef,
+        goal: u32,
+        money: i64,
+        vm: &VirtualMachine,
+    ) -> PyResult<()> {
+        let id: &str = id.as_ref();
+        let idx: CharaTemplateIdx = gobj::id_to_idx_checked(id)
+            .ok_or_else(|| vm.new_value_error(format!("unknown chara template \"{}\"", id)))?;
+        let quest = Quest {
+            id: String::new(),
+            reward: Reward {
+                money,
+                item: Vec::new(),
+            },
+            objective: QuestObjective::SlayMonsters {
+                idx,
+                goal,
+                killed: 0,
+            },
+            deadline: None,
+        };
+        with_gd_mut(|gd| gd.quest.start_new_quest(quest));
+        Ok(())
+    }
+
+    /// Write a message to the game log. Arguments are given to the text as strings.
+    #[pyfunction]
+    fn log(text_id: PyStrRef, args: OptionalArg<PyDictRef>, vm: &VirtualMachine) -> PyResult<()> {
+        let mut log_args = Vec::new();
+        if let OptionalArg::Present(args) = args {
+            for (k, v) in args {
+                let k = vm.to_str(&k)?;
+                let v = vm.to_str(&v)?;
+                log_args.push((k.borrow_value().to_owned(), v.borrow_value().to_owned()));
+            }
+        }
+        call_game_method!(log)(text_id.as_ref(), &log_args);
+        Ok(())
+    }
 }
@@ -1,3 +1,5 @@
+use common::gamedata::MapId;
+use geom::Vec2d;
 use serde_derive::{Deserialize, Serialize};
 
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
@@ -7,6 +9,7 @@ pub enum ScriptYield {
     ShopBuy,
     ShopSell,
     Quest,
+    Teleport { mid: MapId, pos: Option<Vec2d> },
 }
 
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]